@@ -2,6 +2,7 @@ use criterion::{
     black_box,
     criterion_group,
     criterion_main,
+    BatchSize,
     Criterion,
 };
 use fuel_merkle::{
@@ -62,6 +63,43 @@ where
     in_memory::MerkleTree::nodes_from_set(set).0
 }
 
+// Naive batch update: Updates the loaded Merkle tree sequentially.
+pub fn baseline_update_root<D>(
+    mut tree: in_memory::MerkleTree,
+    batch: &[(MerkleTreeKey, D)],
+) -> Bytes32
+where
+    D: AsRef<[u8]>,
+{
+    for (key, data) in batch {
+        tree.update(*key, data.as_ref());
+    }
+    tree.root()
+}
+
+pub fn subject_update_root<D>(
+    mut tree: in_memory::MerkleTree,
+    batch: &[(MerkleTreeKey, D)],
+) -> Bytes32
+where
+    D: AsRef<[u8]>,
+{
+    tree.update_batch(batch.iter().map(|(key, data)| (*key, data)));
+    tree.root()
+}
+
+pub fn subject_update_parallel_root<D>(
+    mut tree: in_memory::MerkleTree,
+    batch: &[(MerkleTreeKey, D)],
+    threads: usize,
+) -> Bytes32
+where
+    D: AsRef<[u8]>,
+{
+    tree.update_batch_parallel(batch.iter().map(|(key, data)| (*key, data)), threads);
+    tree.root()
+}
+
 fn sparse_merkle_tree(c: &mut Criterion) {
     use rand::{
         rngs::StdRng,
@@ -102,5 +140,63 @@ fn sparse_merkle_tree(c: &mut Criterion) {
     group_update.finish();
 }
 
-criterion_group!(benches, sparse_merkle_tree);
+fn sparse_merkle_tree_update_batch(c: &mut Criterion) {
+    use rand::{
+        rngs::StdRng,
+        SeedableRng,
+    };
+
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let gen = || Some((MerkleTreeKey::new(random_bytes32(rng)), random_bytes32(rng)));
+    let data = core::iter::from_fn(gen).take(10_000).collect::<Vec<_>>();
+    // Updates a tenth of the leaves and inserts as many new ones.
+    let mut batch = data
+        .iter()
+        .step_by(10)
+        .map(|(key, _)| (*key, random_bytes32(rng)))
+        .collect::<Vec<_>>();
+    let gen = || Some((MerkleTreeKey::new(random_bytes32(rng)), random_bytes32(rng)));
+    batch.extend(core::iter::from_fn(gen).take(1_000));
+    let threads = std::thread::available_parallelism()
+        .map(usize::from)
+        .unwrap_or(1);
+    let tree = || in_memory::MerkleTree::from_set(data.clone().into_iter());
+
+    let expected_root = baseline_update_root(tree(), &batch);
+    let root = subject_update_root(tree(), &batch);
+    let parallel_root = subject_update_parallel_root(tree(), &batch, threads);
+
+    assert_eq!(expected_root, root);
+    assert_eq!(expected_root, parallel_root);
+
+    let mut group_update = c.benchmark_group("update-batch");
+
+    group_update.bench_with_input("update-batch", &batch, |b, batch| {
+        b.iter_batched(
+            tree,
+            |tree| subject_update_root(tree, black_box(batch)),
+            BatchSize::LargeInput,
+        );
+    });
+
+    group_update.bench_with_input("update-batch-parallel", &batch, |b, batch| {
+        b.iter_batched(
+            tree,
+            |tree| subject_update_parallel_root(tree, black_box(batch), threads),
+            BatchSize::LargeInput,
+        );
+    });
+
+    group_update.bench_with_input("update-batch-baseline", &batch, |b, batch| {
+        b.iter_batched(
+            tree,
+            |tree| baseline_update_root(tree, black_box(batch)),
+            BatchSize::LargeInput,
+        );
+    });
+
+    group_update.finish();
+}
+
+criterion_group!(benches, sparse_merkle_tree, sparse_merkle_tree_update_batch);
 criterion_main!(benches);
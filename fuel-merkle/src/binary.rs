@@ -17,4 +17,8 @@ pub use merkle_tree::{
 };
 pub use primitive::Primitive;
 pub mod in_memory;
-pub use verify::verify;
+pub use verify::{
+    verify,
+    verify_consistency,
+    verify_many,
+};
@@ -51,6 +51,18 @@ impl MerkleTree {
         self.tree.prove(proof_index).ok()
     }
 
+    pub fn prove_many(&self, proof_indices: &[u64]) -> Option<(Bytes32, ProofSet)> {
+        self.tree.prove_many(proof_indices).ok()
+    }
+
+    pub fn root_at(&self, leaves_count: u64) -> Option<Bytes32> {
+        self.tree.root_at(leaves_count).ok()
+    }
+
+    pub fn prove_consistency(&self, leaves_count: u64) -> Option<ProofSet> {
+        self.tree.prove_consistency(leaves_count).ok()
+    }
+
     pub fn reset(&mut self) {
         self.tree.reset();
     }
@@ -265,4 +277,94 @@ mod test {
             assert_eq!(proof_set[1], node_3);
         }
     }
+
+    #[test]
+    fn prove_many_returns_the_merkle_root_and_shared_proof_set_for_7_leaves() {
+        let mut tree = MerkleTree::new();
+
+        let data = &TEST_DATA[0..7]; // 7 leaves
+        for datum in data.iter() {
+            tree.push(datum);
+        }
+
+        let leaf_0 = leaf_sum(data[0]);
+        let leaf_1 = leaf_sum(data[1]);
+        let leaf_2 = leaf_sum(data[2]);
+        let leaf_3 = leaf_sum(data[3]);
+        let leaf_4 = leaf_sum(data[4]);
+        let leaf_5 = leaf_sum(data[5]);
+        let leaf_6 = leaf_sum(data[6]);
+
+        let node_1 = node_sum(&leaf_0, &leaf_1);
+        let node_5 = node_sum(&leaf_2, &leaf_3);
+        let node_3 = node_sum(&node_1, &node_5);
+        let node_9 = node_sum(&leaf_4, &leaf_5);
+        let node_11 = node_sum(&node_9, &leaf_6);
+        let node_7 = node_sum(&node_3, &node_11);
+
+        {
+            let (root, proof_set) = tree.prove_many(&[0, 1]).unwrap();
+            assert_eq!(root, node_7);
+            assert_eq!(proof_set, vec![node_5, node_11]);
+        }
+        {
+            let (root, proof_set) = tree.prove_many(&[4, 0]).unwrap();
+            assert_eq!(root, node_7);
+            assert_eq!(proof_set, vec![leaf_1, node_5, leaf_5, leaf_6]);
+        }
+        {
+            let (root, proof_set) = tree.prove_many(&[0, 1, 2, 3, 4, 5, 6]).unwrap();
+            assert_eq!(root, node_7);
+            assert!(proof_set.is_empty());
+        }
+        {
+            let proof = tree.prove_many(&[0, 7]);
+            assert!(proof.is_none());
+        }
+    }
+
+    #[test]
+    fn root_at_returns_the_merkle_roots_of_the_previous_trees() {
+        let mut tree = MerkleTree::new();
+        let mut roots = vec![tree.root()];
+
+        let data = &TEST_DATA[0..7]; // 7 leaves
+        for datum in data.iter() {
+            tree.push(datum);
+            roots.push(tree.root());
+        }
+
+        for (leaves_count, root) in roots.iter().enumerate() {
+            assert_eq!(tree.root_at(leaves_count as u64), Some(*root));
+        }
+        assert!(tree.root_at(8).is_none());
+    }
+
+    #[test]
+    fn prove_consistency_returns_the_consistency_proof_for_3_of_7_leaves() {
+        let mut tree = MerkleTree::new();
+
+        let data = &TEST_DATA[0..7]; // 7 leaves
+        for datum in data.iter() {
+            tree.push(datum);
+        }
+
+        let leaf_0 = leaf_sum(data[0]);
+        let leaf_1 = leaf_sum(data[1]);
+        let leaf_2 = leaf_sum(data[2]);
+        let leaf_3 = leaf_sum(data[3]);
+        let leaf_4 = leaf_sum(data[4]);
+        let leaf_5 = leaf_sum(data[5]);
+        let leaf_6 = leaf_sum(data[6]);
+
+        let node_1 = node_sum(&leaf_0, &leaf_1);
+        let node_9 = node_sum(&leaf_4, &leaf_5);
+        let node_11 = node_sum(&node_9, &leaf_6);
+
+        let proof_set = tree.prove_consistency(3).unwrap();
+        assert_eq!(proof_set, vec![leaf_2, leaf_3, node_1, node_11]);
+
+        assert!(tree.prove_consistency(0).is_none());
+        assert!(tree.prove_consistency(8).is_none());
+    }
 }
@@ -2,6 +2,8 @@ use crate::{
     binary::{
         empty_sum,
         in_memory::NodesTable,
+        node_sum,
+        verify::MultiProofPositions,
         Node,
         Primitive,
     },
@@ -29,6 +31,9 @@ pub enum MerkleTreeError<StorageError> {
     #[display(fmt = "proof index {_0} is not valid")]
     InvalidProofIndex(u64),
 
+    #[display(fmt = "leaves count {_0} is not valid")]
+    InvalidLeavesCount(u64),
+
     #[display(fmt = "cannot load node with key {_0}; the key is not found in storage")]
     LoadError(u64),
 
@@ -200,6 +205,93 @@ where
         Ok((root, proof_set))
     }
 
+    /// Prove several leaves at once. The proof set holds each side node of the
+    /// leaves once, from left to right, except the nodes derived from the proven
+    /// leaves. It is verified with [`verify_many`](crate::binary::verify_many).
+    pub fn prove_many(
+        &self,
+        proof_indices: &[u64],
+    ) -> Result<(Bytes32, ProofSet), MerkleTreeError<StorageError>> {
+        if let Some(index) = proof_indices
+            .iter()
+            .find(|index| **index >= self.leaves_count)
+        {
+            return Err(MerkleTreeError::InvalidProofIndex(*index))
+        }
+
+        if proof_indices.is_empty() {
+            return Ok((self.root(), ProofSet::new()))
+        }
+
+        let mut proof_set = ProofSet::new();
+
+        let positions =
+            MultiProofPositions::new(proof_indices.iter().copied(), self.leaves_count)
+                .expect("The tree can't have enough leaves to overflow the root index");
+
+        // Allocate scratch storage to store temporary nodes when building the
+        // root.
+        let mut scratch_storage = StorageMap::<NodesTable>::new();
+        let root_node = self
+            .root_node(&mut scratch_storage)
+            .expect("Root node must be present");
+
+        // Get side nodes from the scratch storage or the main storage, as in
+        // `prove`.
+        for side_position in positions.side_positions {
+            let key = side_position.in_order_index();
+            let primitive = StorageInspectInfallible::get(&scratch_storage, &key)
+                .or(StorageInspect::get(&self.storage, &key)?)
+                .ok_or(MerkleTreeError::LoadError(key))?
+                .into_owned();
+            let node = Node::from(primitive);
+            proof_set.push(*node.hash());
+        }
+
+        let root = *root_node.hash();
+        Ok((root, proof_set))
+    }
+
+    /// The root of the tree when it had `leaves_count` leaves.
+    pub fn root_at(
+        &self,
+        leaves_count: u64,
+    ) -> Result<Bytes32, MerkleTreeError<StorageError>> {
+        if leaves_count > self.leaves_count {
+            return Err(MerkleTreeError::InvalidLeavesCount(leaves_count))
+        }
+
+        if leaves_count == 0 {
+            return Ok(*Self::empty_root())
+        }
+
+        self.range_root(0, leaves_count)
+    }
+
+    /// Prove that the tree of `leaves_count` leaves is a prefix of the current
+    /// tree. The proof set is the consistency proof of
+    /// [RFC 6962](https://www.rfc-editor.org/rfc/rfc6962#section-2.1.2), and is
+    /// verified with [`verify_consistency`](crate::binary::verify_consistency).
+    pub fn prove_consistency(
+        &self,
+        leaves_count: u64,
+    ) -> Result<ProofSet, MerkleTreeError<StorageError>> {
+        if leaves_count == 0 || leaves_count > self.leaves_count {
+            return Err(MerkleTreeError::InvalidLeavesCount(leaves_count))
+        }
+
+        let mut proof_set = ProofSet::new();
+        self.consistency_subproof(
+            leaves_count,
+            0,
+            self.leaves_count,
+            true,
+            &mut proof_set,
+        )?;
+
+        Ok(proof_set)
+    }
+
     pub fn reset(&mut self) {
         self.leaves_count = 0;
         self.head = None;
@@ -306,6 +398,84 @@ where
 
         Ok(())
     }
+
+    /// The consistency proof of the `old_leaves_count` first leaves of the leaves
+    /// `start..end`, following the `SUBPROOF` of RFC 6962. `complete` is set while
+    /// the old leaves are the first leaves of the whole tree.
+    fn consistency_subproof(
+        &self,
+        old_leaves_count: u64,
+        start: u64,
+        end: u64,
+        complete: bool,
+        proof_set: &mut ProofSet,
+    ) -> Result<(), MerkleTreeError<StorageError>> {
+        let leaves_count = end - start;
+        if old_leaves_count == leaves_count {
+            if !complete {
+                proof_set.push(self.range_root(start, end)?);
+            }
+            return Ok(())
+        }
+
+        // The leaves are split at the largest power of two lower than their count.
+        let split = 1 << (u64::BITS - 1 - (leaves_count - 1).leading_zeros());
+        if old_leaves_count <= split {
+            self.consistency_subproof(
+                old_leaves_count,
+                start,
+                start + split,
+                complete,
+                proof_set,
+            )?;
+            proof_set.push(self.range_root(start + split, end)?);
+        } else {
+            self.consistency_subproof(
+                old_leaves_count - split,
+                start + split,
+                end,
+                false,
+                proof_set,
+            )?;
+            proof_set.push(self.range_root(start, start + split)?);
+        }
+
+        Ok(())
+    }
+
+    /// The root of the leaves `start..end`. `start` must be a multiple of the
+    /// largest power of two not greater than the leaves count: the leaves are
+    /// then covered by balanced subtrees of descending heights, persisted in
+    /// storage, and joined as the MMR peaks of a tree.
+    fn range_root(
+        &self,
+        start: u64,
+        end: u64,
+    ) -> Result<Bytes32, MerkleTreeError<StorageError>> {
+        let mut peaks = Vec::new();
+        let mut leaf_index = start;
+        while leaf_index < end {
+            let height = u64::BITS - 1 - (end - leaf_index).leading_zeros();
+            let subtree_leaves_count = 1 << height;
+            // The head of a balanced subtree lies halfway between its first and
+            // last leaves in in-order indices.
+            let key = Position::from_leaf_index(leaf_index).in_order_index()
+                + subtree_leaves_count
+                - 1;
+            let primitive = StorageInspect::get(&self.storage, &key)?
+                .ok_or(MerkleTreeError::LoadError(key))?
+                .into_owned();
+            peaks.push(*Node::from(primitive).hash());
+            leaf_index += subtree_leaves_count;
+        }
+
+        let mut root = peaks.pop().expect("Leaves count must be greater than 0");
+        while let Some(peak) = peaks.pop() {
+            root = node_sum(&peak, &root);
+        }
+
+        Ok(root)
+    }
 }
 
 impl<TableType, StorageType, StorageError> MerkleTree<TableType, StorageType>
@@ -628,6 +798,73 @@ mod test {
         assert!(matches!(err, MerkleTreeError::InvalidProofIndex(10)))
     }
 
+    #[test]
+    fn prove_many_returns_invalid_proof_index_error_when_an_index_is_greater_than_number_of_leaves(
+    ) {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        let data = &TEST_DATA[0..5]; // 5 leaves
+        for datum in data.iter() {
+            let _ = tree.push(datum);
+        }
+
+        let err = tree
+            .prove_many(&[1, 10, 3])
+            .expect_err("Expected prove_many() to return Error; got Ok");
+        assert!(matches!(err, MerkleTreeError::InvalidProofIndex(10)))
+    }
+
+    #[test]
+    fn root_at_returns_the_merkle_roots_of_the_prefixes_of_a_loaded_tree() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        let mut roots = vec![tree.root()];
+        for datum in TEST_DATA.iter() {
+            let _ = tree.push(datum);
+            roots.push(tree.root());
+        }
+
+        let leaves_count = TEST_DATA.len() as u64;
+        let tree = MerkleTree::load(&mut storage_map, leaves_count).unwrap();
+        for (leaves_count, root) in roots.iter().enumerate() {
+            assert_eq!(tree.root_at(leaves_count as u64).unwrap(), *root);
+        }
+    }
+
+    #[test]
+    fn root_at_and_prove_consistency_return_invalid_leaves_count_error_for_larger_trees()
+    {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        let data = &TEST_DATA[0..5]; // 5 leaves
+        for datum in data.iter() {
+            let _ = tree.push(datum);
+        }
+
+        let err = tree
+            .root_at(6)
+            .expect_err("Expected root_at() to return Error; got Ok");
+        assert!(matches!(err, MerkleTreeError::InvalidLeavesCount(6)));
+
+        let err = tree
+            .prove_consistency(6)
+            .expect_err("Expected prove_consistency() to return Error; got Ok");
+        assert!(matches!(err, MerkleTreeError::InvalidLeavesCount(6)));
+    }
+
+    #[test]
+    fn prove_many_returns_the_root_and_an_empty_proof_set_for_no_indices() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let tree = MerkleTree::new(&mut storage_map);
+
+        let (root, proof_set) = tree.prove_many(&[]).unwrap();
+        assert_eq!(root, empty_sum().clone());
+        assert!(proof_set.is_empty());
+    }
+
     #[test]
     fn prove_returns_the_merkle_root_and_proof_set_for_1_leaf() {
         let mut storage_map = StorageMap::<TestTable>::new();
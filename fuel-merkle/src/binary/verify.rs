@@ -5,10 +5,19 @@ use crate::{
     },
     common::{
         Bytes32,
+        Position,
         ProofSet,
     },
 };
 
+use alloc::{
+    collections::{
+        BTreeMap,
+        BTreeSet,
+    },
+    vec::Vec,
+};
+
 pub fn verify<T: AsRef<[u8]>>(
     root: &Bytes32,
     data: &T,
@@ -71,15 +80,208 @@ pub fn verify<T: AsRef<[u8]>>(
     sum == *root
 }
 
+/// Verify a multi-proof of several leaves against the root of a binary Merkle
+/// tree of `num_leaves` leaves.
+///
+/// `leaves` holds the index and data of each proven leaf, and `proof_set` the
+/// side nodes returned by
+/// [`MerkleTree::prove_many`](crate::binary::MerkleTree::prove_many) for these indices.
+pub fn verify_many<T: AsRef<[u8]>>(
+    root: &Bytes32,
+    leaves: &[(u64, T)],
+    proof_set: &ProofSet,
+    num_leaves: u64,
+) -> bool {
+    if leaves.is_empty() {
+        return false
+    }
+
+    let mut sums = BTreeMap::new();
+    for (index, data) in leaves {
+        if *index >= num_leaves {
+            return false
+        }
+        let position = Position::from_leaf_index(*index);
+        if sums
+            .insert(position.in_order_index(), leaf_sum(data.as_ref()))
+            .is_some()
+        {
+            return false
+        }
+    }
+
+    let indices = leaves.iter().map(|(index, _)| *index);
+    let Some(positions) = MultiProofPositions::new(indices, num_leaves) else {
+        return false
+    };
+    if positions.side_positions.len() != proof_set.len() {
+        return false
+    }
+    for (position, side) in positions.side_positions.iter().zip(proof_set) {
+        sums.insert(position.in_order_index(), *side);
+    }
+
+    positions.sum(positions.root, &mut sums).as_ref() == Some(root)
+}
+
+/// Verify that the tree of `old_leaves_count` leaves with root `old_root` is a
+/// prefix of the tree of `leaves_count` leaves with root `root`.
+///
+/// `proof_set` is the consistency proof returned by
+/// [`MerkleTree::prove_consistency`](crate::binary::MerkleTree::prove_consistency),
+/// checked as in [RFC 9162](https://www.rfc-editor.org/rfc/rfc9162#section-2.1.4.2).
+pub fn verify_consistency(
+    old_root: &Bytes32,
+    old_leaves_count: u64,
+    root: &Bytes32,
+    leaves_count: u64,
+    proof_set: &ProofSet,
+) -> bool {
+    if old_leaves_count == 0 || old_leaves_count > leaves_count {
+        return false
+    }
+
+    if old_leaves_count == leaves_count {
+        return proof_set.is_empty() && old_root == root
+    }
+
+    // The old root is the first node of the proof when the old tree is a
+    // balanced subtree of the new one.
+    let mut proof = proof_set.iter();
+    let first = if old_leaves_count.is_power_of_two() {
+        *old_root
+    } else {
+        match proof.next() {
+            Some(first) => *first,
+            None => return false,
+        }
+    };
+
+    let mut node_index = old_leaves_count - 1;
+    let mut last_index = leaves_count - 1;
+    while node_index & 1 == 1 {
+        node_index >>= 1;
+        last_index >>= 1;
+    }
+
+    let mut old_sum = first;
+    let mut sum = first;
+    for proof_data in proof {
+        if last_index == 0 {
+            return false
+        }
+
+        if node_index & 1 == 1 || node_index == last_index {
+            old_sum = node_sum(proof_data, &old_sum);
+            sum = node_sum(proof_data, &sum);
+            while node_index & 1 == 0 && node_index != 0 {
+                node_index >>= 1;
+                last_index >>= 1;
+            }
+        } else {
+            sum = node_sum(&sum, proof_data);
+        }
+
+        node_index >>= 1;
+        last_index >>= 1;
+    }
+
+    old_sum == *old_root && sum == *root && last_index == 0
+}
+
+/// The positions involved in a multi-proof of a set of leaves.
+pub(crate) struct MultiProofPositions {
+    /// The position of the root of the tree.
+    pub root: Position,
+    /// The side positions whose nodes make up the proof set, from left to right.
+    /// The side nodes on the path of another proven leaf are derived from the
+    /// leaves and omitted.
+    pub side_positions: Vec<Position>,
+    /// The children of the nodes on the paths of the proven leaves, by in-order
+    /// index.
+    children: BTreeMap<u64, (Position, Position)>,
+}
+
+impl MultiProofPositions {
+    /// The leaf indices must be lower than the non-zero leaves count. Returns `None`
+    /// if the tree is too large for the in-order index of its root to fit in a `u64`.
+    pub fn new<I: IntoIterator<Item = u64>>(
+        leaf_indices: I,
+        leaves_count: u64,
+    ) -> Option<Self> {
+        let root_index = leaves_count.checked_add(1)?.checked_next_power_of_two()? - 1;
+        let root_position = Position::from_in_order_index(root_index);
+
+        let mut root = root_position;
+        let mut children = BTreeMap::new();
+        let mut path_indices = BTreeSet::new();
+        let mut side_positions = BTreeMap::new();
+        for leaf_index in leaf_indices {
+            let leaf_position = Position::from_leaf_index(leaf_index);
+            let mut parent: Option<Position> = None;
+            for (path, side) in root_position.path(&leaf_position, leaves_count).iter() {
+                match parent {
+                    // The first path position is the root of the tree.
+                    None => root = path,
+                    Some(parent) => {
+                        children.insert(parent.in_order_index(), (path, side));
+                        side_positions.insert(side.in_order_index(), side);
+                    }
+                }
+                path_indices.insert(path.in_order_index());
+                parent = Some(path);
+            }
+        }
+        side_positions.retain(|index, _| !path_indices.contains(index));
+
+        Some(Self {
+            root,
+            side_positions: side_positions.into_values().collect(),
+            children,
+        })
+    }
+
+    /// Compute the sum of the node at `position` from the known sums of the
+    /// leaves and side nodes.
+    fn sum(
+        &self,
+        position: Position,
+        sums: &mut BTreeMap<u64, Bytes32>,
+    ) -> Option<Bytes32> {
+        let index = position.in_order_index();
+        if let Some(sum) = sums.get(&index) {
+            return Some(*sum)
+        }
+
+        let (path, side) = *self.children.get(&index)?;
+        let (left, right) = if path.in_order_index() < side.in_order_index() {
+            (path, side)
+        } else {
+            (side, path)
+        };
+        let sum = node_sum(&self.sum(left, sums)?, &self.sum(right, sums)?);
+        sums.insert(index, sum);
+        Some(sum)
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::verify;
+    use super::{
+        verify,
+        verify_consistency,
+        verify_many,
+    };
     use crate::{
         binary::{
             MerkleTree,
             Primitive,
         },
-        common::StorageMap,
+        common::{
+            Bytes32,
+            ProofSet,
+            StorageMap,
+        },
     };
     use fuel_merkle_test_helpers::TEST_DATA;
     use fuel_storage::Mappable;
@@ -201,4 +403,284 @@ mod test {
         );
         assert!(!verification);
     }
+
+    #[test]
+    fn verify_many_returns_true_for_the_multi_proofs_of_any_set_of_leaves() {
+        for leaves_count in 1..=9u64 {
+            let mut storage_map = StorageMap::<TestTable>::new();
+            let mut tree = MerkleTree::new(&mut storage_map);
+            for datum in TEST_DATA[0..leaves_count as usize].iter() {
+                tree.push(datum).unwrap();
+            }
+
+            // Every non-empty subset of the leaves, as a bit mask.
+            for mask in 1u64..(1 << leaves_count) {
+                let indices = (0..leaves_count)
+                    .filter(|index| mask & (1 << index) != 0)
+                    .collect::<Vec<_>>();
+                let leaves = indices
+                    .iter()
+                    .map(|index| (*index, TEST_DATA[*index as usize]))
+                    .collect::<Vec<_>>();
+
+                let (root, proof_set) = tree.prove_many(&indices).unwrap();
+                assert!(verify_many(&root, &leaves, &proof_set, leaves_count));
+            }
+        }
+    }
+
+    #[test]
+    fn verify_many_shares_the_side_nodes_of_the_leaves() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        const LEAVES_COUNT: u64 = 10;
+
+        for datum in TEST_DATA[0..LEAVES_COUNT as usize].iter() {
+            tree.push(datum).unwrap();
+        }
+
+        let indices = [0u64, 1, 2, 5, 9];
+        let single_proofs_len = indices
+            .iter()
+            .map(|index| tree.prove(*index).unwrap().1.len())
+            .sum::<usize>();
+        let (_, proof_set) = tree.prove_many(&indices).unwrap();
+
+        assert!(proof_set.len() < single_proofs_len);
+    }
+
+    #[test]
+    fn verify_many_returns_false_for_a_different_leaf() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        const LEAVES_COUNT: u64 = 5;
+
+        for datum in TEST_DATA[0..LEAVES_COUNT as usize].iter() {
+            tree.push(datum).unwrap();
+        }
+
+        let (root, proof_set) = tree.prove_many(&[1, 3]).unwrap();
+
+        assert!(!verify_many(
+            &root,
+            &[(1, TEST_DATA[1]), (3, TEST_DATA[4])],
+            &proof_set,
+            LEAVES_COUNT
+        ));
+        assert!(!verify_many(
+            &root,
+            &[(1, TEST_DATA[1]), (4, TEST_DATA[3])],
+            &proof_set,
+            LEAVES_COUNT
+        ));
+        assert!(!verify_many(
+            &root,
+            &[(1, TEST_DATA[1])],
+            &proof_set,
+            LEAVES_COUNT
+        ));
+    }
+
+    #[test]
+    fn verify_many_returns_false_for_a_tampered_proof_set() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        const LEAVES_COUNT: u64 = 5;
+
+        for datum in TEST_DATA[0..LEAVES_COUNT as usize].iter() {
+            tree.push(datum).unwrap();
+        }
+
+        let leaves = [(1, TEST_DATA[1]), (3, TEST_DATA[3])];
+        let (root, proof_set) = tree.prove_many(&[1, 3]).unwrap();
+
+        let mut longer = proof_set.clone();
+        longer.push(Default::default());
+        assert!(!verify_many(&root, &leaves, &longer, LEAVES_COUNT));
+
+        let mut shorter = proof_set.clone();
+        shorter.pop();
+        assert!(!verify_many(&root, &leaves, &shorter, LEAVES_COUNT));
+
+        let mut swapped = proof_set;
+        swapped.swap(0, 1);
+        assert!(!verify_many(&root, &leaves, &swapped, LEAVES_COUNT));
+    }
+
+    #[test]
+    fn verify_many_returns_false_for_too_many_leaves() {
+        let leaves = [(0, TEST_DATA[0])];
+        let root = Bytes32::default();
+
+        assert!(!verify_many(&root, &leaves, &ProofSet::new(), u64::MAX));
+        assert!(!verify_many(
+            &root,
+            &leaves,
+            &ProofSet::new(),
+            u64::MAX / 2 + 1
+        ));
+    }
+
+    #[test]
+    fn verify_many_returns_false_for_invalid_or_repeated_indices() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        const LEAVES_COUNT: u64 = 5;
+
+        for datum in TEST_DATA[0..LEAVES_COUNT as usize].iter() {
+            tree.push(datum).unwrap();
+        }
+
+        let (root, proof_set) = tree.prove_many(&[1]).unwrap();
+
+        assert!(!verify_many(
+            &root,
+            &[(1, TEST_DATA[1]), (1, TEST_DATA[1])],
+            &proof_set,
+            LEAVES_COUNT
+        ));
+        assert!(!verify_many(
+            &root,
+            &[(1 + LEAVES_COUNT, TEST_DATA[1])],
+            &proof_set,
+            LEAVES_COUNT
+        ));
+        assert!(!verify_many::<&[u8]>(&root, &[], &proof_set, LEAVES_COUNT));
+    }
+
+    #[test]
+    fn verify_consistency_returns_true_for_the_proofs_of_all_prefixes() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        for leaves_count in 1..=TEST_DATA.len() as u64 {
+            tree.push(TEST_DATA[leaves_count as usize - 1]).unwrap();
+            let root = tree.root();
+
+            for old_leaves_count in 1..=leaves_count {
+                let old_root = tree.root_at(old_leaves_count).unwrap();
+                let proof_set = tree.prove_consistency(old_leaves_count).unwrap();
+                assert!(verify_consistency(
+                    &old_root,
+                    old_leaves_count,
+                    &root,
+                    leaves_count,
+                    &proof_set
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn verify_consistency_returns_false_for_a_different_old_root() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        const LEAVES_COUNT: u64 = 7;
+
+        for datum in TEST_DATA[0..LEAVES_COUNT as usize].iter() {
+            tree.push(datum).unwrap();
+        }
+        let root = tree.root();
+
+        for old_leaves_count in [3, 4] {
+            let old_root = tree.root_at(old_leaves_count - 1).unwrap();
+            let proof_set = tree.prove_consistency(old_leaves_count).unwrap();
+            assert!(!verify_consistency(
+                &old_root,
+                old_leaves_count,
+                &root,
+                LEAVES_COUNT,
+                &proof_set
+            ));
+        }
+    }
+
+    #[test]
+    fn verify_consistency_returns_false_for_a_tampered_proof_set() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        const LEAVES_COUNT: u64 = 7;
+        const OLD_LEAVES_COUNT: u64 = 3;
+
+        for datum in TEST_DATA[0..LEAVES_COUNT as usize].iter() {
+            tree.push(datum).unwrap();
+        }
+        let root = tree.root();
+        let old_root = tree.root_at(OLD_LEAVES_COUNT).unwrap();
+        let proof_set = tree.prove_consistency(OLD_LEAVES_COUNT).unwrap();
+
+        let mut longer = proof_set.clone();
+        longer.push(Default::default());
+        assert!(!verify_consistency(
+            &old_root,
+            OLD_LEAVES_COUNT,
+            &root,
+            LEAVES_COUNT,
+            &longer
+        ));
+
+        let mut shorter = proof_set.clone();
+        shorter.pop();
+        assert!(!verify_consistency(
+            &old_root,
+            OLD_LEAVES_COUNT,
+            &root,
+            LEAVES_COUNT,
+            &shorter
+        ));
+
+        let mut changed = proof_set;
+        changed[1] = Default::default();
+        assert!(!verify_consistency(
+            &old_root,
+            OLD_LEAVES_COUNT,
+            &root,
+            LEAVES_COUNT,
+            &changed
+        ));
+    }
+
+    #[test]
+    fn verify_consistency_returns_false_for_invalid_leaves_counts() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        const LEAVES_COUNT: u64 = 7;
+        const OLD_LEAVES_COUNT: u64 = 3;
+
+        for datum in TEST_DATA[0..LEAVES_COUNT as usize].iter() {
+            tree.push(datum).unwrap();
+        }
+        let root = tree.root();
+        let old_root = tree.root_at(OLD_LEAVES_COUNT).unwrap();
+        let proof_set = tree.prove_consistency(OLD_LEAVES_COUNT).unwrap();
+
+        assert!(!verify_consistency(
+            &old_root,
+            OLD_LEAVES_COUNT + 1,
+            &root,
+            LEAVES_COUNT,
+            &proof_set
+        ));
+        assert!(!verify_consistency(
+            &old_root,
+            0,
+            &root,
+            LEAVES_COUNT,
+            &proof_set
+        ));
+        assert!(!verify_consistency(
+            &root,
+            LEAVES_COUNT + 1,
+            &root,
+            LEAVES_COUNT,
+            &vec![]
+        ));
+    }
 }
@@ -36,6 +36,16 @@ where
             map: Default::default(),
         }
     }
+
+    /// The number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Whether the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
 }
 
 impl<Type> StorageInspect<Type> for StorageMap<Type>
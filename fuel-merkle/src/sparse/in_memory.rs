@@ -7,6 +7,7 @@ use crate::{
         self,
         merkle_tree::MerkleTreeKey,
         Primitive,
+        Proof,
     },
     storage::{
         Mappable,
@@ -174,9 +175,34 @@ impl MerkleTree {
         let _ = self.tree.delete(key);
     }
 
+    /// Apply a set of updates to the tree. This is equivalent to sequentially
+    /// calling [update](Self::update) for each key-value pair.
+    pub fn update_batch<I, D>(&mut self, set: I)
+    where
+        I: IntoIterator<Item = (MerkleTreeKey, D)>,
+        D: AsRef<[u8]>,
+    {
+        let _ = self.tree.update_batch(set);
+    }
+
+    /// Apply a set of updates to the tree as [update_batch](Self::update_batch),
+    /// on up to `threads` threads.
+    #[cfg(feature = "std")]
+    pub fn update_batch_parallel<I, D>(&mut self, set: I, threads: usize)
+    where
+        I: IntoIterator<Item = (MerkleTreeKey, D)>,
+        D: AsRef<[u8]>,
+    {
+        let _ = self.tree.update_batch_parallel(set, threads);
+    }
+
     pub fn root(&self) -> Bytes32 {
         self.tree.root()
     }
+
+    pub fn prove(&self, key: MerkleTreeKey) -> Proof {
+        self.tree.prove(key).expect("`Storage` can't return error")
+    }
 }
 
 impl Default for MerkleTree {
@@ -1,14 +1,25 @@
 use crate::{
     common::{
         error::DeserializeError,
-        node::ChildError,
+        node::{
+            ChildError,
+            ParentNode,
+        },
+        path::{
+            Instruction,
+            Path,
+        },
         AsPathIterator,
         Bytes32,
     },
     sparse::{
         empty_sum,
         primitive::Primitive,
+        ExclusionLeaf,
+        ExclusionProof,
+        InclusionProof,
         Node,
+        Proof,
         StorageNode,
         StorageNodeError,
     },
@@ -174,6 +185,37 @@ where
         }
     }
 
+    /// Generate a proof of the value of `key`: an inclusion proof if the key has a
+    /// leaf in the tree, an exclusion proof otherwise. The proof is checked with
+    /// [`verify`](crate::sparse::verify).
+    pub fn prove(
+        &self,
+        key: MerkleTreeKey,
+    ) -> Result<Proof, MerkleTreeError<StorageError>> {
+        let key = key.into();
+        let (path_nodes, side_nodes) = self.path_set(key)?;
+        let proof_set = side_nodes.iter().map(|node| *node.hash()).collect();
+
+        let proof = match path_nodes.first() {
+            Some(node) if !node.is_placeholder() && node.leaf_key() == &key => {
+                Proof::Inclusion(InclusionProof { proof_set })
+            }
+            Some(node) if !node.is_placeholder() => Proof::Exclusion(ExclusionProof {
+                proof_set,
+                leaf: ExclusionLeaf::Leaf {
+                    key: *node.leaf_key(),
+                    data_hash: *node.leaf_data(),
+                },
+            }),
+            _ => Proof::Exclusion(ExclusionProof {
+                proof_set,
+                leaf: ExclusionLeaf::Placeholder,
+            }),
+        };
+
+        Ok(proof)
+    }
+
     // PRIVATE
 
     fn path_set(
@@ -410,6 +452,56 @@ where
         Ok(())
     }
 
+    /// Apply a set of updates to the tree. This is equivalent to sequentially
+    /// calling [update](Self::update) for each key-value pair, where empty data
+    /// deletes the key, but each node on the paths of the keys is hashed once.
+    pub fn update_batch<I, D>(
+        &mut self,
+        set: I,
+    ) -> Result<(), MerkleTreeError<StorageError>>
+    where
+        I: IntoIterator<Item = (MerkleTreeKey, D)>,
+        D: AsRef<[u8]>,
+    {
+        let changes = Self::batch_changes(set);
+        let mut new_nodes = Vec::new();
+        let root_node = Self::apply_batch(
+            &self.storage,
+            self.root_node().clone(),
+            0,
+            &changes,
+            &mut new_nodes,
+        )?;
+        self.insert_batch(&changes, new_nodes, root_node)
+    }
+
+    /// Apply a set of updates to the tree as [update_batch](Self::update_batch),
+    /// hashing the independent subtrees of the paths on up to `threads` threads.
+    #[cfg(feature = "std")]
+    pub fn update_batch_parallel<I, D>(
+        &mut self,
+        set: I,
+        threads: usize,
+    ) -> Result<(), MerkleTreeError<StorageError>>
+    where
+        I: IntoIterator<Item = (MerkleTreeKey, D)>,
+        D: AsRef<[u8]>,
+        StorageType: Sync,
+        StorageError: Send,
+    {
+        let changes = Self::batch_changes(set);
+        let mut new_nodes = Vec::new();
+        let root_node = Self::apply_batch_parallel(
+            &self.storage,
+            self.root_node().clone(),
+            0,
+            &changes,
+            &mut new_nodes,
+            threads,
+        )?;
+        self.insert_batch(&changes, new_nodes, root_node)
+    }
+
     // PRIVATE
 
     fn update_with_path_set(
@@ -479,6 +571,166 @@ where
         Ok(())
     }
 
+    /// The leaves of a batch of updates, sorted by key, with `None` for the
+    /// deleted keys. The last update of a key overrides the previous ones.
+    fn batch_changes<I, D>(set: I) -> Vec<(Bytes32, Option<Node>)>
+    where
+        I: IntoIterator<Item = (MerkleTreeKey, D)>,
+        D: AsRef<[u8]>,
+    {
+        set.into_iter()
+            .map(|(key, data)| {
+                let key = key.into();
+                let leaf = (!data.as_ref().is_empty())
+                    .then(|| Node::create_leaf(&key, data.as_ref()));
+                (key, leaf)
+            })
+            .collect::<alloc::collections::BTreeMap<_, _>>()
+            .into_iter()
+            .collect()
+    }
+
+    fn insert_batch(
+        &mut self,
+        changes: &[(Bytes32, Option<Node>)],
+        new_nodes: Vec<Node>,
+        root_node: Node,
+    ) -> Result<(), MerkleTreeError<StorageError>> {
+        let leaves = changes.iter().filter_map(|(_, leaf)| leaf.as_ref());
+        for node in leaves.chain(new_nodes.iter()) {
+            self.storage.insert(node.hash(), &node.as_ref().into())?;
+        }
+        self.set_root_node(root_node);
+
+        Ok(())
+    }
+
+    /// Apply the sorted `changes` to the subtree of `node` at `depth`, and
+    /// return the new root of the subtree. The keys of the changes share the
+    /// path of the subtree. The created nodes are pushed to `new_nodes`.
+    fn apply_batch(
+        storage: &StorageType,
+        node: Node,
+        depth: usize,
+        changes: &[(Bytes32, Option<Node>)],
+        new_nodes: &mut Vec<Node>,
+    ) -> Result<Node, MerkleTreeError<StorageError>> {
+        if changes.is_empty() {
+            return Ok(node)
+        }
+
+        if node.is_leaf() {
+            return Ok(Self::rebuild_leaf_subtree(node, depth, changes, new_nodes))
+        }
+
+        let (left_node, right_node) = Self::children(storage, node)?;
+        let (left_changes, right_changes) =
+            split_at_depth(changes, depth, |(key, _)| key);
+        let left_node =
+            Self::apply_batch(storage, left_node, depth + 1, left_changes, new_nodes)?;
+        let right_node =
+            Self::apply_batch(storage, right_node, depth + 1, right_changes, new_nodes)?;
+
+        Ok(join_subtrees(left_node, right_node, depth, new_nodes))
+    }
+
+    /// Parallel variant of [apply_batch](Self::apply_batch): the two subtrees
+    /// of the node are updated on separate threads, until `threads` threads are
+    /// busy.
+    #[cfg(feature = "std")]
+    fn apply_batch_parallel(
+        storage: &StorageType,
+        node: Node,
+        depth: usize,
+        changes: &[(Bytes32, Option<Node>)],
+        new_nodes: &mut Vec<Node>,
+        threads: usize,
+    ) -> Result<Node, MerkleTreeError<StorageError>>
+    where
+        StorageType: Sync,
+        StorageError: Send,
+    {
+        if threads <= 1 || changes.len() <= 1 || node.is_leaf() {
+            return Self::apply_batch(storage, node, depth, changes, new_nodes)
+        }
+
+        let (left_node, right_node) = Self::children(storage, node)?;
+        let (left_changes, right_changes) =
+            split_at_depth(changes, depth, |(key, _)| key);
+        let left_threads = threads / 2;
+        let right_threads = threads - left_threads;
+        let (left, right) = std::thread::scope(|scope| {
+            let left = scope.spawn(move || {
+                let mut new_nodes = Vec::new();
+                Self::apply_batch_parallel(
+                    storage,
+                    left_node,
+                    depth + 1,
+                    left_changes,
+                    &mut new_nodes,
+                    left_threads,
+                )
+                .map(|node| (node, new_nodes))
+            });
+            let right = Self::apply_batch_parallel(
+                storage,
+                right_node,
+                depth + 1,
+                right_changes,
+                new_nodes,
+                right_threads,
+            );
+            let left = left
+                .join()
+                .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+            (left, right)
+        });
+        let (left_node, left_new_nodes) = left?;
+        let right_node = right?;
+        new_nodes.extend(left_new_nodes);
+
+        Ok(join_subtrees(left_node, right_node, depth, new_nodes))
+    }
+
+    /// Build the subtree at `depth` holding the leaf `node`, or no leaf if it is a
+    /// placeholder, with the `changes` applied.
+    fn rebuild_leaf_subtree(
+        node: Node,
+        depth: usize,
+        changes: &[(Bytes32, Option<Node>)],
+        new_nodes: &mut Vec<Node>,
+    ) -> Node {
+        let mut leaves = changes
+            .iter()
+            .filter_map(|(_, leaf)| leaf.clone())
+            .collect::<Vec<_>>();
+        if !node.is_placeholder() {
+            let key = node.leaf_key();
+            if changes.binary_search_by(|(k, _)| k.cmp(key)).is_err() {
+                let index = leaves.partition_point(|leaf| leaf.leaf_key() < key);
+                leaves.insert(index, node);
+            }
+        }
+
+        build_subtree(&leaves, depth, new_nodes)
+    }
+
+    fn children(
+        storage: &StorageType,
+        node: Node,
+    ) -> Result<(Node, Node), MerkleTreeError<StorageError>> {
+        let node = StorageNode::new(storage, node);
+        let left_node = node
+            .left_child()
+            .map_err(MerkleTreeError::ChildError)?
+            .into_node();
+        let right_node = node
+            .right_child()
+            .map_err(MerkleTreeError::ChildError)?
+            .into_node();
+        Ok((left_node, right_node))
+    }
+
     fn delete_with_path_set(
         &mut self,
         requested_leaf_key: &Bytes32,
@@ -546,6 +798,54 @@ where
     }
 }
 
+/// Split the `items`, sorted by key, sharing the first `depth` bits of their
+/// keys, into the items on the left and on the right of the node at `depth`.
+fn split_at_depth<T>(
+    items: &[T],
+    depth: usize,
+    key: impl Fn(&T) -> &Bytes32,
+) -> (&[T], &[T]) {
+    let index = items.partition_point(|item| {
+        matches!(key(item).get_instruction(depth), Some(Instruction::Left))
+    });
+    items.split_at(index)
+}
+
+/// Build the subtree at `depth` of the `leaves`, sorted by key.
+fn build_subtree(leaves: &[Node], depth: usize, new_nodes: &mut Vec<Node>) -> Node {
+    match leaves {
+        [] => Node::create_placeholder(),
+        [leaf] => leaf.clone(),
+        _ => {
+            let (left, right) = split_at_depth(leaves, depth, Node::leaf_key);
+            let left_node = build_subtree(left, depth + 1, new_nodes);
+            let right_node = build_subtree(right, depth + 1, new_nodes);
+            join_subtrees(left_node, right_node, depth, new_nodes)
+        }
+    }
+}
+
+/// Join the subtrees under the node at `depth`. A subtree with a single leaf
+/// collapses into the leaf, and an empty one into a placeholder.
+fn join_subtrees(
+    left_node: Node,
+    right_node: Node,
+    depth: usize,
+    new_nodes: &mut Vec<Node>,
+) -> Node {
+    match (left_node.is_placeholder(), right_node.is_placeholder()) {
+        (true, true) => Node::create_placeholder(),
+        (false, true) if left_node.is_leaf() => left_node,
+        (true, false) if right_node.is_leaf() => right_node,
+        _ => {
+            let height = (Node::max_height() - depth) as u32;
+            let node = Node::create_node(&left_node, &right_node, height);
+            new_nodes.push(node.clone());
+            node
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::{
@@ -1249,4 +1549,164 @@ mod test {
 
         assert_eq!(root, expected_root);
     }
+
+    type Updates = Vec<(MerkleTreeKey, Vec<u8>)>;
+
+    /// A tree of 500 random leaves and a batch of updates to it: new keys,
+    /// overwritten and deleted leaves, deletions of absent keys and repeated keys.
+    fn tree_and_batch() -> (Updates, Updates) {
+        use rand::{
+            rngs::StdRng,
+            SeedableRng,
+        };
+
+        let rng = &mut StdRng::seed_from_u64(8586);
+        let leaves = (0..500)
+            .map(|_| {
+                (
+                    MerkleTreeKey::new_without_hash(random_bytes32(rng)),
+                    random_bytes32(rng).to_vec(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut batch = Vec::new();
+        for (key, _) in leaves.iter().step_by(5) {
+            batch.push((*key, random_bytes32(rng).to_vec()));
+        }
+        for (key, _) in leaves.iter().skip(1).step_by(5) {
+            batch.push((*key, vec![]));
+        }
+        for _ in 0..200 {
+            let key = MerkleTreeKey::new_without_hash(random_bytes32(rng));
+            batch.push((key, random_bytes32(rng).to_vec()));
+        }
+        for _ in 0..10 {
+            let key = MerkleTreeKey::new_without_hash(random_bytes32(rng));
+            batch.push((key, vec![]));
+        }
+        // Set again, then delete, some of the updated keys.
+        for index in (0..batch.len()).step_by(7) {
+            let (key, _) = batch[index];
+            batch.push((key, random_bytes32(rng).to_vec()));
+        }
+        for index in (0..batch.len()).step_by(11) {
+            let (key, _) = batch[index];
+            batch.push((key, vec![]));
+        }
+
+        (leaves, batch)
+    }
+
+    fn sequential_root(
+        leaves: &[(MerkleTreeKey, Vec<u8>)],
+        batch: &[(MerkleTreeKey, Vec<u8>)],
+    ) -> Bytes32 {
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage);
+        for (key, data) in leaves.iter().chain(batch) {
+            tree.update(*key, data).unwrap();
+        }
+        tree.root()
+    }
+
+    #[test]
+    fn test_update_batch_yields_the_root_of_sequential_updates() {
+        let (leaves, batch) = tree_and_batch();
+        let expected_root = sequential_root(&leaves, &batch);
+
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage);
+        for (key, data) in leaves.iter() {
+            tree.update(*key, data).unwrap();
+        }
+        tree.update_batch(batch.iter().map(|(key, data)| (*key, data)))
+            .unwrap();
+        let root = tree.root();
+        assert_eq!(root, expected_root);
+
+        // The loaded tree can be updated further.
+        let deletions = batch
+            .iter()
+            .take(50)
+            .map(|(key, _)| (*key, vec![]))
+            .collect::<Vec<_>>();
+        let mut tree = MerkleTree::load(&mut storage, &root).unwrap();
+        for (key, _) in deletions.iter() {
+            tree.delete(*key).unwrap();
+        }
+        assert_eq!(
+            tree.root(),
+            sequential_root(&leaves, &[batch, deletions].concat())
+        );
+    }
+
+    #[test]
+    fn test_update_batch_parallel_yields_the_root_of_sequential_updates() {
+        let (leaves, batch) = tree_and_batch();
+        let expected_root = sequential_root(&leaves, &batch);
+
+        for threads in [1, 2, 3, 8] {
+            let mut storage = StorageMap::<TestTable>::new();
+            let mut tree = MerkleTree::new(&mut storage);
+            for (key, data) in leaves.iter() {
+                tree.update(*key, data).unwrap();
+            }
+            tree.update_batch_parallel(
+                batch.iter().map(|(key, data)| (*key, data)),
+                threads,
+            )
+            .unwrap();
+            assert_eq!(tree.root(), expected_root);
+        }
+    }
+
+    #[test]
+    fn test_update_batch_on_an_empty_tree_yields_the_root_of_from_set() {
+        let (leaves, _) = tree_and_batch();
+
+        let expected_root = {
+            let mut storage = StorageMap::<TestTable>::new();
+            let tree =
+                MerkleTree::from_set(&mut storage, leaves.clone().into_iter()).unwrap();
+            tree.root()
+        };
+
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage);
+        tree.update_batch(leaves).unwrap();
+
+        assert_eq!(tree.root(), expected_root);
+    }
+
+    #[test]
+    fn test_update_batch_deleting_all_leaves_yields_the_empty_root() {
+        let (leaves, _) = tree_and_batch();
+
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage);
+        tree.update_batch(leaves.iter().map(|(key, data)| (*key, data)))
+            .unwrap();
+        tree.update_batch(leaves.iter().map(|(key, _)| (*key, [])))
+            .unwrap();
+
+        assert_eq!(tree.root(), *empty_sum());
+    }
+
+    #[test]
+    fn test_update_batch_with_one_leaf_yields_the_leaf_root() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage);
+        tree.update(key(b"\x00\x00\x00\x00"), b"DATA").unwrap();
+        tree.update(key(b"\x00\x00\x00\x01"), b"DATA").unwrap();
+
+        tree.update_batch([
+            (key(b"\x00\x00\x00\x00"), &b""[..]),
+            (key(b"\x00\x00\x00\x01"), &b"DATA"[..]),
+        ])
+        .unwrap();
+
+        let leaf = Node::create_leaf(&key(b"\x00\x00\x00\x01").into(), b"DATA");
+        assert_eq!(tree.root(), *leaf.hash());
+    }
 }
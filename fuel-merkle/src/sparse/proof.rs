@@ -0,0 +1,54 @@
+use crate::common::{
+    Bytes32,
+    ProofSet,
+};
+
+/// Proof that a key is included in a sparse Merkle tree, or that it is absent.
+///
+/// The proof set holds the hashes of the side nodes on the path of the key, from
+/// the leaf to the root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Proof {
+    Inclusion(InclusionProof),
+    Exclusion(ExclusionProof),
+}
+
+impl Proof {
+    pub fn proof_set(&self) -> &ProofSet {
+        match self {
+            Proof::Inclusion(proof) => &proof.proof_set,
+            Proof::Exclusion(proof) => &proof.proof_set,
+        }
+    }
+
+    pub fn is_inclusion(&self) -> bool {
+        matches!(self, Proof::Inclusion(_))
+    }
+
+    pub fn is_exclusion(&self) -> bool {
+        matches!(self, Proof::Exclusion(_))
+    }
+}
+
+/// Proof that the leaf of the key is part of the tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InclusionProof {
+    pub proof_set: ProofSet,
+}
+
+/// Proof that the key has no leaf in the tree: its path ends in an empty subtree,
+/// or in the leaf of another key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExclusionProof {
+    pub proof_set: ProofSet,
+    pub leaf: ExclusionLeaf,
+}
+
+/// The node at the end of the path of an excluded key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExclusionLeaf {
+    /// The leaf of another key, with the hash of its data.
+    Leaf { key: Bytes32, data_hash: Bytes32 },
+    /// An empty subtree.
+    Placeholder,
+}
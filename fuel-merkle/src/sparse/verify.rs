@@ -0,0 +1,284 @@
+use crate::{
+    common::{
+        path::{
+            ComparablePath,
+            Instruction,
+            Path,
+        },
+        Bytes32,
+        Prefix,
+    },
+    sparse::{
+        hash::sum,
+        proof::{
+            ExclusionLeaf,
+            Proof,
+        },
+        MerkleTreeKey,
+        Node,
+    },
+};
+
+/// Verify a proof of the value of `key` against the root of a sparse Merkle tree.
+///
+/// A `Some` value is verified with an inclusion proof of the leaf of the key with
+/// the value, and `None` with an exclusion proof of the key.
+pub fn verify<D: AsRef<[u8]>>(
+    root: &Bytes32,
+    key: MerkleTreeKey,
+    value: Option<D>,
+    proof: &Proof,
+) -> bool {
+    let key: Bytes32 = key.into();
+    let proof_set = proof.proof_set();
+    if proof_set.len() > Node::max_height() {
+        return false
+    }
+
+    let leaf = match (value, proof) {
+        (Some(value), Proof::Inclusion(_)) => {
+            Node::new(0, Prefix::Leaf, key, sum(value.as_ref()))
+        }
+        (None, Proof::Exclusion(proof)) => match proof.leaf {
+            ExclusionLeaf::Leaf {
+                key: leaf_key,
+                data_hash,
+            } => {
+                // The leaf must be another leaf on the path of the key.
+                if leaf_key == key || key.common_path_length(&leaf_key) < proof_set.len()
+                {
+                    return false
+                }
+                Node::new(0, Prefix::Leaf, leaf_key, data_hash)
+            }
+            ExclusionLeaf::Placeholder => Node::create_placeholder(),
+        },
+        _ => return false,
+    };
+
+    // The side nodes are ordered from the leaf to the root: the last one is a child
+    // of the root, at depth 0.
+    let mut current = *leaf.hash();
+    for (index, side) in proof_set.iter().enumerate() {
+        let depth = proof_set.len() - 1 - index;
+        let (left, right) = match key.get_instruction(depth) {
+            Some(Instruction::Left) => (current, *side),
+            Some(Instruction::Right) => (*side, current),
+            None => return false,
+        };
+        current = *Node::new(0, Prefix::Node, left, right).hash();
+    }
+
+    current == *root
+}
+
+#[cfg(test)]
+mod test {
+    use super::verify;
+    use crate::{
+        common::Bytes32,
+        sparse::{
+            hash::sum,
+            in_memory::MerkleTree,
+            ExclusionLeaf,
+            ExclusionProof,
+            MerkleTreeKey,
+            Proof,
+        },
+    };
+    use rand::{
+        rngs::StdRng,
+        Rng,
+        SeedableRng,
+    };
+
+    fn key<B: AsRef<[u8]>>(data: B) -> MerkleTreeKey {
+        MerkleTreeKey::new_without_hash(sum(data.as_ref()))
+    }
+
+    fn random_key(rng: &mut StdRng) -> Bytes32 {
+        let mut bytes = [0u8; 32];
+        rng.fill(bytes.as_mut());
+        bytes
+    }
+
+    #[test]
+    fn verify_returns_true_for_the_proofs_of_an_empty_tree() {
+        let tree = MerkleTree::new();
+
+        let proof = tree.prove(key(b"\x00\x00\x00\x00"));
+
+        assert!(proof.is_exclusion());
+        assert!(proof.proof_set().is_empty());
+        assert!(verify::<&[u8]>(
+            &tree.root(),
+            key(b"\x00\x00\x00\x00"),
+            None,
+            &proof
+        ));
+    }
+
+    #[test]
+    fn verify_returns_true_for_the_proofs_of_a_tree_with_one_leaf() {
+        let mut tree = MerkleTree::new();
+        tree.update(key(b"\x00\x00\x00\x00"), b"DATA");
+        let root = tree.root();
+
+        let inclusion = tree.prove(key(b"\x00\x00\x00\x00"));
+        let exclusion = tree.prove(key(b"\x00\x00\x00\x01"));
+
+        assert!(inclusion.is_inclusion());
+        assert!(verify(
+            &root,
+            key(b"\x00\x00\x00\x00"),
+            Some(b"DATA"),
+            &inclusion
+        ));
+        assert!(exclusion.is_exclusion());
+        assert!(verify::<&[u8]>(
+            &root,
+            key(b"\x00\x00\x00\x01"),
+            None,
+            &exclusion
+        ));
+    }
+
+    #[test]
+    fn verify_returns_true_for_the_proofs_of_all_keys() {
+        let rng = &mut StdRng::seed_from_u64(8586);
+        let keys = (0..100).map(|_| random_key(rng)).collect::<Vec<_>>();
+        let mut tree = MerkleTree::new();
+        for (i, key) in keys.iter().enumerate() {
+            tree.update(MerkleTreeKey::new_without_hash(*key), &i.to_be_bytes());
+        }
+        let root = tree.root();
+
+        for (i, key) in keys.iter().enumerate() {
+            let key = MerkleTreeKey::new_without_hash(*key);
+            let proof = tree.prove(key);
+            assert!(proof.is_inclusion());
+            assert!(verify(&root, key, Some(i.to_be_bytes()), &proof));
+        }
+
+        for _ in 0..100 {
+            let key = MerkleTreeKey::new_without_hash(random_key(rng));
+            let proof = tree.prove(key);
+            assert!(proof.is_exclusion());
+            assert!(verify::<&[u8]>(&root, key, None, &proof));
+        }
+    }
+
+    #[test]
+    fn verify_returns_true_for_exclusion_proofs_ending_in_a_placeholder() {
+        let mut tree = MerkleTree::new();
+        // The first bits of the keys are 000 and 001: the right child of the root
+        // is a placeholder.
+        let keys = [[0x00; 32], [0x20; 32]];
+        for key in keys {
+            tree.update(MerkleTreeKey::new_without_hash(key), b"DATA");
+        }
+        let root = tree.root();
+        let key = MerkleTreeKey::new_without_hash([0x80; 32]);
+
+        let proof = tree.prove(key);
+
+        assert!(matches!(
+            proof,
+            Proof::Exclusion(ExclusionProof {
+                leaf: ExclusionLeaf::Placeholder,
+                ..
+            })
+        ));
+        assert!(verify::<&[u8]>(&root, key, None, &proof));
+    }
+
+    #[test]
+    fn verify_returns_false_for_a_different_value() {
+        let mut tree = MerkleTree::new();
+        tree.update(key(b"\x00\x00\x00\x00"), b"DATA");
+        tree.update(key(b"\x00\x00\x00\x01"), b"DATA");
+        let root = tree.root();
+
+        let proof = tree.prove(key(b"\x00\x00\x00\x00"));
+
+        assert!(!verify(
+            &root,
+            key(b"\x00\x00\x00\x00"),
+            Some(b"ATAD"),
+            &proof
+        ));
+        assert!(!verify::<&[u8]>(
+            &root,
+            key(b"\x00\x00\x00\x00"),
+            None,
+            &proof
+        ));
+    }
+
+    #[test]
+    fn verify_returns_false_for_the_proof_of_another_key() {
+        let mut tree = MerkleTree::new();
+        tree.update(key(b"\x00\x00\x00\x00"), b"DATA");
+        tree.update(key(b"\x00\x00\x00\x01"), b"DATA");
+        let root = tree.root();
+
+        let proof = tree.prove(key(b"\x00\x00\x00\x00"));
+        let exclusion = tree.prove(key(b"\x00\x00\x00\x02"));
+
+        assert!(!verify(
+            &root,
+            key(b"\x00\x00\x00\x01"),
+            Some(b"DATA"),
+            &proof
+        ));
+        assert!(!verify::<&[u8]>(
+            &root,
+            key(b"\x00\x00\x00\x01"),
+            None,
+            &exclusion
+        ));
+    }
+
+    #[test]
+    fn verify_returns_false_for_a_tampered_proof_set() {
+        let mut tree = MerkleTree::new();
+        tree.update(key(b"\x00\x00\x00\x00"), b"DATA");
+        tree.update(key(b"\x00\x00\x00\x01"), b"DATA");
+        let root = tree.root();
+
+        let Proof::Inclusion(mut proof) = tree.prove(key(b"\x00\x00\x00\x00")) else {
+            panic!("Expected an inclusion proof")
+        };
+        proof.proof_set.push([0; 32]);
+
+        assert!(!verify(
+            &root,
+            key(b"\x00\x00\x00\x00"),
+            Some(b"DATA"),
+            &Proof::Inclusion(proof)
+        ));
+    }
+
+    #[test]
+    fn verify_returns_false_for_an_exclusion_proof_with_the_leaf_of_the_key() {
+        let mut tree = MerkleTree::new();
+        tree.update(key(b"\x00\x00\x00\x00"), b"DATA");
+        tree.update(key(b"\x00\x00\x00\x01"), b"DATA");
+        let root = tree.root();
+
+        let proof = Proof::Exclusion(ExclusionProof {
+            proof_set: tree.prove(key(b"\x00\x00\x00\x00")).proof_set().clone(),
+            leaf: ExclusionLeaf::Leaf {
+                key: key(b"\x00\x00\x00\x00").into(),
+                data_hash: sum(b"DATA"),
+            },
+        });
+
+        assert!(!verify::<&[u8]>(
+            &root,
+            key(b"\x00\x00\x00\x00"),
+            None,
+            &proof
+        ));
+    }
+}
@@ -6,20 +6,37 @@ use crate::{
     sum::{
         empty_sum,
         Node,
+        ProofSet,
     },
 };
 
 use fuel_storage::{
     Mappable,
+    StorageInspect,
     StorageMutate,
 };
 
 use core::marker::PhantomData;
 
 #[derive(Debug, Clone, derive_more::Display)]
-pub enum MerkleTreeError {
+pub enum MerkleTreeError<StorageError> {
     #[display(fmt = "proof index {_0} is not valid")]
     InvalidProofIndex(u64),
+
+    #[display(
+        fmt = "cannot load node with key {}; the key is not found in storage",
+        "hex::encode(_0)"
+    )]
+    LoadError(Bytes32),
+
+    #[display(fmt = "{}", _0)]
+    StorageError(StorageError),
+}
+
+impl<StorageError> From<StorageError> for MerkleTreeError<StorageError> {
+    fn from(err: StorageError) -> MerkleTreeError<StorageError> {
+        MerkleTreeError::StorageError(err)
+    }
 }
 
 /// The Binary Merkle Sum Tree is an extension to the existing Binary
@@ -83,6 +100,56 @@ where
         Ok(root_pair)
     }
 
+    /// The number of leaves pushed to the tree.
+    pub fn leaves_count(&self) -> u64 {
+        // The subtrees are balanced: a subtree of height H has 2^H leaves.
+        let mut leaves_count = 0;
+        let mut current = self.head.as_ref();
+        while let Some(subtree) = current {
+            leaves_count += 1 << subtree.node().height();
+            current = subtree.next();
+        }
+        leaves_count
+    }
+
+    /// Prove the leaf at `proof_index`. The proof set holds the fee and digest
+    /// of the side nodes, ordered from the leaf to the root.
+    pub fn prove(
+        &mut self,
+        proof_index: u64,
+    ) -> Result<((u64, Bytes32), ProofSet), MerkleTreeError<StorageError>> {
+        if proof_index >= self.leaves_count() {
+            return Err(MerkleTreeError::InvalidProofIndex(proof_index))
+        }
+
+        let root_node = self.root_node()?.expect("Root node must be present");
+        let root = (root_node.fee(), *root_node.hash());
+
+        // The left child of a node of height H is a balanced subtree of
+        // 2^(H - 1) leaves, as the subtrees are joined from the right.
+        let mut proof_set = ProofSet::new();
+        let mut current = root_node;
+        let mut index = proof_index;
+        while current.is_node() {
+            let left_leaves_count = 1 << (current.height() - 1);
+            let left_key = current.left_child_key().expect("Node has a left child");
+            let right_key = current.right_child_key().expect("Node has a right child");
+            let (path_key, side_key) = if index < left_leaves_count {
+                (left_key, right_key)
+            } else {
+                index -= left_leaves_count;
+                (right_key, left_key)
+            };
+
+            let side = self.load(&side_key)?;
+            proof_set.push((side.fee(), *side.hash()));
+            current = self.load(&path_key)?;
+        }
+        proof_set.reverse(); // Reorder side nodes from leaf to root.
+
+        Ok((root, proof_set))
+    }
+
     pub fn push(&mut self, fee: u64, data: &[u8]) -> Result<(), StorageError> {
         let node = Node::create_leaf(fee, data);
         self.storage.insert(node.hash(), &node)?;
@@ -98,6 +165,13 @@ where
     // PRIVATE
     //
 
+    fn load(&self, key: &Bytes32) -> Result<Node, MerkleTreeError<StorageError>> {
+        let node = StorageInspect::get(&self.storage, key)?
+            .ok_or(MerkleTreeError::LoadError(*key))?
+            .into_owned();
+        Ok(node)
+    }
+
     fn root_node(&mut self) -> Result<Option<Node>, StorageError> {
         let root_node = match self.head {
             None => None,
@@ -168,6 +242,7 @@ mod test {
             leaf_sum,
             node_sum,
             MerkleTree,
+            MerkleTreeError,
             Node,
         },
     };
@@ -314,4 +389,82 @@ mod test {
         let expected = (FEE * 7, node_5);
         assert_eq!(root, expected);
     }
+
+    #[test]
+    fn prove_returns_invalid_proof_index_error_when_index_is_greater_than_number_of_leaves(
+    ) {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        let data = &TEST_DATA[0..5]; // 5 leaves
+        for datum in data.iter() {
+            let _ = tree.push(FEE, datum);
+        }
+
+        let err = tree
+            .prove(10)
+            .expect_err("Expected prove() to return Error; got Ok");
+        assert!(matches!(err, MerkleTreeError::InvalidProofIndex(10)));
+    }
+
+    #[test]
+    fn prove_returns_the_root_and_proof_set_for_7_leaves() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        let data = &TEST_DATA[0..7]; // 7 leaves
+        for datum in data.iter() {
+            let _ = tree.push(FEE, datum);
+        }
+        assert_eq!(tree.leaves_count(), 7);
+
+        //              N5
+        //            /    \
+        //           /      \
+        //          /        \
+        //         /          \
+        //       N3            N4
+        //      /  \           /\
+        //     /    \         /  \
+        //   N0      N1      N2   \
+        //  /  \    /  \    /  \   \
+        // L0  L1  L2  L3  L4  L5  L6
+
+        let leaf_0 = leaf_sum(FEE, data[0]);
+        let leaf_1 = leaf_sum(FEE, data[1]);
+        let leaf_2 = leaf_sum(FEE, data[2]);
+        let leaf_3 = leaf_sum(FEE, data[3]);
+        let leaf_4 = leaf_sum(FEE, data[4]);
+        let leaf_5 = leaf_sum(FEE, data[5]);
+        let leaf_6 = leaf_sum(FEE, data[6]);
+
+        let node_0 = node_sum(FEE * 1, &leaf_0, FEE * 1, &leaf_1);
+        let node_1 = node_sum(FEE * 1, &leaf_2, FEE * 1, &leaf_3);
+        let node_2 = node_sum(FEE * 1, &leaf_4, FEE * 1, &leaf_5);
+        let node_3 = node_sum(FEE * 2, &node_0, FEE * 2, &node_1);
+        let node_4 = node_sum(FEE * 2, &node_2, FEE * 1, &leaf_6);
+        let node_5 = node_sum(FEE * 4, &node_3, FEE * 3, &node_4);
+
+        {
+            let (root, proof_set) = tree.prove(0).unwrap();
+            assert_eq!(root, (FEE * 7, node_5));
+            assert_eq!(
+                proof_set,
+                vec![(FEE * 1, leaf_1), (FEE * 2, node_1), (FEE * 3, node_4)]
+            );
+        }
+        {
+            let (root, proof_set) = tree.prove(4).unwrap();
+            assert_eq!(root, (FEE * 7, node_5));
+            assert_eq!(
+                proof_set,
+                vec![(FEE * 1, leaf_5), (FEE * 1, leaf_6), (FEE * 4, node_3)]
+            );
+        }
+        {
+            let (root, proof_set) = tree.prove(6).unwrap();
+            assert_eq!(root, (FEE * 7, node_5));
+            assert_eq!(proof_set, vec![(FEE * 2, node_2), (FEE * 4, node_3)]);
+        }
+    }
 }
@@ -0,0 +1,245 @@
+use crate::{
+    common::Bytes32,
+    sum::{
+        leaf_sum,
+        node_sum,
+        ProofSet,
+    },
+};
+
+/// Verify the proof of the leaf at `proof_index` with `fee` and `data` against
+/// the `(fee, digest)` root of a sum tree of `num_leaves` leaves.
+///
+/// The digests of the path must hash to the root digest, and the fees of the
+/// leaf and of the side nodes must add up to the root fee.
+pub fn verify<T: AsRef<[u8]>>(
+    root: &(u64, Bytes32),
+    fee: u64,
+    data: &T,
+    proof_set: &ProofSet,
+    proof_index: u64,
+    num_leaves: u64,
+) -> bool {
+    let mut sum = (fee, leaf_sum(fee, data.as_ref()));
+
+    if proof_index >= num_leaves {
+        return false
+    }
+
+    if proof_set.is_empty() {
+        return if num_leaves == 1 { *root == sum } else { false }
+    }
+
+    let mut height = 1usize;
+    let mut stable_end = proof_index;
+
+    loop {
+        let subtree_start_index = proof_index / (1 << height) * (1 << height);
+        let subtree_end_index = subtree_start_index + (1 << height) - 1;
+
+        if subtree_end_index >= num_leaves {
+            break
+        }
+
+        stable_end = subtree_end_index;
+
+        if proof_set.len() < height {
+            return false
+        }
+
+        let proof_data = proof_set[height - 1];
+        let joined = if proof_index - subtree_start_index < 1 << (height - 1) {
+            join(&sum, &proof_data)
+        } else {
+            join(&proof_data, &sum)
+        };
+        match joined {
+            Some(joined) => sum = joined,
+            None => return false,
+        }
+
+        height += 1;
+    }
+
+    if stable_end != num_leaves - 1 {
+        if proof_set.len() < height {
+            return false
+        }
+        let proof_data = proof_set[height - 1];
+        match join(&sum, &proof_data) {
+            Some(joined) => sum = joined,
+            None => return false,
+        }
+        height += 1;
+    }
+
+    while height - 1 < proof_set.len() {
+        let proof_data = proof_set[height - 1];
+        match join(&proof_data, &sum) {
+            Some(joined) => sum = joined,
+            None => return false,
+        }
+        height += 1;
+    }
+
+    sum == *root
+}
+
+/// Join two nodes, or `None` if their fees overflow.
+fn join(lhs: &(u64, Bytes32), rhs: &(u64, Bytes32)) -> Option<(u64, Bytes32)> {
+    let fee = lhs.0.checked_add(rhs.0)?;
+    Some((fee, node_sum(lhs.0, &lhs.1, rhs.0, &rhs.1)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::verify;
+    use crate::{
+        common::{
+            Bytes32,
+            StorageMap,
+        },
+        sum::{
+            MerkleTree,
+            Node,
+        },
+    };
+    use fuel_merkle_test_helpers::TEST_DATA;
+    use fuel_storage::Mappable;
+
+    #[derive(Debug)]
+    struct TestTable;
+
+    impl Mappable for TestTable {
+        type Key = Self::OwnedKey;
+        type OwnedKey = Bytes32;
+        type OwnedValue = Node;
+        type Value = Self::OwnedValue;
+    }
+
+    fn fee(index: usize) -> u64 {
+        100 + index as u64
+    }
+
+    #[test]
+    fn verify_returns_true_for_the_proofs_of_all_leaves() {
+        for leaves_count in 1..=TEST_DATA.len() {
+            let mut storage_map = StorageMap::<TestTable>::new();
+            let mut tree = MerkleTree::new(&mut storage_map);
+            for (index, datum) in TEST_DATA[0..leaves_count].iter().enumerate() {
+                tree.push(fee(index), datum).unwrap();
+            }
+
+            for (index, datum) in TEST_DATA[0..leaves_count].iter().enumerate() {
+                let (root, proof_set) = tree.prove(index as u64).unwrap();
+                assert_eq!(root, tree.root().unwrap());
+                assert!(verify(
+                    &root,
+                    fee(index),
+                    datum,
+                    &proof_set,
+                    index as u64,
+                    leaves_count as u64,
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn verify_returns_false_for_a_different_fee() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        const PROOF_INDEX: usize = 2;
+        const LEAVES_COUNT: usize = 5;
+
+        for (index, datum) in TEST_DATA[0..LEAVES_COUNT].iter().enumerate() {
+            tree.push(fee(index), datum).unwrap();
+        }
+
+        let (root, proof_set) = tree.prove(PROOF_INDEX as u64).unwrap();
+        let verification = verify(
+            &root,
+            fee(PROOF_INDEX) + 1,
+            &TEST_DATA[PROOF_INDEX],
+            &proof_set,
+            PROOF_INDEX as u64,
+            LEAVES_COUNT as u64,
+        );
+        assert!(!verification);
+    }
+
+    #[test]
+    fn verify_returns_false_when_the_fees_do_not_add_up_to_the_root_fee() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        const PROOF_INDEX: usize = 2;
+        const LEAVES_COUNT: usize = 5;
+
+        for (index, datum) in TEST_DATA[0..LEAVES_COUNT].iter().enumerate() {
+            tree.push(fee(index), datum).unwrap();
+        }
+
+        let ((root_fee, root_digest), proof_set) =
+            tree.prove(PROOF_INDEX as u64).unwrap();
+        let verification = verify(
+            &(root_fee + 1, root_digest),
+            fee(PROOF_INDEX),
+            &TEST_DATA[PROOF_INDEX],
+            &proof_set,
+            PROOF_INDEX as u64,
+            LEAVES_COUNT as u64,
+        );
+        assert!(!verification);
+    }
+
+    #[test]
+    fn verify_returns_false_for_a_tampered_side_node_fee() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        const PROOF_INDEX: usize = 2;
+        const LEAVES_COUNT: usize = 5;
+
+        for (index, datum) in TEST_DATA[0..LEAVES_COUNT].iter().enumerate() {
+            tree.push(fee(index), datum).unwrap();
+        }
+
+        let (root, mut proof_set) = tree.prove(PROOF_INDEX as u64).unwrap();
+        proof_set[0].0 = u64::MAX;
+        let verification = verify(
+            &root,
+            fee(PROOF_INDEX),
+            &TEST_DATA[PROOF_INDEX],
+            &proof_set,
+            PROOF_INDEX as u64,
+            LEAVES_COUNT as u64,
+        );
+        assert!(!verification);
+    }
+
+    #[test]
+    fn verify_returns_false_when_the_proof_index_is_invalid() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        const PROOF_INDEX: usize = 0;
+        const LEAVES_COUNT: usize = 5;
+
+        for (index, datum) in TEST_DATA[0..LEAVES_COUNT].iter().enumerate() {
+            tree.push(fee(index), datum).unwrap();
+        }
+
+        let (root, proof_set) = tree.prove(PROOF_INDEX as u64).unwrap();
+        let verification = verify(
+            &root,
+            fee(PROOF_INDEX),
+            &TEST_DATA[PROOF_INDEX],
+            &proof_set,
+            PROOF_INDEX as u64 + 15,
+            LEAVES_COUNT as u64,
+        );
+        assert!(!verification);
+    }
+}
@@ -2,7 +2,12 @@ use fuel_merkle::{
     common::Bytes32,
     sparse::{
         in_memory,
+        verify,
+        ExclusionLeaf,
+        ExclusionProof,
+        InclusionProof,
         MerkleTreeKey,
+        Proof,
     },
 };
 use serde::Deserialize;
@@ -21,6 +26,7 @@ trait MerkleTreeTestAdaptor {
     fn update(&mut self, key: &Bytes32, data: &[u8]);
     fn delete(&mut self, key: &Bytes32);
     fn root(&self) -> Bytes32;
+    fn prove(&self, key: &Bytes32) -> Proof;
 }
 
 #[derive(Deserialize)]
@@ -94,6 +100,86 @@ impl MerkleTreeTestAdaptor for InMemoryMerkleTreeTestAdaptor {
     fn root(&self) -> Bytes32 {
         self.tree.as_ref().root()
     }
+
+    fn prove(&self, key: &Bytes32) -> Proof {
+        self.tree
+            .as_ref()
+            .prove(MerkleTreeKey::new_without_hash(*key))
+    }
+}
+
+fn decode_bytes32(encoded: EncodedValue) -> Result<Bytes32, TestError> {
+    encoded
+        .into_bytes()?
+        .try_into()
+        .map_err(|_| TestError::DecodingError)
+}
+
+#[derive(Deserialize)]
+struct ProofLeaf {
+    key: EncodedValue,
+    data_hash: EncodedValue,
+}
+
+/// Expected proof of a key: an inclusion proof if the key has data, an exclusion
+/// proof ending in the leaf, or in a placeholder if there is no leaf, otherwise.
+#[derive(Deserialize)]
+struct ProofStep {
+    key: EncodedValue,
+    data: Option<EncodedValue>,
+    leaf: Option<ProofLeaf>,
+    proof_set: Vec<EncodedValue>,
+}
+
+impl ProofStep {
+    pub fn execute(
+        self,
+        name: &str,
+        tree: &dyn MerkleTreeTestAdaptor,
+    ) -> Result<(), TestError> {
+        let key = decode_bytes32(self.key)?;
+        let proof_set = self
+            .proof_set
+            .into_iter()
+            .map(decode_bytes32)
+            .collect::<Result<Vec<_>, _>>()?;
+        let data = self.data.map(EncodedValue::into_bytes).transpose()?;
+        let expected_proof = match (&data, self.leaf) {
+            (Some(_), _) => Proof::Inclusion(InclusionProof { proof_set }),
+            (None, Some(leaf)) => Proof::Exclusion(ExclusionProof {
+                proof_set,
+                leaf: ExclusionLeaf::Leaf {
+                    key: decode_bytes32(leaf.key)?,
+                    data_hash: decode_bytes32(leaf.data_hash)?,
+                },
+            }),
+            (None, None) => Proof::Exclusion(ExclusionProof {
+                proof_set,
+                leaf: ExclusionLeaf::Placeholder,
+            }),
+        };
+
+        let proof = tree.prove(&key);
+        if proof != expected_proof {
+            return Err(TestError::Failed(
+                name.to_string(),
+                format!("Proof of key 0x{} does not match", hex::encode(key)),
+            ))
+        }
+
+        let key = MerkleTreeKey::new_without_hash(key);
+        if verify(&tree.root(), key, data, &proof) {
+            Ok(())
+        } else {
+            Err(TestError::Failed(
+                name.to_string(),
+                format!(
+                    "Proof of key 0x{} is not verified",
+                    hex::encode(Bytes32::from(key))
+                ),
+            ))
+        }
+    }
 }
 
 #[derive(Deserialize)]
@@ -101,6 +187,8 @@ pub struct Test {
     name: String,
     expected_root: EncodedValue,
     steps: Vec<Step>,
+    #[serde(default)]
+    proofs: Vec<ProofStep>,
 }
 
 impl Test {
@@ -115,10 +203,8 @@ impl Test {
         let root = tree.root();
         let expected_root: Bytes32 = self.expected_root.into_bytes()?.try_into().unwrap();
 
-        if root == expected_root {
-            Ok(())
-        } else {
-            Err(TestError::Failed(
+        if root != expected_root {
+            return Err(TestError::Failed(
                 self.name,
                 format!(
                     "Root 0x{} does not match expected root 0x{}",
@@ -127,5 +213,11 @@ impl Test {
                 ),
             ))
         }
+
+        for proof in self.proofs {
+            proof.execute(&self.name, &tree)?
+        }
+
+        Ok(())
     }
 }
@@ -13,8 +13,10 @@ use crate::{
     interpreter::{
         InitialBalances,
         Interpreter,
+        Memory,
     },
 };
+use core::fmt;
 use derivative::Derivative;
 
 use fuel_tx::ScriptExecutionResult;
@@ -31,8 +33,8 @@ pub struct Backtrace {
     call_stack: Vec<CallFrame>,
     contract: ContractId,
     registers: [Word; VM_REGISTER_COUNT],
-    #[derivative(Debug(format_with = "fmt_truncated_hex::<16>"))]
-    memory: Vec<u8>,
+    #[derivative(Debug(format_with = "fmt_truncated_memory::<16>"))]
+    memory: Memory,
     result: ScriptExecutionResult,
     initial_balances: InitialBalances,
 }
@@ -47,7 +49,7 @@ impl Backtrace {
     ) -> Self {
         let call_stack = vm.call_stack().to_owned();
         let contract = vm.internal_contract_or_default();
-        let memory = vm.memory().to_owned();
+        let memory = vm.memory().clone();
         let initial_balances = vm.initial_balances().clone();
         let mut registers = [0; VM_REGISTER_COUNT];
 
@@ -79,8 +81,8 @@ impl Backtrace {
     }
 
     /// Memory of the VM when the error occurred.
-    pub fn memory(&self) -> &[u8] {
-        self.memory.as_slice()
+    pub const fn memory(&self) -> &Memory {
+        &self.memory
     }
 
     /// [`ScriptExecutionResult`] of the error that caused this backtrace.
@@ -100,7 +102,7 @@ impl Backtrace {
         Vec<CallFrame>,
         ContractId,
         [Word; VM_REGISTER_COUNT],
-        Vec<u8>,
+        Memory,
         ScriptExecutionResult,
         InitialBalances,
     ) {
@@ -123,3 +125,12 @@ impl Backtrace {
         )
     }
 }
+
+/// Format the start of the memory like the other truncated byte fields.
+fn fmt_truncated_memory<const N: usize>(
+    memory: &Memory,
+    f: &mut fmt::Formatter,
+) -> fmt::Result {
+    // Reading one byte past `N` keeps the ellipsis of the truncated output.
+    fmt_truncated_hex::<N>(&memory.read(0..N + 1).into_owned(), f)
+}
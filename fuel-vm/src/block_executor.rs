@@ -0,0 +1,438 @@
+//! Execution of the transactions of a block, followed by the `Mint` transaction
+//! paying their fees to the coinbase contract.
+
+use alloc::{
+    collections::BTreeMap,
+    vec::Vec,
+};
+
+use fuel_tx::{
+    field::{
+        Inputs,
+        Outputs,
+    },
+    input,
+    output,
+    Chargeable,
+    CheckError,
+    ConsensusParameters,
+    Input,
+    Mint,
+    Output,
+    Receipt,
+    Script,
+    Transaction,
+    TransactionFee,
+    TxId,
+    TxPointer,
+    UtxoId,
+};
+use fuel_types::{
+    canonical::Serialize,
+    AssetId,
+    Bytes32,
+    ContractId,
+    Word,
+};
+
+use crate::{
+    checked_transaction::{
+        Checked,
+        CheckedTransaction,
+        IntoChecked,
+    },
+    crypto::ephemeral_merkle_root,
+    error::InterpreterError,
+    interpreter::{
+        CheckedMetadata,
+        ExecutableTransaction,
+        Interpreter,
+        InterpreterParams,
+    },
+    state::StateTransition,
+    storage::InterpreterStorage,
+};
+
+#[cfg(feature = "std")]
+mod parallel;
+#[cfg(feature = "std")]
+mod versioned;
+
+/// Result of the execution of a transaction of a block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutedTransaction {
+    /// Id of the transaction.
+    pub id: TxId,
+    /// The transaction, with the outputs updated by the execution.
+    pub transaction: Transaction,
+    /// Receipts of the execution.
+    pub receipts: Vec<Receipt>,
+    /// Gas used by the execution, including the predicates. The gas left out of the
+    /// limit is refunded.
+    pub gas_used: Word,
+    /// Fee charged for the transaction, in `fee_asset_id`.
+    pub fee: Word,
+    /// Asset the fee is paid in, the base asset of the chain.
+    pub fee_asset_id: AssetId,
+    /// Whether the execution reverted, in which case its changes to the storage were
+    /// undone.
+    pub reverted: bool,
+}
+
+/// Result of the execution of a block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutedBlock {
+    /// The executed transactions, in order.
+    pub transactions: Vec<ExecutedTransaction>,
+    /// The trailing `Mint` transaction crediting the fees to the coinbase contract.
+    pub mint: Mint,
+    /// Receipts of the execution of the `Mint` transaction.
+    pub mint_receipts: Vec<Receipt>,
+    /// Fees collected from the transactions, by asset.
+    pub fees: BTreeMap<AssetId, Word>,
+    /// Merkle root of the ids of the transactions, the `Mint` transaction included.
+    pub transactions_root: Bytes32,
+    /// Merkle root of the receipts of all the transactions, the `Mint` transaction
+    /// included, in order.
+    pub receipts_root: Bytes32,
+}
+
+impl ExecutedBlock {
+    /// Gas used by all the transactions of the block.
+    pub fn gas_used(&self) -> Word {
+        self.transactions
+            .iter()
+            .fold(0, |gas, tx| gas.saturating_add(tx.gas_used))
+    }
+}
+
+/// Reasons a block can't be executed.
+///
+/// The storage keeps the changes made by the transactions executed before the
+/// failure.
+#[derive(Debug, derive_more::Display)]
+#[display(bound = "StorageError: core::fmt::Display")]
+pub enum BlockExecutionError<StorageError> {
+    /// A transaction of the block couldn't be executed.
+    #[display(fmt = "Transaction {index} failed: {error}")]
+    Transaction {
+        /// Index of the transaction in the block.
+        index: usize,
+        /// Error of the execution.
+        error: InterpreterError<StorageError>,
+    },
+    /// The block contains a `Mint` transaction, which is produced by the executor.
+    #[display(fmt = "Unexpected mint transaction at index {index}")]
+    UnexpectedMint {
+        /// Index of the transaction in the block.
+        index: usize,
+    },
+    /// The `Mint` transaction couldn't be produced or executed.
+    #[display(fmt = "Failed to mint the fees: {_0}")]
+    Mint(InterpreterError<StorageError>),
+}
+
+/// Executor of the transactions of a block.
+///
+/// The transactions are executed in order against the storage, and the fees of all
+/// the transactions are credited to the coinbase contract of the storage by a
+/// trailing `Mint` transaction.
+///
+/// The storage changes of reverted scripts are undone by the interpreter: it takes
+/// a checkpoint with [`InterpreterStorage::checkpoint`] before running each script
+/// and returns to it with [`InterpreterStorage::revert_to`] if the script reverts or
+/// panics. The storage must implement these methods to actually undo its changes.
+#[derive(Debug, Clone)]
+pub struct BlockExecutor<S> {
+    storage: S,
+    consensus_params: ConsensusParameters,
+    interpreter_params: InterpreterParams,
+}
+
+impl<S> BlockExecutor<S> {
+    /// Create a new executor of blocks over `storage`.
+    pub fn new(storage: S, consensus_params: ConsensusParameters) -> Self {
+        let interpreter_params = InterpreterParams::from(&consensus_params);
+        Self {
+            storage,
+            consensus_params,
+            interpreter_params,
+        }
+    }
+
+    /// Consensus parameters the transactions are executed with.
+    pub const fn consensus_params(&self) -> &ConsensusParameters {
+        &self.consensus_params
+    }
+
+    /// Remove the executor, returning the storage.
+    pub fn into_storage(self) -> S {
+        self.storage
+    }
+}
+
+impl<S> AsRef<S> for BlockExecutor<S> {
+    fn as_ref(&self) -> &S {
+        &self.storage
+    }
+}
+
+impl<S> AsMut<S> for BlockExecutor<S> {
+    fn as_mut(&mut self) -> &mut S {
+        &mut self.storage
+    }
+}
+
+impl<S> BlockExecutor<S>
+where
+    S: InterpreterStorage,
+{
+    /// Execute the transactions of a block in order, then the `Mint` transaction
+    /// crediting their fees to the coinbase contract.
+    ///
+    /// The `Mint` transaction is produced by the executor, so `transactions` can't
+    /// contain one.
+    pub fn execute_block<I>(
+        &mut self,
+        transactions: I,
+    ) -> Result<ExecutedBlock, BlockExecutionError<S::DataError>>
+    where
+        I: IntoIterator<Item = CheckedTransaction>,
+    {
+        let mut executed = Vec::new();
+
+        for (index, tx) in transactions.into_iter().enumerate() {
+            let result = match tx {
+                CheckedTransaction::Script(tx) => self.execute_transaction(tx),
+                CheckedTransaction::Create(tx) => self.execute_transaction(tx),
+                CheckedTransaction::Mint(_) => {
+                    return Err(BlockExecutionError::UnexpectedMint { index })
+                }
+            };
+            executed.push(
+                result
+                    .map_err(|error| BlockExecutionError::Transaction { index, error })?,
+            );
+        }
+
+        self.finalize_block(executed)
+    }
+
+    fn execute_transaction<Tx>(
+        &mut self,
+        tx: Checked<Tx>,
+    ) -> Result<ExecutedTransaction, InterpreterError<S::DataError>>
+    where
+        Tx: ExecutableTransaction,
+        <Tx as IntoChecked>::Metadata: CheckedMetadata,
+    {
+        execute_transaction(
+            &mut self.storage,
+            &self.interpreter_params,
+            &self.consensus_params,
+            tx,
+        )
+    }
+
+    /// Sum the fees of the executed transactions by asset, mint them and compute the
+    /// roots of the block.
+    fn finalize_block(
+        &mut self,
+        executed: Vec<ExecutedTransaction>,
+    ) -> Result<ExecutedBlock, BlockExecutionError<S::DataError>> {
+        let mut fees = BTreeMap::new();
+        for tx in &executed {
+            let fee: &mut Word = fees.entry(tx.fee_asset_id).or_default();
+            *fee = fee.checked_add(tx.fee).ok_or(BlockExecutionError::Mint(
+                CheckError::ArithmeticOverflow.into(),
+            ))?;
+        }
+
+        let (mint_id, mint) = self
+            .mint(&executed, &fees)
+            .map_err(BlockExecutionError::Mint)?;
+        let (_, mint, mint_receipts) = mint.into_inner();
+
+        let transactions_root = ephemeral_merkle_root(
+            executed
+                .iter()
+                .map(|tx| tx.id)
+                .chain(core::iter::once(mint_id))
+                .collect::<Vec<_>>()
+                .iter(),
+        );
+        let receipts: Vec<_> = executed
+            .iter()
+            .flat_map(|tx| tx.receipts.iter())
+            .chain(mint_receipts.iter())
+            .map(Receipt::to_bytes)
+            .collect();
+        let receipts_root = ephemeral_merkle_root(receipts.iter());
+
+        Ok(ExecutedBlock {
+            transactions: executed,
+            mint,
+            mint_receipts,
+            fees,
+            transactions_root,
+            receipts_root,
+        })
+    }
+
+    /// Execute the `Mint` transaction crediting the fees to the coinbase contract,
+    /// returning its id and its state transition.
+    ///
+    /// A `Mint` transaction only mints the base asset, which is the asset all the
+    /// fees are paid in. The coinbase contract input points to the last transaction
+    /// of the block with the coinbase as contract output. Its UTXO id and pointer
+    /// are left empty if there is none, since the storage doesn't know them.
+    fn mint(
+        &mut self,
+        executed: &[ExecutedTransaction],
+        fees: &BTreeMap<AssetId, Word>,
+    ) -> Result<(TxId, StateTransition<Mint>), InterpreterError<S::DataError>> {
+        let block_height = self
+            .storage
+            .block_height()
+            .map_err(InterpreterError::Storage)?;
+        let coinbase = self.storage.coinbase().map_err(InterpreterError::Storage)?;
+        let asset_id = self.consensus_params.base_asset_id;
+        if fees
+            .iter()
+            .any(|(fee_asset_id, fee)| *fee_asset_id != asset_id && *fee > 0)
+        {
+            return Err(CheckError::TransactionMintNonBaseAsset.into())
+        }
+        let amount = fees.get(&asset_id).copied().unwrap_or_default();
+        let tx_pointer = |index: usize| {
+            u16::try_from(index)
+                .map(|index| TxPointer::new(block_height, index))
+                .map_err(|_| CheckError::ArithmeticOverflow)
+        };
+
+        let (utxo_id, contract_tx_pointer) =
+            match last_contract_output(executed, &coinbase) {
+                Some((index, utxo_id)) => (utxo_id, tx_pointer(index)?),
+                None => Default::default(),
+            };
+
+        let mint = Transaction::mint(
+            tx_pointer(executed.len())?,
+            input::contract::Contract {
+                utxo_id,
+                balance_root: Default::default(),
+                state_root: Default::default(),
+                tx_pointer: contract_tx_pointer,
+                contract_id: coinbase,
+            },
+            output::contract::Contract {
+                input_index: 0,
+                balance_root: Default::default(),
+                state_root: Default::default(),
+            },
+            amount,
+            asset_id,
+        )
+        .into_checked_basic(block_height, &self.consensus_params)?;
+        let id = mint.id();
+
+        let mut interpreter = Interpreter::<_, Script>::with_storage(
+            &mut self.storage,
+            self.interpreter_params.clone(),
+        );
+        Ok((id, interpreter.execute_mint(mint)?))
+    }
+}
+
+/// Index of the last executed transaction with `contract` as contract output, and the
+/// UTXO id of that output.
+fn last_contract_output(
+    executed: &[ExecutedTransaction],
+    contract: &ContractId,
+) -> Option<(usize, UtxoId)> {
+    executed.iter().enumerate().rev().find_map(|(index, tx)| {
+        let (inputs, outputs) = match &tx.transaction {
+            Transaction::Script(script) => (script.inputs(), script.outputs()),
+            Transaction::Create(create) => (create.inputs(), create.outputs()),
+            Transaction::Mint(_) => return None,
+        };
+        outputs
+            .iter()
+            .enumerate()
+            .find_map(|(output_index, output)| {
+                let input_index = match output {
+                    Output::Contract(output) => output.input_index,
+                    _ => return None,
+                };
+                match inputs.get(input_index as usize)? {
+                    Input::Contract(input) if input.contract_id == *contract => {
+                        let output_index = u8::try_from(output_index).ok()?;
+                        Some((index, UtxoId::new(tx.id, output_index)))
+                    }
+                    _ => None,
+                }
+            })
+    })
+}
+
+/// Execute a transaction on `storage`.
+fn execute_transaction<S, Tx>(
+    storage: &mut S,
+    interpreter_params: &InterpreterParams,
+    consensus_params: &ConsensusParameters,
+    tx: Checked<Tx>,
+) -> Result<ExecutedTransaction, InterpreterError<S::DataError>>
+where
+    S: InterpreterStorage,
+    Tx: ExecutableTransaction,
+    <Tx as IntoChecked>::Metadata: CheckedMetadata,
+{
+    let id = tx.id();
+    let mut interpreter =
+        Interpreter::<_, Tx>::with_storage(&mut *storage, interpreter_params.clone());
+    let state = interpreter.transact(tx)?;
+
+    let reverted = state.should_revert();
+    let receipts = state.receipts().to_vec();
+    let transaction = state.tx().clone();
+
+    // Scripts start with the gas limit minus the gas of the predicates, and are
+    // refunded the gas left in `$ggas`. The other transactions are only charged for
+    // their predicates.
+    let remaining_gas = if transaction.as_script().is_some() {
+        Some(interpreter.remaining_gas())
+    } else {
+        transaction
+            .limit()
+            .checked_sub(transaction.gas_used_by_predicates())
+    }
+    .ok_or(CheckError::ArithmeticOverflow)?;
+    let gas_used = transaction
+        .limit()
+        .checked_sub(remaining_gas)
+        .ok_or(CheckError::ArithmeticOverflow)?;
+    let fee = fee(consensus_params, &transaction, remaining_gas)
+        .ok_or(CheckError::ArithmeticOverflow)?;
+
+    Ok(ExecutedTransaction {
+        id,
+        transaction: transaction.into(),
+        receipts,
+        gas_used,
+        fee,
+        fee_asset_id: interpreter_params.base_asset_id,
+        reverted,
+    })
+}
+
+/// Fee of the transaction, refunding the gas it didn't use.
+fn fee<Tx: Chargeable>(
+    consensus_params: &ConsensusParameters,
+    tx: &Tx,
+    remaining_gas: Word,
+) -> Option<Word> {
+    let fee_params = &consensus_params.fee_params;
+    let max_fee = TransactionFee::checked_from_tx(fee_params, tx)?.max_fee();
+    let refund = TransactionFee::gas_refund_value(fee_params, remaining_gas, tx.price())?;
+    max_fee.checked_sub(refund)
+}
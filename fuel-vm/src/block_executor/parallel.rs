@@ -0,0 +1,227 @@
+//! Optimistic concurrent execution of the script transactions of a block.
+//!
+//! The scripts between two `Create` transactions are executed in rounds, in the
+//! manner of Block-STM. Every round executes the scripts that aren't validated yet
+//! concurrently, each against a [`VersionedStorage`] that sees the entries written by
+//! the last execution of the scripts before it. The scripts are then validated in
+//! order: a script whose reads no longer match the last entries written before it
+//! conflicts with a previous script and is executed again in the next round. The
+//! validated prefix of the scripts only depends on final writes, so every round
+//! validates at least one more script, and the result is the one of the sequential
+//! execution.
+
+use alloc::{
+    collections::BTreeMap,
+    vec::Vec,
+};
+use core::{
+    num::NonZeroUsize,
+    sync::atomic::{
+        AtomicUsize,
+        Ordering,
+    },
+};
+
+use fuel_tx::Script;
+
+use crate::{
+    checked_transaction::{
+        Checked,
+        CheckedTransaction,
+    },
+    error::InterpreterError,
+    storage::InterpreterStorage,
+};
+
+use super::{
+    execute_transaction,
+    versioned::{
+        MultiVersionMemory,
+        Version,
+        VersionedKey,
+        VersionedStorage,
+        VersionedValue,
+    },
+    BlockExecutionError,
+    BlockExecutor,
+    ExecutedBlock,
+    ExecutedTransaction,
+};
+
+/// Last execution of a script.
+struct Execution<E> {
+    incarnation: u32,
+    reads: BTreeMap<VersionedKey, Option<Version>>,
+    writes: BTreeMap<VersionedKey, VersionedValue>,
+    result: Result<ExecutedTransaction, InterpreterError<E>>,
+}
+
+impl<S> BlockExecutor<S>
+where
+    S: InterpreterStorage + Sync,
+    S::DataError: Send,
+{
+    /// Execute the transactions of a block like [`Self::execute_block`], running the
+    /// independent scripts concurrently on up to `threads` threads.
+    ///
+    /// Scripts reading the contract state, balances or code written by a previous
+    /// script of the block are executed again once the previous one is final, so the
+    /// storage, receipts and roots are identical to the ones of the sequential
+    /// execution. `Create` transactions are executed sequentially.
+    pub fn execute_block_parallel<I>(
+        &mut self,
+        transactions: I,
+        threads: NonZeroUsize,
+    ) -> Result<ExecutedBlock, BlockExecutionError<S::DataError>>
+    where
+        I: IntoIterator<Item = CheckedTransaction>,
+    {
+        let mut executed = Vec::new();
+        let mut scripts = Vec::new();
+
+        for (index, tx) in transactions.into_iter().enumerate() {
+            match tx {
+                CheckedTransaction::Script(tx) => scripts.push(tx),
+                CheckedTransaction::Create(tx) => {
+                    self.execute_scripts(&mut executed, &mut scripts, threads)?;
+                    let tx = self.execute_transaction(tx).map_err(|error| {
+                        BlockExecutionError::Transaction { index, error }
+                    })?;
+                    executed.push(tx);
+                }
+                CheckedTransaction::Mint(_) => {
+                    self.execute_scripts(&mut executed, &mut scripts, threads)?;
+                    return Err(BlockExecutionError::UnexpectedMint { index })
+                }
+            }
+        }
+        self.execute_scripts(&mut executed, &mut scripts, threads)?;
+
+        self.finalize_block(executed)
+    }
+
+    /// Execute the `scripts` following the `executed` transactions, and write their
+    /// changes to the storage.
+    fn execute_scripts(
+        &mut self,
+        executed: &mut Vec<ExecutedTransaction>,
+        scripts: &mut Vec<Checked<Script>>,
+        threads: NonZeroUsize,
+    ) -> Result<(), BlockExecutionError<S::DataError>> {
+        let scripts = core::mem::take(scripts);
+        let mut memory = MultiVersionMemory::default();
+        let mut executions: Vec<Option<Execution<S::DataError>>> =
+            scripts.iter().map(|_| None).collect();
+        let mut pending: Vec<usize> = (0..scripts.len()).collect();
+        let mut validated = 0;
+
+        while validated < scripts.len() {
+            let incarnations: Vec<u32> = executions
+                .iter()
+                .map(|execution| {
+                    execution
+                        .as_ref()
+                        .map_or(0, |execution| execution.incarnation + 1)
+                })
+                .collect();
+            let results = run_concurrently(&pending, threads, |index| {
+                let incarnation = incarnations[index];
+                let mut storage = VersionedStorage::new(&self.storage, &memory, index);
+                let result = execute_transaction(
+                    &mut storage,
+                    &self.interpreter_params,
+                    &self.consensus_params,
+                    scripts[index].clone(),
+                );
+                let (reads, writes) = storage.into_read_write_sets();
+                Execution {
+                    incarnation,
+                    reads,
+                    writes,
+                    result,
+                }
+            });
+
+            for (index, execution) in results {
+                let previous = executions[index]
+                    .as_ref()
+                    .into_iter()
+                    .flat_map(|previous| previous.writes.keys());
+                memory.replace(index, execution.incarnation, previous, &execution.writes);
+                executions[index] = Some(execution);
+            }
+
+            pending.clear();
+            let mut prefix_valid = true;
+            for (index, execution) in executions.iter().enumerate().skip(validated) {
+                let valid = execution
+                    .as_ref()
+                    .is_some_and(|execution| memory.validate(index, &execution.reads));
+                if valid && prefix_valid {
+                    validated = index + 1;
+                } else {
+                    prefix_valid = false;
+                    if !valid {
+                        pending.push(index);
+                    }
+                }
+            }
+        }
+
+        let first_index = executed.len();
+        for (index, execution) in executions.into_iter().flatten().enumerate() {
+            let tx =
+                execution
+                    .result
+                    .map_err(|error| BlockExecutionError::Transaction {
+                        index: first_index + index,
+                        error,
+                    })?;
+            for (key, value) in &execution.writes {
+                value.apply(key, &mut self.storage).map_err(|error| {
+                    BlockExecutionError::Transaction {
+                        index: first_index + index,
+                        error: InterpreterError::Storage(error),
+                    }
+                })?;
+            }
+            executed.push(tx);
+        }
+
+        Ok(())
+    }
+}
+
+/// Call `f` with every index of `indices` on up to `threads` threads.
+fn run_concurrently<T, F>(
+    indices: &[usize],
+    threads: NonZeroUsize,
+    f: F,
+) -> Vec<(usize, T)>
+where
+    T: Send,
+    F: Fn(usize) -> T + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let worker = || {
+        let mut results = Vec::new();
+        while let Some(&index) = indices.get(next.fetch_add(1, Ordering::Relaxed)) {
+            results.push((index, f(index)));
+        }
+        results
+    };
+
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads.get().min(indices.len()))
+            .map(|_| scope.spawn(worker))
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| {
+                worker
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    })
+}
@@ -0,0 +1,511 @@
+//! Multi-version storage of the transactions executed concurrently.
+
+use alloc::{
+    borrow::Cow,
+    collections::BTreeMap,
+    vec::Vec,
+};
+use core::cell::RefCell;
+
+use fuel_storage::{
+    Mappable,
+    MerkleRoot,
+    MerkleRootStorage,
+    StorageInspect,
+    StorageMutate,
+    StorageRead,
+    StorageSize,
+};
+use fuel_tx::Contract;
+use fuel_types::{
+    BlockHeight,
+    Bytes32,
+    ContractId,
+    Salt,
+    Word,
+};
+
+use crate::storage::{
+    add_one,
+    ContractsAssetKey,
+    ContractsAssets,
+    ContractsAssetsStorage,
+    ContractsInfo,
+    ContractsRawCode,
+    ContractsState,
+    ContractsStateKey,
+    InterpreterStorage,
+    StorageCheckpoint,
+};
+
+/// Version of an entry: the index of the transaction that wrote it, and the
+/// incarnation of the transaction, incremented every time it is executed again.
+pub(super) type Version = (usize, u32);
+
+/// Key of a versioned entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) enum VersionedKey {
+    State(ContractsStateKey),
+    Asset(ContractsAssetKey),
+    Code(ContractId),
+    Info(ContractId),
+}
+
+/// Value of a versioned entry. `None` is a removed entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum VersionedValue {
+    State(Option<Bytes32>),
+    Asset(Option<Word>),
+    Code(Option<Contract>),
+    Info(Option<(Salt, Bytes32)>),
+}
+
+impl VersionedValue {
+    /// Write the value to `storage`.
+    pub fn apply<S>(
+        &self,
+        key: &VersionedKey,
+        storage: &mut S,
+    ) -> Result<(), S::DataError>
+    where
+        S: InterpreterStorage,
+    {
+        match (key, self) {
+            (VersionedKey::State(key), VersionedValue::State(value)) => {
+                write::<ContractsState, _>(storage, key, value.as_ref())
+            }
+            (VersionedKey::Asset(key), VersionedValue::Asset(value)) => {
+                write::<ContractsAssets, _>(storage, key, value.as_ref())
+            }
+            (VersionedKey::Code(key), VersionedValue::Code(value)) => {
+                write::<ContractsRawCode, _>(
+                    storage,
+                    key,
+                    value.as_ref().map(AsRef::as_ref),
+                )
+            }
+            (VersionedKey::Info(key), VersionedValue::Info(value)) => {
+                write::<ContractsInfo, _>(storage, key, value.as_ref())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn write<Type: Mappable, S>(
+    storage: &mut S,
+    key: &Type::Key,
+    value: Option<&Type::Value>,
+) -> Result<(), S::Error>
+where
+    S: StorageMutate<Type>,
+{
+    match value {
+        Some(value) => storage.insert(key, value)?,
+        None => storage.remove(key)?,
+    };
+    Ok(())
+}
+
+/// A table whose entries are versioned.
+pub(super) trait VersionedTable: Mappable {
+    fn versioned_key(key: &Self::Key) -> VersionedKey;
+
+    fn versioned_value(value: Option<&Self::Value>) -> VersionedValue;
+
+    fn owned_value(value: &VersionedValue) -> Option<Self::OwnedValue>;
+}
+
+impl VersionedTable for ContractsState {
+    fn versioned_key(key: &ContractsStateKey) -> VersionedKey {
+        VersionedKey::State(*key)
+    }
+
+    fn versioned_value(value: Option<&Bytes32>) -> VersionedValue {
+        VersionedValue::State(value.copied())
+    }
+
+    fn owned_value(value: &VersionedValue) -> Option<Bytes32> {
+        match value {
+            VersionedValue::State(value) => *value,
+            _ => None,
+        }
+    }
+}
+
+impl VersionedTable for ContractsAssets {
+    fn versioned_key(key: &ContractsAssetKey) -> VersionedKey {
+        VersionedKey::Asset(*key)
+    }
+
+    fn versioned_value(value: Option<&Word>) -> VersionedValue {
+        VersionedValue::Asset(value.copied())
+    }
+
+    fn owned_value(value: &VersionedValue) -> Option<Word> {
+        match value {
+            VersionedValue::Asset(value) => *value,
+            _ => None,
+        }
+    }
+}
+
+impl VersionedTable for ContractsRawCode {
+    fn versioned_key(key: &ContractId) -> VersionedKey {
+        VersionedKey::Code(*key)
+    }
+
+    fn versioned_value(value: Option<&[u8]>) -> VersionedValue {
+        VersionedValue::Code(value.map(Contract::from))
+    }
+
+    fn owned_value(value: &VersionedValue) -> Option<Contract> {
+        match value {
+            VersionedValue::Code(value) => value.clone(),
+            _ => None,
+        }
+    }
+}
+
+impl VersionedTable for ContractsInfo {
+    fn versioned_key(key: &ContractId) -> VersionedKey {
+        VersionedKey::Info(*key)
+    }
+
+    fn versioned_value(value: Option<&(Salt, Bytes32)>) -> VersionedValue {
+        VersionedValue::Info(value.copied())
+    }
+
+    fn owned_value(value: &VersionedValue) -> Option<(Salt, Bytes32)> {
+        match value {
+            VersionedValue::Info(value) => *value,
+            _ => None,
+        }
+    }
+}
+
+/// Entries written by the transactions of the block, with every version of them.
+#[derive(Debug, Default)]
+pub(super) struct MultiVersionMemory {
+    entries: BTreeMap<VersionedKey, BTreeMap<usize, (u32, VersionedValue)>>,
+}
+
+impl MultiVersionMemory {
+    /// The last entry written by a transaction before `index`.
+    pub fn latest(
+        &self,
+        key: &VersionedKey,
+        index: usize,
+    ) -> Option<(Version, &VersionedValue)> {
+        let (tx, (incarnation, value)) =
+            self.entries.get(key)?.range(..index).next_back()?;
+        Some(((*tx, *incarnation), value))
+    }
+
+    /// Replace the `previous` entries written by the transaction `index` with
+    /// `writes`.
+    pub fn replace<'a>(
+        &mut self,
+        index: usize,
+        incarnation: u32,
+        previous: impl Iterator<Item = &'a VersionedKey>,
+        writes: &BTreeMap<VersionedKey, VersionedValue>,
+    ) {
+        for key in previous {
+            if let Some(versions) = self.entries.get_mut(key) {
+                versions.remove(&index);
+            }
+        }
+        for (key, value) in writes {
+            self.entries
+                .entry(*key)
+                .or_default()
+                .insert(index, (incarnation, value.clone()));
+        }
+    }
+
+    /// Returns true if the entries read by the transaction `index` are still the
+    /// last ones written before it.
+    pub fn validate(
+        &self,
+        index: usize,
+        reads: &BTreeMap<VersionedKey, Option<Version>>,
+    ) -> bool {
+        reads.iter().all(|(key, version)| {
+            self.latest(key, index).map(|(version, _)| version) == *version
+        })
+    }
+}
+
+/// Storage of a transaction executed concurrently with the others of its block.
+///
+/// Reads see the entries written by the previous transactions of the block, or the
+/// ones of the underlying storage if none of them wrote the entry, and are recorded
+/// with their version to be validated after the execution. Writes are kept in the
+/// view.
+///
+/// The Merkle roots of the contracts are those of the underlying storage: the
+/// interpreter doesn't read them during the execution.
+///
+/// While a checkpoint is taken, the writes replaced are journaled to be restored when
+/// reverting to it. The reads stay recorded: the reverted execution depended on them.
+pub(super) struct VersionedStorage<'a, S> {
+    storage: &'a S,
+    memory: &'a MultiVersionMemory,
+    index: usize,
+    reads: RefCell<BTreeMap<VersionedKey, Option<Version>>>,
+    writes: BTreeMap<VersionedKey, VersionedValue>,
+    journal: Vec<(VersionedKey, Option<VersionedValue>)>,
+    checkpoints: Vec<usize>,
+}
+
+impl<'a, S> VersionedStorage<'a, S> {
+    pub fn new(storage: &'a S, memory: &'a MultiVersionMemory, index: usize) -> Self {
+        Self {
+            storage,
+            memory,
+            index,
+            reads: Default::default(),
+            writes: Default::default(),
+            journal: Default::default(),
+            checkpoints: Default::default(),
+        }
+    }
+
+    /// The versions of the entries read, and the entries written.
+    #[allow(clippy::type_complexity)]
+    pub fn into_read_write_sets(
+        self,
+    ) -> (
+        BTreeMap<VersionedKey, Option<Version>>,
+        BTreeMap<VersionedKey, VersionedValue>,
+    ) {
+        (self.reads.into_inner(), self.writes)
+    }
+
+    /// The value of the entry written by the transaction or one before it, if any.
+    fn read(&self, key: &VersionedKey) -> Option<Cow<'_, VersionedValue>> {
+        if let Some(value) = self.writes.get(key) {
+            return Some(Cow::Borrowed(value))
+        }
+        let latest = self.memory.latest(key, self.index);
+        self.reads
+            .borrow_mut()
+            .entry(*key)
+            .or_insert(latest.map(|(version, _)| version));
+        latest.map(|(_, value)| Cow::Borrowed(value))
+    }
+
+    /// Write the entry, journaling the write it replaces if a checkpoint is taken.
+    fn write(&mut self, key: VersionedKey, value: VersionedValue) {
+        let previous = self.writes.insert(key, value);
+        if !self.checkpoints.is_empty() {
+            self.journal.push((key, previous));
+        }
+    }
+}
+
+impl<Type: VersionedTable, S> StorageInspect<Type> for VersionedStorage<'_, S>
+where
+    S: StorageInspect<Type>,
+{
+    type Error = <S as StorageInspect<Type>>::Error;
+
+    fn get(
+        &self,
+        key: &<Type as Mappable>::Key,
+    ) -> Result<Option<Cow<'_, <Type as Mappable>::OwnedValue>>, Self::Error> {
+        match self.read(&Type::versioned_key(key)) {
+            Some(value) => Ok(Type::owned_value(&value).map(Cow::Owned)),
+            None => <S as StorageInspect<Type>>::get(self.storage, key),
+        }
+    }
+
+    fn contains_key(&self, key: &<Type as Mappable>::Key) -> Result<bool, Self::Error> {
+        match self.read(&Type::versioned_key(key)) {
+            Some(value) => Ok(Type::owned_value(&value).is_some()),
+            None => <S as StorageInspect<Type>>::contains_key(self.storage, key),
+        }
+    }
+}
+
+impl<S> StorageSize<ContractsRawCode> for VersionedStorage<'_, S>
+where
+    S: StorageSize<ContractsRawCode>,
+{
+    fn size_of_value(&self, key: &ContractId) -> Result<Option<usize>, Self::Error> {
+        match self.read(&VersionedKey::Code(*key)) {
+            Some(value) => {
+                Ok(ContractsRawCode::owned_value(&value).map(|code| code.as_ref().len()))
+            }
+            None => self.storage.size_of_value(key),
+        }
+    }
+}
+
+impl<S> StorageRead<ContractsRawCode> for VersionedStorage<'_, S>
+where
+    S: StorageRead<ContractsRawCode>,
+{
+    fn read(
+        &self,
+        key: &ContractId,
+        buf: &mut [u8],
+    ) -> Result<Option<usize>, Self::Error> {
+        match self.read(&VersionedKey::Code(*key)) {
+            Some(value) => Ok(ContractsRawCode::owned_value(&value).map(|code| {
+                let len = buf.len().min(code.as_ref().len());
+                buf[..len].copy_from_slice(&code.as_ref()[..len]);
+                len
+            })),
+            None => self.storage.read(key, buf),
+        }
+    }
+
+    fn read_alloc(&self, key: &ContractId) -> Result<Option<Vec<u8>>, Self::Error> {
+        match self.read(&VersionedKey::Code(*key)) {
+            Some(value) => Ok(ContractsRawCode::owned_value(&value).map(Into::into)),
+            None => self.storage.read_alloc(key),
+        }
+    }
+}
+
+impl<Type: VersionedTable, S> StorageMutate<Type> for VersionedStorage<'_, S>
+where
+    S: StorageMutate<Type>,
+{
+    fn insert(
+        &mut self,
+        key: &<Type as Mappable>::Key,
+        value: &<Type as Mappable>::Value,
+    ) -> Result<Option<<Type as Mappable>::OwnedValue>, Self::Error> {
+        let existing =
+            <Self as StorageInspect<Type>>::get(self, key)?.map(Cow::into_owned);
+        self.write(Type::versioned_key(key), Type::versioned_value(Some(value)));
+        Ok(existing)
+    }
+
+    fn remove(
+        &mut self,
+        key: &<Type as Mappable>::Key,
+    ) -> Result<Option<<Type as Mappable>::OwnedValue>, Self::Error> {
+        let existing =
+            <Self as StorageInspect<Type>>::get(self, key)?.map(Cow::into_owned);
+        self.write(Type::versioned_key(key), Type::versioned_value(None));
+        Ok(existing)
+    }
+}
+
+impl<Key, Type: VersionedTable, S> MerkleRootStorage<Key, Type>
+    for VersionedStorage<'_, S>
+where
+    S: MerkleRootStorage<Key, Type>,
+{
+    fn root(&self, key: &Key) -> Result<MerkleRoot, Self::Error> {
+        self.storage.root(key)
+    }
+}
+
+impl<S: ContractsAssetsStorage> ContractsAssetsStorage for VersionedStorage<'_, S> {}
+
+impl<S> InterpreterStorage for VersionedStorage<'_, S>
+where
+    S: InterpreterStorage,
+{
+    type DataError = <S as InterpreterStorage>::DataError;
+
+    fn block_height(&self) -> Result<BlockHeight, Self::DataError> {
+        self.storage.block_height()
+    }
+
+    fn timestamp(&self, height: BlockHeight) -> Result<Word, Self::DataError> {
+        self.storage.timestamp(height)
+    }
+
+    fn block_hash(&self, block_height: BlockHeight) -> Result<Bytes32, Self::DataError> {
+        self.storage.block_hash(block_height)
+    }
+
+    fn coinbase(&self) -> Result<ContractId, Self::DataError> {
+        self.storage.coinbase()
+    }
+
+    fn merkle_contract_state_range(
+        &self,
+        id: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> Result<Vec<Option<Cow<'_, Bytes32>>>, Self::DataError> {
+        state_keys(start_key)
+            .take(range as usize)
+            .map(|key| self.merkle_contract_state(id, &key))
+            .collect()
+    }
+
+    fn merkle_contract_state_insert_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        values: &[Bytes32],
+    ) -> Result<Option<()>, Self::DataError> {
+        let mut any_unset_key = false;
+        for (key, value) in state_keys(start_key).zip(values) {
+            any_unset_key |= self
+                .merkle_contract_state_insert(contract, &key, value)?
+                .is_none();
+        }
+        Ok((!any_unset_key).then_some(()))
+    }
+
+    fn merkle_contract_state_remove_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> Result<Option<()>, Self::DataError> {
+        let mut all_set_key = true;
+        for key in state_keys(start_key).take(range as usize) {
+            all_set_key &= self.merkle_contract_state_remove(contract, &key)?.is_some();
+        }
+        Ok(all_set_key.then_some(()))
+    }
+
+    fn checkpoint(&mut self) -> Result<StorageCheckpoint, Self::DataError> {
+        let checkpoint = StorageCheckpoint::new(self.checkpoints.len());
+        self.checkpoints.push(self.journal.len());
+        Ok(checkpoint)
+    }
+
+    fn revert_to(
+        &mut self,
+        checkpoint: StorageCheckpoint,
+    ) -> Result<(), Self::DataError> {
+        if let Some(&len) = self.checkpoints.get(checkpoint.depth()) {
+            for (key, value) in self.journal.drain(len..).rev() {
+                match value {
+                    Some(value) => self.writes.insert(key, value),
+                    None => self.writes.remove(&key),
+                };
+            }
+        }
+        self.checkpoints.truncate(checkpoint.depth());
+        Ok(())
+    }
+
+    fn release(&mut self, checkpoint: StorageCheckpoint) -> Result<(), Self::DataError> {
+        self.checkpoints.truncate(checkpoint.depth());
+        if self.checkpoints.is_empty() {
+            self.journal.clear();
+        }
+        Ok(())
+    }
+}
+
+/// Consecutive storage keys, starting at `start_key`.
+fn state_keys(start_key: &Bytes32) -> impl Iterator<Item = Bytes32> {
+    core::iter::successors(Some(**start_key), |key| {
+        let mut key = *key;
+        (!add_one(&mut key)).then_some(key)
+    })
+    .map(Bytes32::from)
+}
@@ -87,7 +87,11 @@ pub struct Checked<Tx: IntoChecked> {
 }
 
 impl<Tx: IntoChecked> Checked<Tx> {
-    fn new(transaction: Tx, metadata: Tx::Metadata, checks_bitmask: Checks) -> Self {
+    pub(crate) fn new(
+        transaction: Tx,
+        metadata: Tx::Metadata,
+        checks_bitmask: Checks,
+    ) -> Self {
         Checked {
             transaction,
             metadata,
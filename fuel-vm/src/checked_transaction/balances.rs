@@ -31,6 +31,21 @@ pub(crate) fn initial_free_balances<T>(
     params: &FeeParameters,
     base_asset_id: &AssetId,
 ) -> Result<AvailableBalances, CheckError>
+where
+    T: Chargeable + field::Inputs + field::Outputs,
+{
+    free_balances(transaction, params, base_asset_id, Err)
+}
+
+/// Computes the free balances like [`initial_free_balances`], but passes the
+/// insufficient balance errors to `insufficient`. If it returns `Ok`, the missing
+/// amount is virtually provided and the balance is zero after the deduction.
+pub(crate) fn free_balances<T>(
+    transaction: &T,
+    params: &FeeParameters,
+    base_asset_id: &AssetId,
+    mut insufficient: impl FnMut(CheckError) -> Result<(), CheckError>,
+) -> Result<AvailableBalances, CheckError>
 where
     T: Chargeable + field::Inputs + field::Outputs,
 {
@@ -70,12 +85,16 @@ where
 
     let base_asset_balance = non_retryable_balances.entry(*base_asset_id).or_default();
 
-    *base_asset_balance = fee.checked_deduct_total(*base_asset_balance).ok_or(
-        CheckError::InsufficientFeeAmount {
-            expected: fee.max_fee(),
-            provided: *base_asset_balance,
-        },
-    )?;
+    *base_asset_balance = match fee.checked_deduct_total(*base_asset_balance) {
+        Some(balance) => balance,
+        None => {
+            insufficient(CheckError::InsufficientFeeAmount {
+                expected: fee.max_fee(),
+                provided: *base_asset_balance,
+            })?;
+            0
+        }
+    };
 
     // reduce free balances by coin outputs
     for (asset_id, amount) in
@@ -92,14 +111,17 @@ where
         let balance = non_retryable_balances
             .get_mut(asset_id)
             .ok_or(CheckError::TransactionOutputCoinAssetIdNotFound(*asset_id))?;
-        *balance =
-            balance
-                .checked_sub(*amount)
-                .ok_or(CheckError::InsufficientInputAmount {
+        *balance = match balance.checked_sub(*amount) {
+            Some(balance) => balance,
+            None => {
+                insufficient(CheckError::InsufficientInputAmount {
                     asset: *asset_id,
                     expected: *amount,
                     provided: *balance,
                 })?;
+                0
+            }
+        };
     }
 
     Ok(AvailableBalances {
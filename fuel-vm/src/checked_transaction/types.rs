@@ -166,7 +166,7 @@ pub mod mint {
 pub mod script {
     use super::super::{
         balances::{
-            initial_free_balances,
+            free_balances,
             AvailableBalances,
         },
         Checked,
@@ -209,37 +209,50 @@ pub mod script {
         type Metadata = CheckedMetadata;
 
         fn into_checked_basic(
-            mut self,
+            self,
             block_height: BlockHeight,
             consensus_params: &ConsensusParameters,
         ) -> Result<Checked<Self>, CheckError> {
-            let chain_id = consensus_params.chain_id();
-            self.precompute(&chain_id)?;
-            self.check_without_signatures(block_height, consensus_params)?;
+            into_checked_basic_with(self, block_height, consensus_params, Err)
+        }
+    }
 
-            // validate fees and compute free balances
-            let AvailableBalances {
-                non_retryable_balances,
-                retryable_balance,
-                fee,
-            } = initial_free_balances(
-                &self,
-                consensus_params.fee_params(),
-                consensus_params.base_asset_id(),
-            )?;
+    /// Performs the basic checks of [`IntoChecked::into_checked_basic`], passing the
+    /// insufficient balance errors to `insufficient`. If it returns `Ok`, the missing
+    /// amounts are provided virtually.
+    pub(crate) fn into_checked_basic_with(
+        mut tx: Script,
+        block_height: BlockHeight,
+        consensus_params: &ConsensusParameters,
+        insufficient: impl FnMut(CheckError) -> Result<(), CheckError>,
+    ) -> Result<Checked<Script>, CheckError> {
+        let chain_id = consensus_params.chain_id();
+        tx.precompute(&chain_id)?;
+        tx.check_without_signatures(block_height, consensus_params)?;
 
-            let metadata = CheckedMetadata {
-                non_retryable_balances: NonRetryableFreeBalances(non_retryable_balances),
-                retryable_balance: RetryableAmount {
-                    amount: retryable_balance,
-                    base_asset_id: consensus_params.base_asset_id,
-                },
-                block_height,
-                fee,
-                gas_used_by_predicates: 0,
-            };
+        // validate fees and compute free balances
+        let AvailableBalances {
+            non_retryable_balances,
+            retryable_balance,
+            fee,
+        } = free_balances(
+            &tx,
+            consensus_params.fee_params(),
+            consensus_params.base_asset_id(),
+            insufficient,
+        )?;
 
-            Ok(Checked::basic(self, metadata))
-        }
+        let metadata = CheckedMetadata {
+            non_retryable_balances: NonRetryableFreeBalances(non_retryable_balances),
+            retryable_balance: RetryableAmount {
+                amount: retryable_balance,
+                base_asset_id: consensus_params.base_asset_id,
+            },
+            block_height,
+            fee,
+            gas_used_by_predicates: 0,
+        };
+
+        Ok(Checked::basic(tx, metadata))
     }
 }
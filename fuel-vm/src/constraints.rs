@@ -13,9 +13,9 @@ use fuel_types::ContractId;
 #[cfg(test)]
 use fuel_types::canonical::Deserialize;
 
-use crate::{
-    consts::MEM_SIZE,
-    prelude::MemoryRange,
+use crate::interpreter::{
+    Memory,
+    MemoryRange,
 };
 
 pub mod reg_key;
@@ -42,12 +42,12 @@ impl<T> CheckedMemValue<T> {
     }
 
     /// Try to read a value of type `T` from memory.
-    pub fn try_from(self, memory: &[u8; MEM_SIZE]) -> Result<T, PanicReason>
+    pub fn try_from(self, memory: &Memory) -> Result<T, PanicReason>
     where
         T: for<'a> TryFrom<&'a [u8]>,
         PanicReason: for<'a> From<<T as TryFrom<&'a [u8]>>::Error>,
     {
-        Ok(T::try_from(&memory[self.0.usizes()])?)
+        Ok(T::try_from(&memory.read(self.0.usizes()))?)
     }
 
     /// The start of the range.
@@ -62,11 +62,11 @@ impl<T> CheckedMemValue<T> {
 
     #[cfg(test)]
     /// Inspect a value of type `T` from memory.
-    pub fn inspect(self, memory: &[u8; MEM_SIZE]) -> T
+    pub fn inspect(self, memory: &Memory) -> T
     where
         T: Deserialize,
     {
-        T::from_bytes(&memory[self.0.usizes()])
+        T::from_bytes(&memory.read(self.0.usizes()))
             .expect("Inspect failed; invalid value for type")
     }
 }
@@ -77,18 +77,16 @@ impl<const LEN: usize> CheckedMemConstLen<LEN> {
         Ok(Self(MemoryRange::new_const::<_, LEN>(address)?))
     }
 
-    /// Get the memory slice for this range.
-    pub fn read(self, memory: &[u8; MEM_SIZE]) -> &[u8; LEN] {
-        (&memory[self.0.usizes()]).try_into().expect(
-            "This is always correct as the address and LEN are checked on construction.",
-        )
+    /// Read the memory bytes of this range.
+    pub fn read(self, memory: &Memory) -> [u8; LEN] {
+        // This is always correct as the address and LEN are checked on construction.
+        memory.read_bytes(self.0.start)
     }
 
-    /// Get the mutable memory slice for this range.
-    pub fn write(self, memory: &mut [u8; MEM_SIZE]) -> &mut [u8; LEN] {
-        (&mut memory[self.0.usizes()]).try_into().expect(
-            "This is always correct as the address and LEN are checked on construction.",
-        )
+    /// Overwrite the memory bytes of this range.
+    pub fn write(self, memory: &mut Memory, bytes: &[u8; LEN]) {
+        // This is always correct as the address and LEN are checked on construction.
+        memory.write(self.0.start, bytes)
     }
 }
 
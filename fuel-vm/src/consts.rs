@@ -37,6 +37,9 @@ pub const MEM_SIZE: usize = VM_MAX_RAM as usize;
 
 static_assertions::const_assert!(VM_MAX_RAM < usize::MAX as u64);
 
+/// Size of a VM memory page, in bytes. Pages are allocated lazily on their first write.
+pub const MEM_PAGE_SIZE: usize = 16 * 1024;
+
 // no limits to heap for now.
 
 /// Offset for the assets balances in VM memory
@@ -43,6 +43,9 @@ pub enum InterpreterError<StorageError> {
     #[display(fmt = "Execution error")]
     /// The debug state is not initialized; debug routines can't be called.
     DebugStateNotInitialized,
+    #[display(fmt = "Execution error")]
+    /// The requested position isn't in the recorded history of the VM.
+    HistoryUnavailable,
     /// Storage I/O error
     #[display(fmt = "Storage error: {}", _0)]
     Storage(StorageError),
@@ -105,6 +108,7 @@ where
             Self::CheckError(e) => InterpreterError::CheckError(e.clone()),
             Self::NoTransactionInitialized => InterpreterError::NoTransactionInitialized,
             Self::DebugStateNotInitialized => InterpreterError::DebugStateNotInitialized,
+            Self::HistoryUnavailable => InterpreterError::HistoryUnavailable,
             Self::Bug(e) => InterpreterError::Bug(e.clone()),
         }
     }
@@ -138,6 +142,7 @@ where
             (Self::NoTransactionInitialized, Self::NoTransactionInitialized) => true,
             (Self::Storage(a), Self::Storage(b)) => a == b,
             (Self::DebugStateNotInitialized, Self::DebugStateNotInitialized) => true,
+            (Self::HistoryUnavailable, Self::HistoryUnavailable) => true,
 
             _ => false,
         }
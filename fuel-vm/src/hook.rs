@@ -0,0 +1,156 @@
+//! Execution hooks, used to observe every instruction executed by the VM
+
+use alloc::boxed::Box;
+use core::{
+    any::Any,
+    fmt,
+};
+
+use dyn_clone::DynClone;
+use fuel_asm::{
+    Instruction,
+    PanicReason,
+    RawInstruction,
+};
+use fuel_tx::Receipt;
+use fuel_types::{
+    ContractId,
+    Word,
+};
+
+use crate::{
+    consts::VM_REGISTER_COUNT,
+    context::Context,
+};
+
+mod call_tree;
+mod trace;
+
+pub use call_tree::{
+    CallNode,
+    CallOutcome,
+    CallTracer,
+    CallTree,
+};
+pub use trace::{
+    ExecutionTrace,
+    TraceRecorder,
+    TraceStep,
+};
+
+/// State of the VM around the execution of a single instruction.
+#[derive(Debug, Clone, Copy)]
+pub struct InstructionStep<'a> {
+    /// The raw instruction word at `$pc`.
+    pub raw: RawInstruction,
+    /// The decoded instruction, or `None` if the word isn't a valid instruction. The
+    /// VM then panics without executing anything.
+    pub instruction: Option<Instruction>,
+    /// Value of `$pc` when the instruction was fetched.
+    pub pc: Word,
+    /// Execution context of the VM.
+    pub context: &'a Context,
+    /// The register file at the time of the hook call.
+    pub registers: &'a [Word; VM_REGISTER_COUNT],
+    /// Contract that owns the instruction, or `None` for scripts and predicates.
+    pub contract: Option<ContractId>,
+    /// Number of call frames on the stack.
+    pub call_depth: usize,
+}
+
+/// Effects of a single executed instruction.
+#[derive(Debug, Clone, Copy)]
+pub struct InstructionOutcome<'a> {
+    /// Gas charged for the instruction itself, as the decrease of `$ggas`. Gas
+    /// forwarded to a called contract is not included.
+    pub gas_charged: Word,
+    /// Receipts appended by the instruction.
+    pub receipts: &'a [Receipt],
+    /// Reason of the panic, if the instruction panicked.
+    pub panic_reason: Option<PanicReason>,
+}
+
+/// Hook called by the interpreter before and after each executed instruction.
+///
+/// Install it with [`crate::interpreter::Interpreter::with_execution_hook`]. When no
+/// hook is installed, the interpreter skips the bookkeeping entirely.
+pub trait ExecutionHook: AsAny + DynClone {
+    /// Called before the instruction is executed.
+    fn before_instruction(&mut self, _step: &InstructionStep<'_>) {}
+
+    /// Called after the instruction is executed, including when it panicked.
+    fn after_instruction(
+        &mut self,
+        _step: &InstructionStep<'_>,
+        _outcome: &InstructionOutcome<'_>,
+    ) {
+    }
+}
+
+dyn_clone::clone_trait_object!(ExecutionHook);
+
+/// Access to a hook as [`Any`], so the interpreter can hand back its concrete type.
+pub trait AsAny: Any {
+    /// Borrow as [`Any`].
+    fn as_any(&self) -> &dyn Any;
+
+    /// Mutably borrow as [`Any`].
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    /// Convert into a boxed [`Any`].
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<T> AsAny for T
+where
+    T: Any,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+/// Slot holding the execution hook of an interpreter.
+#[derive(Default, Clone)]
+pub(crate) struct HookSlot(Option<Box<dyn ExecutionHook + Send + Sync>>);
+
+impl HookSlot {
+    pub(crate) fn set(&mut self, hook: Box<dyn ExecutionHook + Send + Sync>) {
+        self.0 = Some(hook);
+    }
+
+    pub(crate) fn take(&mut self) -> Option<Box<dyn ExecutionHook + Send + Sync>> {
+        self.0.take()
+    }
+
+    pub(crate) fn get<H>(&self) -> Option<&H>
+    where
+        H: ExecutionHook,
+    {
+        self.0.as_deref()?.as_any().downcast_ref()
+    }
+
+    pub(crate) fn get_mut<H>(&mut self) -> Option<&mut H>
+    where
+        H: ExecutionHook,
+    {
+        self.0.as_deref_mut()?.as_any_mut().downcast_mut()
+    }
+}
+
+impl fmt::Debug for HookSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(_) => write!(f, "HookSlot(enabled)"),
+            None => write!(f, "HookSlot(disabled)"),
+        }
+    }
+}
@@ -0,0 +1,231 @@
+//! Tree of the contract calls performed by an execution
+
+use alloc::vec::Vec;
+
+use fuel_asm::{
+    PanicReason,
+    RegId,
+};
+use fuel_tx::Receipt;
+use fuel_types::{
+    AssetId,
+    Bytes32,
+    ContractId,
+    Word,
+};
+
+use super::{
+    ExecutionHook,
+    InstructionOutcome,
+    InstructionStep,
+};
+
+/// How a call, or the script itself, finished.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CallOutcome {
+    /// Returned a [`Word`] with `RET`.
+    Return(Word),
+    /// Returned data with `RETD`.
+    ReturnData {
+        /// Digest of the returned data.
+        digest: Bytes32,
+        /// The returned data.
+        data: Option<Vec<u8>>,
+    },
+    /// Reverted with `RVRT`.
+    Revert(Word),
+    /// Panicked.
+    Panic(PanicReason),
+}
+
+/// A contract call and the calls it performed in turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CallNode {
+    /// The called contract.
+    pub contract_id: ContractId,
+    /// Amount of coins forwarded to the contract.
+    pub amount: Word,
+    /// Asset of the forwarded coins.
+    pub asset_id: AssetId,
+    /// First parameter of the call.
+    pub param1: Word,
+    /// Second parameter of the call.
+    pub param2: Word,
+    /// `$cgas` when the contract was entered.
+    pub gas_at_entry: Word,
+    /// `$cgas` when the contract was left, or `None` if the execution halted in a
+    /// nested call.
+    pub gas_at_exit: Option<Word>,
+    /// How the call finished, or `None` while it's executing. A call unwound by a
+    /// revert or panic in a nested call has the outcome of that revert or panic.
+    pub outcome: Option<CallOutcome>,
+    /// `Log` and `LogData` receipts emitted by the contract itself.
+    pub logs: Vec<Receipt>,
+    /// Calls performed by the contract.
+    pub calls: Vec<CallNode>,
+}
+
+/// Calls performed by an execution, nested as they happened.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CallTree {
+    /// Calls performed directly by the script.
+    pub calls: Vec<CallNode>,
+    /// `Log` and `LogData` receipts emitted by the script itself.
+    pub logs: Vec<Receipt>,
+    /// How the script finished, if it did.
+    pub outcome: Option<CallOutcome>,
+}
+
+impl CallTree {
+    /// Visit every call of the tree in depth-first order, with its depth starting
+    /// at `1` for the calls performed by the script.
+    pub fn walk<F>(&self, mut f: F)
+    where
+        F: FnMut(usize, &CallNode),
+    {
+        fn walk_inner<F>(nodes: &[CallNode], depth: usize, f: &mut F)
+        where
+            F: FnMut(usize, &CallNode),
+        {
+            for node in nodes {
+                f(depth, node);
+                walk_inner(&node.calls, depth + 1, f);
+            }
+        }
+
+        walk_inner(&self.calls, 1, &mut f)
+    }
+}
+
+/// [`ExecutionHook`] that reconstructs the [`CallTree`] of an execution.
+#[derive(Debug, Default, Clone)]
+pub struct CallTracer {
+    gas_before: Word,
+    open: Vec<CallNode>,
+    tree: CallTree,
+}
+
+impl CallTracer {
+    /// Create an empty tracer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The call tree recorded so far.
+    pub const fn tree(&self) -> &CallTree {
+        &self.tree
+    }
+
+    /// Take the recorded call tree, resetting the tracer.
+    pub fn take_tree(&mut self) -> CallTree {
+        self.open.clear();
+        core::mem::take(&mut self.tree)
+    }
+
+    /// Reset the tracer, discarding the recorded call tree.
+    pub fn clear(&mut self) {
+        self.take_tree();
+    }
+
+    /// Finish the innermost open call, or the script if no call is open.
+    fn finish(&mut self, outcome: CallOutcome, gas_at_exit: Word) {
+        match self.open.pop() {
+            Some(mut node) => {
+                node.outcome = Some(outcome);
+                node.gas_at_exit = Some(gas_at_exit);
+                self.attach(node);
+            }
+            None => self.tree.outcome = Some(outcome),
+        }
+    }
+
+    /// Attach a finished call to its caller.
+    fn attach(&mut self, node: CallNode) {
+        match self.open.last_mut() {
+            Some(parent) => parent.calls.push(node),
+            None => self.tree.calls.push(node),
+        }
+    }
+
+    /// Halt the execution: the innermost open call finishes with `outcome`, and the
+    /// calls and the script it unwinds take the same outcome.
+    fn halt(&mut self, outcome: CallOutcome, gas_at_exit: Word) {
+        self.finish(outcome.clone(), gas_at_exit);
+
+        while let Some(mut node) = self.open.pop() {
+            node.outcome = Some(outcome.clone());
+            self.attach(node);
+        }
+        self.tree.outcome = Some(outcome);
+    }
+
+    fn logs_mut(&mut self) -> &mut Vec<Receipt> {
+        match self.open.last_mut() {
+            Some(node) => &mut node.logs,
+            None => &mut self.tree.logs,
+        }
+    }
+}
+
+impl ExecutionHook for CallTracer {
+    fn before_instruction(&mut self, step: &InstructionStep<'_>) {
+        self.gas_before = step.registers[RegId::CGAS];
+    }
+
+    fn after_instruction(
+        &mut self,
+        _step: &InstructionStep<'_>,
+        outcome: &InstructionOutcome<'_>,
+    ) {
+        let gas_at_exit = self.gas_before.saturating_sub(outcome.gas_charged);
+
+        for receipt in outcome.receipts {
+            match receipt {
+                Receipt::Call {
+                    to,
+                    amount,
+                    asset_id,
+                    gas,
+                    param1,
+                    param2,
+                    ..
+                } => self.open.push(CallNode {
+                    contract_id: *to,
+                    amount: *amount,
+                    asset_id: *asset_id,
+                    param1: *param1,
+                    param2: *param2,
+                    gas_at_entry: *gas,
+                    gas_at_exit: None,
+                    outcome: None,
+                    logs: Vec::new(),
+                    calls: Vec::new(),
+                }),
+                Receipt::Log { .. } | Receipt::LogData { .. } => {
+                    self.logs_mut().push(receipt.clone())
+                }
+                Receipt::Return { val, .. } => {
+                    self.finish(CallOutcome::Return(*val), gas_at_exit)
+                }
+                Receipt::ReturnData { digest, data, .. } => self.finish(
+                    CallOutcome::ReturnData {
+                        digest: *digest,
+                        data: data.clone(),
+                    },
+                    gas_at_exit,
+                ),
+                Receipt::Revert { ra, .. } => {
+                    self.halt(CallOutcome::Revert(*ra), gas_at_exit)
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(reason) = outcome.panic_reason {
+            self.halt(CallOutcome::Panic(reason), gas_at_exit);
+        }
+    }
+}
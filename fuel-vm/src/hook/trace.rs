@@ -0,0 +1,134 @@
+//! Structured execution trace, recorded instruction by instruction
+
+use alloc::vec::Vec;
+
+use fuel_asm::{
+    Instruction,
+    PanicReason,
+    RawInstruction,
+    RegId,
+};
+use fuel_tx::Receipt;
+use fuel_types::{
+    ContractId,
+    Word,
+};
+
+use super::{
+    ExecutionHook,
+    InstructionOutcome,
+    InstructionStep,
+};
+
+/// A single executed instruction of an [`ExecutionTrace`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TraceStep {
+    /// Value of `$pc` when the instruction was fetched.
+    pub pc: Word,
+    /// Offset of the instruction from `$is`.
+    pub offset: Word,
+    /// Contract that owns the instruction, or `None` for scripts and predicates.
+    pub contract: Option<ContractId>,
+    /// Number of call frames on the stack.
+    pub depth: usize,
+    /// The raw instruction word.
+    pub raw: RawInstruction,
+    /// The executed instruction, or `None` if the word isn't a valid instruction.
+    pub instruction: Option<Instruction>,
+    /// Global gas available before the instruction.
+    pub gas: Word,
+    /// Gas charged for the instruction.
+    pub gas_cost: Word,
+    /// Register file before the instruction, if recorded.
+    pub registers: Option<Vec<Word>>,
+    /// Receipts appended by the instruction.
+    pub receipts: Vec<Receipt>,
+    /// Reason of the panic, if the instruction panicked.
+    pub panic_reason: Option<PanicReason>,
+}
+
+/// Instruction-level trace of an execution, similar to the struct logs of the
+/// EVM `debug_traceTransaction`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExecutionTrace {
+    /// Executed instructions, in order.
+    pub steps: Vec<TraceStep>,
+}
+
+impl ExecutionTrace {
+    /// Total gas charged by the traced instructions.
+    pub fn gas_used(&self) -> Word {
+        self.steps.iter().map(|step| step.gas_cost).sum()
+    }
+
+    /// Returns true if any traced instruction panicked.
+    pub fn failed(&self) -> bool {
+        self.steps.iter().any(|step| step.panic_reason.is_some())
+    }
+}
+
+/// [`ExecutionHook`] that records an [`ExecutionTrace`].
+#[derive(Debug, Default, Clone)]
+pub struct TraceRecorder {
+    record_registers: bool,
+    pending: Option<TraceStep>,
+    trace: ExecutionTrace,
+}
+
+impl TraceRecorder {
+    /// Create a recorder that doesn't capture the register file.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Capture the register file before each instruction.
+    pub fn with_registers(mut self) -> Self {
+        self.record_registers = true;
+        self
+    }
+
+    /// The trace recorded so far.
+    pub const fn trace(&self) -> &ExecutionTrace {
+        &self.trace
+    }
+
+    /// Take the recorded trace, leaving an empty one in its place.
+    pub fn take_trace(&mut self) -> ExecutionTrace {
+        core::mem::take(&mut self.trace)
+    }
+}
+
+impl ExecutionHook for TraceRecorder {
+    fn before_instruction(&mut self, step: &InstructionStep<'_>) {
+        let registers = self.record_registers.then(|| step.registers.to_vec());
+
+        self.pending = Some(TraceStep {
+            pc: step.pc,
+            offset: step.pc.saturating_sub(step.registers[RegId::IS]),
+            contract: step.contract,
+            depth: step.call_depth,
+            raw: step.raw,
+            instruction: step.instruction,
+            gas: step.registers[RegId::GGAS],
+            gas_cost: 0,
+            registers,
+            receipts: Vec::new(),
+            panic_reason: None,
+        });
+    }
+
+    fn after_instruction(
+        &mut self,
+        _step: &InstructionStep<'_>,
+        outcome: &InstructionOutcome<'_>,
+    ) {
+        if let Some(mut step) = self.pending.take() {
+            step.gas_cost = outcome.gas_charged;
+            step.receipts = outcome.receipts.to_vec();
+            step.panic_reason = outcome.panic_reason;
+            self.trace.steps.push(step);
+        }
+    }
+}
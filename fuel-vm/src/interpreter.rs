@@ -7,7 +7,15 @@ use crate::{
     consts::*,
     context::Context,
     error::SimpleResult,
-    state::Debugger,
+    hook::{
+        ExecutionHook,
+        HookSlot,
+    },
+    state::{
+        Debugger,
+        StateChanges,
+    },
+    storage::StorageCheckpoint,
 };
 use alloc::{
     borrow::ToOwned,
@@ -50,7 +58,9 @@ use fuel_types::{
     Word,
 };
 
+mod access_list;
 mod alu;
+mod async_storage;
 mod balances;
 mod blockchain;
 mod constructors;
@@ -76,7 +86,10 @@ use crate::profiler::Profiler;
 use crate::profiler::InstructionLocation;
 
 pub use balances::RuntimeBalances;
-pub use memory::MemoryRange;
+pub use memory::{
+    Memory,
+    MemoryRange,
+};
 
 use crate::checked_transaction::{
     CreateCheckedMetadata,
@@ -88,7 +101,7 @@ use crate::checked_transaction::{
 };
 
 use self::{
-    memory::Memory,
+    diff::History,
     receipts::ReceiptsCtx,
 };
 
@@ -103,16 +116,21 @@ use self::{
 #[derive(Debug, Clone)]
 pub struct Interpreter<S, Tx = ()> {
     registers: [Word; VM_REGISTER_COUNT],
-    memory: Memory<MEM_SIZE>,
+    memory: Memory,
     frames: Vec<CallFrame>,
     receipts: ReceiptsCtx,
     tx: Tx,
     initial_balances: InitialBalances,
     storage: S,
     debugger: Debugger,
+    state_changes: StateChanges,
+    /// Storage checkpoint taken before running the script, until it completes.
+    storage_checkpoint: Option<StorageCheckpoint>,
     context: Context,
     balances: RuntimeBalances,
     profiler: Profiler,
+    execution_hook: HookSlot,
+    history: Option<History<S, Tx>>,
     interpreter_params: InterpreterParams,
     /// `PanicContext` after the latest execution. It is consumed by
     /// `append_panic_receipt` and is `PanicContext::None` after consumption.
@@ -205,8 +223,8 @@ pub(crate) enum PanicContext {
 
 impl<S, Tx> Interpreter<S, Tx> {
     /// Returns the current state of the VM memory
-    pub fn memory(&self) -> &[u8] {
-        self.memory.as_slice()
+    pub const fn memory(&self) -> &Memory {
+        &self.memory
     }
 
     /// Returns the current state of the registers
@@ -223,6 +241,14 @@ impl<S, Tx> Interpreter<S, Tx> {
         &self.debugger
     }
 
+    /// Changes made to the storage by the current transaction.
+    ///
+    /// The changes rewound with [`Self::step_back`] or [`Self::goto`] are still
+    /// reported.
+    pub const fn state_changes(&self) -> &StateChanges {
+        &self.state_changes
+    }
+
     /// The current transaction.
     pub fn transaction(&self) -> &Tx {
         &self.tx
@@ -287,6 +313,29 @@ impl<S, Tx> Interpreter<S, Tx> {
     pub const fn profiler(&self) -> &Profiler {
         &self.profiler
     }
+
+    /// Reference to the installed execution hook, if it is of type `H`.
+    pub fn execution_hook<H>(&self) -> Option<&H>
+    where
+        H: ExecutionHook,
+    {
+        self.execution_hook.get()
+    }
+
+    /// Mutable reference to the installed execution hook, if it is of type `H`.
+    pub fn execution_hook_mut<H>(&mut self) -> Option<&mut H>
+    where
+        H: ExecutionHook,
+    {
+        self.execution_hook.get_mut()
+    }
+
+    /// Remove the installed execution hook, returning it.
+    pub fn remove_execution_hook(
+        &mut self,
+    ) -> Option<alloc::boxed::Box<dyn ExecutionHook + Send + Sync>> {
+        self.execution_hook.take()
+    }
 }
 
 pub(crate) fn flags(flag: Reg<FLAG>) -> Flags {
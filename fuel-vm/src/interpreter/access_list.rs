@@ -0,0 +1,57 @@
+use super::Interpreter;
+use crate::{
+    error::InterpreterError,
+    storage::{
+        AccessList,
+        AccessRecorder,
+        InterpreterStorage,
+    },
+};
+
+impl<S, Tx> Interpreter<S, Tx>
+where
+    S: InterpreterStorage,
+{
+    /// Add an [`AccessRecorder`] wrapper around the storage to record the contracts,
+    /// storage slots and balances accessed by the VM.
+    ///
+    /// The accesses of the successive transactions are accumulated until they are
+    /// taken with [`Interpreter::take_access_list`].
+    pub fn add_access_recording(self) -> Interpreter<AccessRecorder<S>, Tx> {
+        let (interpreter, storage) = self.replace_storage(());
+        interpreter.replace_storage(AccessRecorder::new(storage)).0
+    }
+
+    /// Load the entries of `access_list` from the storage ahead of the execution of
+    /// a transaction, with [`InterpreterStorage::prefetch`].
+    pub fn prefetch(
+        &mut self,
+        access_list: &AccessList,
+    ) -> Result<(), InterpreterError<S::DataError>> {
+        self.storage
+            .prefetch(access_list)
+            .map_err(InterpreterError::Storage)
+    }
+}
+
+impl<S, Tx> Interpreter<AccessRecorder<S>, Tx>
+where
+    S: InterpreterStorage,
+{
+    /// The storage entries accessed since the recording started or was last taken.
+    pub fn access_list(&self) -> AccessList {
+        self.storage.access_list()
+    }
+
+    /// Return the storage entries accessed since the recording started or was last
+    /// taken, and start a new recording.
+    pub fn take_access_list(&mut self) -> AccessList {
+        self.storage.take_access_list()
+    }
+
+    /// Remove the [`AccessRecorder`] wrapper from the storage.
+    pub fn remove_access_recording(self) -> Interpreter<S, Tx> {
+        let (interpreter, storage) = self.replace_storage(());
+        interpreter.replace_storage(storage.into_inner()).0
+    }
+}
@@ -0,0 +1,336 @@
+use super::{
+    balances::RuntimeBalances,
+    CheckedMetadata,
+    ExecutableTransaction,
+    Interpreter,
+    Memory,
+    PanicContext,
+};
+use crate::{
+    call::CallFrame,
+    checked_transaction::{
+        Checked,
+        IntoChecked,
+    },
+    context::Context,
+    error::InterpreterError,
+    state::{
+        ExecuteState,
+        ProgramState,
+        StateChanges,
+        StateTransitionRef,
+    },
+    storage::{
+        AccessList,
+        AsyncInterpreterStorage,
+        AsyncStorage,
+        AsyncStorageError,
+        InterpreterStorage,
+        StorageCheckpoint,
+    },
+};
+
+use alloc::vec::Vec;
+
+use fuel_asm::{
+    Instruction,
+    Opcode,
+    PanicReason,
+    RawInstruction,
+};
+use fuel_tx::{
+    field::StorageSlots,
+    Input,
+};
+use fuel_types::Word;
+
+use crate::consts::VM_REGISTER_COUNT;
+
+/// State of the VM before an instruction reading the storage, restored if the
+/// instruction reads an entry that wasn't fetched.
+struct Suspended<Tx> {
+    registers: [Word; VM_REGISTER_COUNT],
+    memory: Memory,
+    frames: Vec<CallFrame>,
+    receipts: usize,
+    tx: Tx,
+    balances: RuntimeBalances,
+    context: Context,
+    panic_context: PanicContext,
+    /// The state changes recorded before the instruction. The instruction records
+    /// its own changes from scratch.
+    state_changes: StateChanges,
+    checkpoint: StorageCheckpoint,
+}
+
+impl<S, Tx> Interpreter<AsyncStorage<S>, Tx>
+where
+    S: AsyncInterpreterStorage,
+    Tx: ExecutableTransaction,
+    <Tx as IntoChecked>::Metadata: CheckedMetadata,
+{
+    /// Execute the transaction against an async storage backend.
+    ///
+    /// The storage-touching instructions (`SRW`, `SRWQ`, `SWW`, `SWWQ`, `SCWQ`,
+    /// `CALL`, `LDC`, `CCP`, `CSIZ`, `CROO`, `BAL`, `TR`, `TRO`, `SMO`, `MINT`,
+    /// `BURN`, `TIME` and `BHSH`) suspend the execution when they read an entry
+    /// that wasn't fetched yet. The changes of the interrupted instruction are
+    /// undone, the missing entries are fetched, yielding to the async runtime, and
+    /// the execution resumes at the same instruction. The instructions before it
+    /// aren't executed again.
+    ///
+    /// Fetching the entries recorded by an [`crate::storage::AccessRecorder`] with
+    /// [`AsyncStorage::fetch`], and the blocks read by `TIME` and `BHSH` with
+    /// [`AsyncStorage::fetch_block`], beforehand executes the transaction without
+    /// suspending it.
+    ///
+    /// The execution hook only observes the complete executions of the
+    /// instructions: a storage-touching instruction is executed without the hook
+    /// until its entries are fetched, then undone and executed again with the hook.
+    pub async fn transact_async(
+        &mut self,
+        tx: Checked<Tx>,
+    ) -> Result<
+        StateTransitionRef<'_, Tx>,
+        InterpreterError<AsyncStorageError<S::DataError>>,
+    > {
+        // The input contracts and the deployed contract are checked before the
+        // execution, without suspending it.
+        let mut entries = AccessList::default();
+        for input in tx.transaction().inputs() {
+            if let Input::Contract(contract) = input {
+                entries.insert_contract(&contract.contract_id);
+            }
+        }
+        if let Some(create) = tx.transaction().as_create() {
+            if let Some(metadata) = create.metadata() {
+                entries.insert_contract(&metadata.contract_id);
+                for slot in create.storage_slots() {
+                    entries.insert_storage_slot(&metadata.contract_id, slot.key());
+                }
+            }
+        }
+        self.storage
+            .fetch(&entries)
+            .await
+            .map_err(|e| InterpreterError::Storage(AsyncStorageError::Storage(e)))?;
+
+        let state_result = match self.init_script(tx) {
+            Ok(()) => self.run_async().await,
+            Err(e) => Err(e),
+        };
+
+        #[cfg(feature = "profile-any")]
+        {
+            let r = match &state_result {
+                Ok(state) => Ok(state),
+                Err(err) => Err(err.erase_generics()),
+            };
+            self.profiler.on_transaction(r);
+        }
+
+        let state = state_result?;
+        Ok(StateTransitionRef::new(
+            state,
+            self.transaction(),
+            self.receipts(),
+            self.state_changes(),
+        ))
+    }
+
+    /// Async variant of [`Self::run`], suspending the script to fetch the entries it
+    /// reads.
+    async fn run_async(
+        &mut self,
+    ) -> Result<ProgramState, InterpreterError<AsyncStorageError<S::DataError>>> {
+        if self.transaction().as_create().is_some() {
+            // The entries of the deployed contract are fetched.
+            return self.run()
+        }
+
+        self.prepare_script()?;
+
+        let program = if self.has_script_code() {
+            self.run_program_async().await
+        } else {
+            self.run_empty_script()
+        };
+
+        self.finalize_script(program)
+    }
+
+    /// Async variant of [`Self::run_program`].
+    async fn run_program_async(
+        &mut self,
+    ) -> Result<ProgramState, InterpreterError<AsyncStorageError<S::DataError>>> {
+        loop {
+            // Check whether the instruction will be executed in a call context
+            let in_call = !self.frames.is_empty();
+
+            let state = self.execute_async().await?;
+
+            if in_call {
+                // Only reverts and debug events should interrupt execution from a call
+                // context
+                match state {
+                    ExecuteState::Revert(r) => return Ok(ProgramState::Revert(r)),
+                    ExecuteState::DebugEvent(d) => return Ok(ProgramState::RunProgram(d)),
+                    _ => (),
+                }
+            } else {
+                match state {
+                    ExecuteState::Return(r) => return Ok(ProgramState::Return(r)),
+
+                    ExecuteState::ReturnData(d) => return Ok(ProgramState::ReturnData(d)),
+
+                    ExecuteState::Revert(r) => return Ok(ProgramState::Revert(r)),
+
+                    ExecuteState::Proceed => (),
+
+                    ExecuteState::DebugEvent(d) => return Ok(ProgramState::RunProgram(d)),
+                }
+            }
+        }
+    }
+
+    /// Async variant of [`Self::execute`], fetching the entries read by the
+    /// instruction before completing it.
+    async fn execute_async(
+        &mut self,
+    ) -> Result<ExecuteState, InterpreterError<AsyncStorageError<S::DataError>>> {
+        let Some(raw) = self.fetch_instruction() else {
+            return Err(InterpreterError::Panic(PanicReason::MemoryOverflow))
+        };
+
+        if self.debugger.is_active() {
+            let debug = self.eval_debugger_state();
+            if !debug.should_continue() {
+                return Ok(debug.into())
+            }
+        }
+
+        if !reads_storage(raw) {
+            return self.run_instruction(raw)
+        }
+
+        let Some(hook) = self.execution_hook.take() else {
+            return self.fetch_and_run(raw, true).await?
+        };
+        let fetched = self.fetch_and_run(raw, false).await;
+        self.execution_hook.set(hook);
+        // The result is the one of the execution with the hook.
+        let _ = fetched?;
+        self.run_instruction(raw)
+    }
+
+    /// Execute the instruction, suspending it to fetch the entries it reads until it
+    /// completes. The changes of the complete execution are kept if `keep` is set,
+    /// and undone otherwise.
+    ///
+    /// The outer error is a failure to fetch the entries.
+    async fn fetch_and_run(
+        &mut self,
+        raw: RawInstruction,
+        keep: bool,
+    ) -> Result<
+        Result<ExecuteState, InterpreterError<AsyncStorageError<S::DataError>>>,
+        InterpreterError<AsyncStorageError<S::DataError>>,
+    > {
+        loop {
+            let suspended = self.suspend()?;
+            let result = self.run_instruction(raw);
+            let fetched = !self.storage.has_missing_entries();
+            self.resume_from(suspended, fetched && keep)?;
+            if fetched {
+                return Ok(result)
+            }
+
+            self.storage
+                .fetch_missing()
+                .await
+                .map_err(|e| InterpreterError::Storage(AsyncStorageError::Storage(e)))?;
+        }
+    }
+
+    /// Save the state of the VM before an instruction, and take a storage
+    /// checkpoint.
+    fn suspend(
+        &mut self,
+    ) -> Result<Suspended<Tx>, InterpreterError<AsyncStorageError<S::DataError>>> {
+        let checkpoint = self
+            .storage
+            .checkpoint()
+            .map_err(InterpreterError::Storage)?;
+
+        Ok(Suspended {
+            registers: self.registers,
+            memory: self.memory.clone(),
+            frames: self.frames.clone(),
+            receipts: self.receipts.len(),
+            tx: self.tx.clone(),
+            balances: self.balances.clone(),
+            context: self.context.clone(),
+            panic_context: self.panic_context.clone(),
+            state_changes: core::mem::take(&mut self.state_changes),
+            checkpoint,
+        })
+    }
+
+    /// Keep the changes made since `suspended` if `keep` is set, or restore the VM
+    /// and the storage to it otherwise.
+    fn resume_from(
+        &mut self,
+        suspended: Suspended<Tx>,
+        keep: bool,
+    ) -> Result<(), InterpreterError<AsyncStorageError<S::DataError>>> {
+        let changes =
+            core::mem::replace(&mut self.state_changes, suspended.state_changes);
+
+        if keep {
+            self.state_changes.extend(changes);
+            return self
+                .storage
+                .release(suspended.checkpoint)
+                .map_err(InterpreterError::Storage)
+        }
+
+        self.registers = suspended.registers;
+        self.memory = suspended.memory;
+        self.frames = suspended.frames;
+        self.receipts.truncate(suspended.receipts);
+        self.tx = suspended.tx;
+        self.balances = suspended.balances;
+        self.context = suspended.context;
+        self.panic_context = suspended.panic_context;
+        self.storage
+            .revert_to(suspended.checkpoint)
+            .map_err(InterpreterError::Storage)
+    }
+}
+
+/// Whether the instruction can read an entry of the storage that wasn't fetched.
+fn reads_storage(raw: RawInstruction) -> bool {
+    Instruction::try_from(raw).is_ok_and(|instruction| {
+        matches!(
+            instruction.opcode(),
+            Opcode::SRW
+                | Opcode::SRWQ
+                | Opcode::SWW
+                | Opcode::SWWQ
+                | Opcode::SCWQ
+                | Opcode::CALL
+                | Opcode::LDC
+                | Opcode::CCP
+                | Opcode::CSIZ
+                | Opcode::CROO
+                | Opcode::BAL
+                | Opcode::TR
+                | Opcode::TRO
+                | Opcode::SMO
+                | Opcode::MINT
+                | Opcode::BURN
+                | Opcode::TIME
+                | Opcode::BHSH
+        )
+    })
+}
@@ -20,7 +20,10 @@ use alloc::collections::BTreeMap;
 use core::ops::Index;
 use hashbrown::HashMap;
 
-use super::MemoryRange;
+use super::{
+    Memory,
+    MemoryRange,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(crate) struct Balance {
@@ -110,7 +113,7 @@ impl RuntimeBalances {
 
     fn set_memory_balance_inner(
         balance: &Balance,
-        memory: &mut [u8; MEM_SIZE],
+        memory: &mut Memory,
     ) -> SimpleResult<Word> {
         let value = balance.value();
         let offset = balance.offset();
@@ -118,7 +121,7 @@ impl RuntimeBalances {
         let offset = offset + AssetId::LEN;
         let range = MemoryRange::new_const::<_, WORD_SIZE>(offset)?;
 
-        range.write(memory).copy_from_slice(&value.to_be_bytes());
+        range.write(memory, &value.to_be_bytes());
 
         Ok(value)
     }
@@ -133,7 +136,7 @@ impl RuntimeBalances {
     /// ordered, as in the protocol.
     pub fn checked_balance_add(
         &mut self,
-        memory: &mut [u8; MEM_SIZE],
+        memory: &mut Memory,
         asset: &AssetId,
         value: Word,
     ) -> Option<Word> {
@@ -148,7 +151,7 @@ impl RuntimeBalances {
     /// appropriate offset
     pub fn checked_balance_sub(
         &mut self,
-        memory: &mut [u8; MEM_SIZE],
+        memory: &mut Memory,
         asset: &AssetId,
         value: Word,
     ) -> Option<Word> {
@@ -175,9 +178,8 @@ impl RuntimeBalances {
             let value = balance.value();
             let ofs = balance.offset();
 
-            vm.memory[ofs..ofs + AssetId::LEN].copy_from_slice(asset.as_ref());
-            vm.memory[ofs + AssetId::LEN..ofs + AssetId::LEN + WORD_SIZE]
-                .copy_from_slice(&value.to_be_bytes());
+            vm.memory.write(ofs, asset.as_ref());
+            vm.memory.write(ofs + AssetId::LEN, &value.to_be_bytes());
         });
 
         vm.balances = self;
@@ -248,10 +250,10 @@ fn writes_to_memory_correctly() {
     assets_sorted
         .iter()
         .fold(VM_MEMORY_BALANCES_OFFSET, |ofs, (asset, value)| {
-            assert_eq!(asset.as_ref(), &memory[ofs..ofs + AssetId::LEN]);
+            assert_eq!(asset.as_ref(), &*memory.read(ofs..ofs + AssetId::LEN));
             assert_eq!(
                 &value.to_be_bytes(),
-                &memory[ofs + AssetId::LEN..ofs + AssetId::LEN + WORD_SIZE]
+                &*memory.read(ofs + AssetId::LEN..ofs + AssetId::LEN + WORD_SIZE)
             );
 
             ofs + AssetId::LEN + WORD_SIZE
@@ -28,6 +28,7 @@ use super::{
     },
     ExecutableTransaction,
     Interpreter,
+    Memory,
     MemoryRange,
     RuntimeBalances,
 };
@@ -52,6 +53,7 @@ use crate::{
     },
     prelude::Profiler,
     storage::{
+        ChangesRecorder,
         ContractsAssetsStorage,
         ContractsRawCode,
         InterpreterStorage,
@@ -136,7 +138,10 @@ where
         let (SystemRegisters { fp, pc, is, .. }, _) =
             split_registers(&mut self.registers);
         BurnCtx {
-            storage: &mut self.storage,
+            storage: &mut ChangesRecorder {
+                storage: &mut self.storage,
+                changes: &mut self.state_changes,
+            },
             context: &self.context,
             append: AppendReceipt {
                 receipts: &mut self.receipts,
@@ -148,7 +153,9 @@ where
             pc,
             is: is.as_ref(),
         }
-        .burn(a, b)
+        .burn(a, b)?;
+        self.record_burn();
+        Ok(())
     }
 
     pub(crate) fn mint(&mut self, a: Word, b: Word) -> IoResult<(), S::DataError> {
@@ -156,7 +163,10 @@ where
         let (SystemRegisters { fp, pc, is, .. }, _) =
             split_registers(&mut self.registers);
         MintCtx {
-            storage: &mut self.storage,
+            storage: &mut ChangesRecorder {
+                storage: &mut self.storage,
+                changes: &mut self.state_changes,
+            },
             context: &self.context,
             append: AppendReceipt {
                 receipts: &mut self.receipts,
@@ -168,7 +178,37 @@ where
             pc,
             is: is.as_ref(),
         }
-        .mint(a, b)
+        .mint(a, b)?;
+        self.record_mint();
+        Ok(())
+    }
+
+    /// Record the amount burned by the last `BURN` in the state changes.
+    fn record_burn(&mut self) {
+        if let Some(Receipt::Burn {
+            sub_id,
+            contract_id,
+            val,
+            ..
+        }) = self.receipts.as_ref().last()
+        {
+            self.state_changes
+                .record_burn(&contract_id.asset_id(sub_id), *val);
+        }
+    }
+
+    /// Record the amount minted by the last `MINT` in the state changes.
+    fn record_mint(&mut self) {
+        if let Some(Receipt::Mint {
+            sub_id,
+            contract_id,
+            val,
+            ..
+        }) = self.receipts.as_ref().last()
+        {
+            self.state_changes
+                .record_mint(&contract_id.asset_id(sub_id), *val);
+        }
     }
 
     pub(crate) fn code_copy(
@@ -243,8 +283,7 @@ where
     ) -> IoResult<(), S::DataError> {
         let gas_cost = self.gas_costs().csiz;
         let current_contract =
-            current_contract(&self.context, self.registers.fp(), self.memory.as_ref())?
-                .copied();
+            current_contract(&self.context, self.registers.fp(), &self.memory)?;
         let (
             SystemRegisters {
                 cgas, ggas, pc, is, ..
@@ -276,16 +315,21 @@ where
         rb: RegisterId,
         c: Word,
     ) -> IoResult<(), S::DataError> {
-        let contract_id = self.internal_contract().copied();
+        let contract_id = self.internal_contract();
         let (SystemRegisters { pc, .. }, mut w) = split_registers(&mut self.registers);
         let result = &mut w[WriteRegKey::try_from(rb)?];
 
         let input = StateClearQWord::new(a, c)?;
         let Self {
             ref mut storage,
+            ref mut state_changes,
             ref memory,
             ..
         } = self;
+        let storage = &mut ChangesRecorder {
+            storage,
+            changes: state_changes,
+        };
 
         state_clear_qword(&contract_id?, storage, memory, pc, result, input)
     }
@@ -331,7 +375,7 @@ where
         d: Word,
     ) -> IoResult<(), S::DataError> {
         let owner = self.ownership_registers();
-        let contract_id = self.internal_contract().copied();
+        let contract_id = self.internal_contract();
         let (SystemRegisters { pc, .. }, mut w) = split_registers(&mut self.registers);
         let result = &mut w[WriteRegKey::try_from(rb)?];
 
@@ -356,13 +400,17 @@ where
         let exists = &mut w[WriteRegKey::try_from(rb)?];
         let Self {
             ref mut storage,
+            ref mut state_changes,
             ref memory,
             ref context,
             ..
         } = self;
         state_write_word(
             StateWordCtx {
-                storage,
+                storage: &mut ChangesRecorder {
+                    storage,
+                    changes: state_changes,
+                },
                 memory,
                 context,
                 fp: fp.as_ref(),
@@ -381,18 +429,23 @@ where
         c: Word,
         d: Word,
     ) -> IoResult<(), S::DataError> {
-        let contract_id = self.internal_contract().copied();
+        let contract_id = self.internal_contract();
         let (SystemRegisters { pc, .. }, mut w) = split_registers(&mut self.registers);
         let result = &mut w[WriteRegKey::try_from(rb)?];
 
         let input = StateWriteQWord::new(a, c, d)?;
         let Self {
             ref mut storage,
+            ref mut state_changes,
             ref mut memory,
             ..
         } = self;
+        let storage = &mut ChangesRecorder {
+            storage,
+            changes: state_changes,
+        };
 
-        state_write_qword(&contract_id?, storage, memory.as_mut(), pc, result, input)
+        state_write_qword(&contract_id?, storage, memory, pc, result, input)
     }
 
     pub(crate) fn timestamp(
@@ -425,7 +478,10 @@ where
             receipts: &mut self.receipts,
             tx: &mut self.tx,
             balances: &mut self.balances,
-            storage: &mut self.storage,
+            storage: &mut ChangesRecorder {
+                storage: &mut self.storage,
+                changes: &mut self.state_changes,
+            },
             current_contract: self.frames.last().map(|frame| frame.to()).copied(),
             fp: fp.as_ref(),
             pc,
@@ -440,7 +496,7 @@ where
 
 struct LoadContractCodeCtx<'vm, S, I> {
     contract_max_size: u64,
-    memory: &'vm mut [u8; MEM_SIZE],
+    memory: &'vm mut Memory,
     input_contracts: InputContracts<'vm, I>,
     storage: &'vm S,
     ssp: RegMut<'vm, SSP>,
@@ -525,18 +581,14 @@ where
                 WORD_SIZE,
             )?;
 
-            let old_code_size = Word::from_be_bytes(
-                self.memory[fp_code_size.usizes()]
-                    .try_into()
-                    .expect("`fp_code_size_end` is `WORD_SIZE`"),
-            );
+            let old_code_size =
+                Word::from_be_bytes(self.memory.read_bytes(fp_code_size.start));
 
             let new_code_size = old_code_size
                 .checked_add(length as Word)
                 .ok_or(PanicReason::MemoryOverflow)?;
 
-            self.memory[fp_code_size.usizes()]
-                .copy_from_slice(&new_code_size.to_be_bytes());
+            fp_code_size.write(self.memory, &new_code_size.to_be_bytes());
         }
 
         Ok(inc_pc(self.pc)?)
@@ -561,9 +613,9 @@ where
         let sub_id_range = CheckedMemConstLen::<{ Bytes32::LEN }>::new(b)?;
         let memory = &*self.append.memory;
 
-        let sub_id = Bytes32::from_bytes_ref(sub_id_range.read(memory));
+        let sub_id = &Bytes32::from(sub_id_range.read(memory));
 
-        let contract_id = ContractId::from_bytes_ref(range.read(memory));
+        let contract_id = &ContractId::from(range.read(memory));
         let asset_id = contract_id.asset_id(sub_id);
 
         let balance = balance(self.storage, contract_id, &asset_id)?;
@@ -601,9 +653,9 @@ where
         let sub_id_range = CheckedMemConstLen::<{ Bytes32::LEN }>::new(b)?;
         let memory = &*self.append.memory;
 
-        let sub_id = Bytes32::from_bytes_ref(sub_id_range.read(memory));
+        let sub_id = &Bytes32::from(sub_id_range.read(memory));
 
-        let contract_id = ContractId::from_bytes_ref(range.read(memory));
+        let contract_id = &ContractId::from(range.read(memory));
         let asset_id = contract_id.asset_id(sub_id);
 
         let balance = balance(self.storage, contract_id, &asset_id)?;
@@ -622,7 +674,7 @@ where
 }
 
 struct CodeCopyCtx<'vm, S, I> {
-    memory: &'vm mut [u8; MEM_SIZE],
+    memory: &'vm mut Memory,
     input_contracts: InputContracts<'vm, I>,
     storage: &'vm S,
     owner: OwnershipRegisters,
@@ -676,7 +728,7 @@ where
 
 pub(crate) fn block_hash<S: InterpreterStorage>(
     storage: &S,
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     owner: OwnershipRegisters,
     pc: RegMut<PC>,
     a: Word,
@@ -710,7 +762,7 @@ pub(crate) fn block_height(
 
 pub(crate) fn coinbase<S: InterpreterStorage>(
     storage: &S,
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     owner: OwnershipRegisters,
     pc: RegMut<PC>,
     a: Word,
@@ -722,7 +774,7 @@ pub(crate) fn coinbase<S: InterpreterStorage>(
 }
 
 struct CodeRootCtx<'vm, S, I> {
-    memory: &'vm mut [u8; MEM_SIZE],
+    memory: &'vm mut Memory,
     input_contracts: InputContracts<'vm, I>,
     storage: &'vm S,
     owner: OwnershipRegisters,
@@ -737,7 +789,7 @@ impl<'vm, S, I: Iterator<Item = &'vm ContractId>> CodeRootCtx<'vm, S, I> {
         MemoryRange::new(a, Bytes32::LEN)?;
         let contract_id = CheckedMemConstLen::<{ ContractId::LEN }>::new(b)?;
 
-        let contract_id = ContractId::from_bytes_ref(contract_id.read(self.memory));
+        let contract_id = &ContractId::from(contract_id.read(self.memory));
 
         self.input_contracts.check(contract_id)?;
 
@@ -757,7 +809,7 @@ impl<'vm, S, I: Iterator<Item = &'vm ContractId>> CodeRootCtx<'vm, S, I> {
 
 struct CodeSizeCtx<'vm, S, I> {
     storage: &'vm S,
-    memory: &'vm mut [u8; MEM_SIZE],
+    memory: &'vm mut Memory,
     gas_cost: DependentCost,
     profiler: &'vm mut Profiler,
     input_contracts: InputContracts<'vm, I>,
@@ -779,7 +831,7 @@ impl<'vm, S, I: Iterator<Item = &'vm ContractId>> CodeSizeCtx<'vm, S, I> {
     {
         let contract_id = CheckedMemConstLen::<{ ContractId::LEN }>::new(b)?;
 
-        let contract_id = ContractId::from_bytes_ref(contract_id.read(self.memory));
+        let contract_id = &ContractId::from(contract_id.read(self.memory));
 
         self.input_contracts.check(contract_id)?;
 
@@ -799,7 +851,7 @@ impl<'vm, S, I: Iterator<Item = &'vm ContractId>> CodeSizeCtx<'vm, S, I> {
 
 pub(crate) struct StateWordCtx<'vm, S> {
     pub storage: &'vm mut S,
-    pub memory: &'vm [u8; MEM_SIZE],
+    pub memory: &'vm Memory,
     pub context: &'vm Context,
     pub fp: Reg<'vm, FP>,
     pub pc: RegMut<'vm, PC>,
@@ -821,10 +873,10 @@ pub(crate) fn state_read_word<S: InterpreterStorage>(
 
     let contract = internal_contract(context, fp, memory)?;
 
-    let key = Bytes32::from_bytes_ref(key.read(memory));
+    let key = &Bytes32::from(key.read(memory));
 
     let value = storage
-        .merkle_contract_state(contract, key)
+        .merkle_contract_state(&contract, key)
         .map_err(RuntimeError::Storage)?
         .map(|bytes| {
             Word::from_be_bytes(
@@ -857,8 +909,8 @@ pub(crate) fn state_write_word<S: InterpreterStorage>(
     let contract = internal_contract_bounds(context, fp)?;
 
     // Safety: Memory bounds logically verified by the interpreter
-    let contract = ContractId::from_bytes_ref(contract.read(memory));
-    let key = Bytes32::from_bytes_ref(key.read(memory));
+    let contract = &ContractId::from(contract.read(memory));
+    let key = &Bytes32::from(key.read(memory));
 
     let mut value = Bytes32::default();
 
@@ -897,7 +949,7 @@ where
 {
     base_asset_id: AssetId,
     max_message_data_length: u64,
-    memory: &'vm mut [u8; MEM_SIZE],
+    memory: &'vm mut Memory,
     tx_offset: usize,
     receipts: &'vm mut ReceiptsCtx,
     tx: &'vm mut Tx,
@@ -957,10 +1009,10 @@ where
         let sender = CheckedMemConstLen::<{ Address::LEN }>::new(*self.fp)?;
         let txid = tx_id(self.memory);
         let msg_data = msg_data_range.read(self.memory).to_vec();
-        let sender = Address::from_bytes_ref(sender.read(self.memory));
+        let sender = &Address::from(sender.read(self.memory));
 
         let receipt = Receipt::message_out(
-            txid,
+            &txid,
             self.receipts.len() as Word,
             *sender,
             recipient,
@@ -1017,12 +1069,12 @@ impl StateReadQWord {
 fn state_read_qword<S: InterpreterStorage>(
     contract_id: &ContractId,
     storage: &S,
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     pc: RegMut<PC>,
     result_register: &mut Word,
     input: StateReadQWord,
 ) -> IoResult<(), S::DataError> {
-    let origin_key = Bytes32::from_bytes_ref(input.origin_key_memory_range.read(memory));
+    let origin_key = &Bytes32::from(input.origin_key_memory_range.read(memory));
 
     let mut all_set = true;
     let result: Vec<u8> = storage
@@ -1040,7 +1092,9 @@ fn state_read_qword<S: InterpreterStorage>(
 
     *result_register = all_set as Word;
 
-    memory[input.destination_address_memory_range.usizes()].copy_from_slice(&result);
+    input
+        .destination_address_memory_range
+        .write(memory, &result);
 
     inc_pc(pc)?;
 
@@ -1080,15 +1134,16 @@ impl StateWriteQWord {
 fn state_write_qword<S: InterpreterStorage>(
     contract_id: &ContractId,
     storage: &mut S,
-    memory: &[u8; MEM_SIZE],
+    memory: &Memory,
     pc: RegMut<PC>,
     result_register: &mut Word,
     input: StateWriteQWord,
 ) -> IoResult<(), S::DataError> {
     let destination_key =
-        Bytes32::from_bytes_ref(input.starting_storage_key_memory_range.read(memory));
+        &Bytes32::from(input.starting_storage_key_memory_range.read(memory));
 
-    let values: Vec<_> = memory[input.source_address_memory_range.usizes()]
+    let values: Vec<_> = memory
+        .read(input.source_address_memory_range.usizes())
         .chunks_exact(Bytes32::LEN)
         .flat_map(|chunk| Some(Bytes32::from(<[u8; 32]>::try_from(chunk).ok()?)))
         .collect();
@@ -1130,13 +1185,12 @@ impl StateClearQWord {
 fn state_clear_qword<S: InterpreterStorage>(
     contract_id: &ContractId,
     storage: &mut S,
-    memory: &[u8; MEM_SIZE],
+    memory: &Memory,
     pc: RegMut<PC>,
     result_register: &mut Word,
     input: StateClearQWord,
 ) -> IoResult<(), S::DataError> {
-    let start_key =
-        Bytes32::from_bytes_ref(input.start_storage_key_memory_range.read(memory));
+    let start_key = &Bytes32::from(input.start_storage_key_memory_range.read(memory));
 
     let all_previously_set = storage
         .merkle_contract_state_remove_range(contract_id, start_key, input.num_slots)
@@ -15,7 +15,7 @@ use fuel_tx::Contract;
 #[test]
 fn test_load_contract() -> IoResult<(), Infallible> {
     let mut storage = MemoryStorage::new(Default::default(), Default::default());
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let mut pc = 4;
     let hp = 2000;
     let mut ssp = 1000;
@@ -28,9 +28,7 @@ fn test_load_contract() -> IoResult<(), Infallible> {
     let offset = 20;
     let num_bytes = 40;
 
-    memory[contract_id_mem_address as usize
-        ..contract_id_mem_address as usize + ContractId::LEN]
-        .copy_from_slice(contract_id.as_ref());
+    memory.write(contract_id_mem_address as usize, contract_id.as_ref());
     storage
         .storage_contract_insert(&contract_id, &Contract::from(vec![5u8; 400]))
         .unwrap();
@@ -57,7 +55,7 @@ fn test_load_contract() -> IoResult<(), Infallible> {
 #[test]
 fn test_code_copy() -> IoResult<(), Infallible> {
     let mut storage = MemoryStorage::new(Default::default(), Default::default());
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let mut pc = 4;
 
     let contract_id = ContractId::from([4u8; 32]);
@@ -67,9 +65,7 @@ fn test_code_copy() -> IoResult<(), Infallible> {
     let offset = 20;
     let num_bytes = 40;
 
-    memory[contract_id_mem_address as usize
-        ..contract_id_mem_address as usize + ContractId::LEN]
-        .copy_from_slice(contract_id.as_ref());
+    memory.write(contract_id_mem_address as usize, contract_id.as_ref());
     storage
         .storage_contract_insert(&contract_id, &Contract::from(vec![5u8; 400]))
         .unwrap();
@@ -32,11 +32,10 @@ fn test_burn(
     sub_id: [u8; 32],
 ) -> IoResult<(), Infallible> {
     let mut storage = MemoryStorage::new(Default::default(), Default::default());
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let contract_id = ContractId::from([3u8; 32]);
-    memory[0..ContractId::LEN].copy_from_slice(contract_id.as_slice());
-    memory[ContractId::LEN..ContractId::LEN + Bytes32::LEN]
-        .copy_from_slice(sub_id.as_slice());
+    memory.write(0, contract_id.as_slice());
+    memory.write(ContractId::LEN, sub_id.as_slice());
     let sub_id = Bytes32::from(sub_id);
     let asset_id = contract_id.asset_id(&sub_id);
     let initialize = initialize.into();
@@ -114,11 +113,10 @@ fn test_mint(
     sub_id: [u8; 32],
 ) -> IoResult<(), Infallible> {
     let mut storage = MemoryStorage::new(Default::default(), Default::default());
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let contract_id = ContractId::from([3u8; 32]);
-    memory[0..ContractId::LEN].copy_from_slice(contract_id.as_slice());
-    memory[ContractId::LEN..ContractId::LEN + Bytes32::LEN]
-        .copy_from_slice(sub_id.as_slice());
+    memory.write(0, contract_id.as_slice());
+    memory.write(ContractId::LEN, sub_id.as_slice());
     let sub_id = Bytes32::from(sub_id);
     let asset_id = contract_id.asset_id(&sub_id);
     let initialize = initialize.into();
@@ -180,7 +178,7 @@ fn test_mint(
 #[test]
 fn test_block_hash() {
     let storage = MemoryStorage::new(Default::default(), Default::default());
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let owner = OwnershipRegisters {
         sp: 1000,
         ssp: 1,
@@ -193,7 +191,7 @@ fn test_block_hash() {
     let mut pc = 4;
     block_hash(&storage, &mut memory, owner, RegMut::new(&mut pc), 20, 40).unwrap();
     assert_eq!(pc, 8);
-    assert_ne!(memory[20..20 + 32], [1u8; 32]);
+    assert_ne!(memory.read(20..20 + 32)[..], [1u8; 32]);
 }
 
 #[test]
@@ -211,7 +209,7 @@ fn test_block_height() {
 #[test]
 fn test_coinbase() {
     let storage = MemoryStorage::new(Default::default(), Default::default());
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let owner = OwnershipRegisters {
         sp: 1000,
         ssp: 1,
@@ -224,15 +222,15 @@ fn test_coinbase() {
     let mut pc = 4;
     coinbase(&storage, &mut memory, owner, RegMut::new(&mut pc), 20).unwrap();
     assert_eq!(pc, 8);
-    assert_eq!(memory[20..20 + 32], [0u8; 32]);
+    assert_eq!(memory.read(20..20 + 32)[..], [0u8; 32]);
 }
 
 #[test]
 fn test_code_root() {
     let contract_id = ContractId::new([3u8; ContractId::LEN]);
     let mut storage = MemoryStorage::new(Default::default(), Default::default());
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
-    memory[0..ContractId::LEN].copy_from_slice(contract_id.as_slice());
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
+    memory.write(0, contract_id.as_slice());
     let owner = OwnershipRegisters {
         sp: 1000,
         ssp: 1,
@@ -282,7 +280,7 @@ fn test_code_root() {
     .code_root(20, 0)
     .unwrap();
     assert_eq!(pc, 8);
-    assert_eq!(memory[20..20 + 32], [6u8; 32]);
+    assert_eq!(memory.read(20..20 + 32)[..], [6u8; 32]);
 
     let owner = OwnershipRegisters {
         sp: 1000,
@@ -308,8 +306,8 @@ fn test_code_root() {
 fn test_code_size() {
     let contract_id = ContractId::new([3u8; ContractId::LEN]);
     let mut storage = MemoryStorage::new(Default::default(), Default::default());
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
-    memory[0..ContractId::LEN].copy_from_slice(contract_id.as_slice());
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
+    memory.write(0, contract_id.as_slice());
     StorageAsMut::storage::<ContractsRawCode>(&mut storage)
         .write(&ContractId::from([3u8; 32]), vec![1u8; 100])
         .unwrap();
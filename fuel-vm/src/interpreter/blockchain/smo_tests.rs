@@ -212,9 +212,9 @@ fn test_smo(
     let mut rng = StdRng::seed_from_u64(100);
     let base_asset_id = rng.gen();
 
-    let mut memory: Memory<MEM_SIZE> = vec![0; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![0; MEM_SIZE].try_into().unwrap();
     for (offset, bytes) in mem {
-        memory[offset..offset + bytes.len()].copy_from_slice(bytes.as_slice());
+        memory.write(offset, bytes.as_slice());
     }
     let mut receipts = Default::default();
     let mut tx = Create::default();
@@ -17,7 +17,7 @@ use test_case::test_case;
 struct SWWQInput {
     input: StateWriteQWord,
     storage_slots: Vec<([u8; 32], [u8; 32])>,
-    memory: Memory<MEM_SIZE>,
+    memory: Memory,
 }
 
 #[test_case(
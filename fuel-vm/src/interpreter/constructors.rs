@@ -4,16 +4,24 @@
 use super::{
     ExecutableTransaction,
     Interpreter,
+    Memory,
     RuntimeBalances,
 };
 use crate::{
     consts::*,
     context::Context,
+    hook::{
+        ExecutionHook,
+        HookSlot,
+    },
     interpreter::{
         InterpreterParams,
         PanicContext,
     },
-    state::Debugger,
+    state::{
+        Debugger,
+        StateChanges,
+    },
     storage::MemoryStorage,
 };
 
@@ -36,23 +44,64 @@ where
     pub fn with_storage(storage: S, interpreter_params: InterpreterParams) -> Self {
         Self {
             registers: [0; VM_REGISTER_COUNT],
-            memory: vec![0; MEM_SIZE]
-                .try_into()
-                .expect("Failed to allocate memory"),
+            memory: Memory::new(),
             frames: vec![],
             receipts: Default::default(),
             tx: Default::default(),
             initial_balances: Default::default(),
             storage,
             debugger: Debugger::default(),
+            state_changes: StateChanges::default(),
+            storage_checkpoint: None,
             context: Context::default(),
             balances: RuntimeBalances::default(),
             profiler: Profiler::default(),
+            execution_hook: HookSlot::default(),
+            history: None,
             interpreter_params,
             panic_context: PanicContext::None,
         }
     }
 
+    /// Reset the interpreter to the state of a freshly constructed instance, keeping
+    /// the storage, the interpreter parameters, the profiler receiver, the
+    /// execution hook and the history recording, if enabled.
+    ///
+    /// The memory pages are released, while the page table, the call frames and the
+    /// receipts keep their allocated capacity.
+    pub fn reset_for_tx(&mut self) {
+        self.registers = [0; VM_REGISTER_COUNT];
+        self.memory.clear();
+        self.frames.clear();
+        self.receipts.clear();
+        self.tx = Default::default();
+        self.initial_balances = Default::default();
+        self.debugger = Debugger::default();
+        self.state_changes.clear();
+        self.storage_checkpoint = None;
+        if let Some(history) = &mut self.history {
+            history.clear();
+        }
+        self.context = Context::default();
+        self.balances = RuntimeBalances::default();
+        #[cfg(feature = "profile-any")]
+        {
+            *self.profiler.data_mut() = Default::default();
+        }
+        self.panic_context = PanicContext::None;
+    }
+
+    /// Sets an execution hook for the VM, called before and after each instruction.
+    ///
+    /// Replaces any previously installed hook.
+    pub fn with_execution_hook<H>(&mut self, hook: H) -> &mut Self
+    where
+        H: ExecutionHook + Send + Sync,
+    {
+        self.execution_hook.set(alloc::boxed::Box::new(hook));
+        self
+    }
+
     /// Sets a profiler for the VM
     #[cfg(feature = "profile-any")]
     pub fn with_profiler<P>(&mut self, receiver: P) -> &mut Self
@@ -64,6 +113,53 @@ where
     }
 }
 
+impl<S, Tx> Interpreter<S, Tx> {
+    /// Move the interpreter state to a new storage, returning the previous one.
+    pub(crate) fn replace_storage<T>(self, storage: T) -> (Interpreter<T, Tx>, S) {
+        let interpreter = Interpreter {
+            registers: self.registers,
+            memory: self.memory,
+            frames: self.frames,
+            receipts: self.receipts,
+            tx: self.tx,
+            initial_balances: self.initial_balances,
+            storage,
+            debugger: self.debugger,
+            state_changes: self.state_changes,
+            storage_checkpoint: self.storage_checkpoint,
+            context: self.context,
+            balances: self.balances,
+            profiler: self.profiler,
+            execution_hook: self.execution_hook,
+            history: None,
+            interpreter_params: self.interpreter_params,
+            panic_context: self.panic_context,
+        };
+
+        (interpreter, self.storage)
+    }
+
+    /// Remove the execution hook and the profiler receiver, as on a freshly built
+    /// interpreter.
+    pub(crate) fn detach_observers(&mut self) {
+        self.execution_hook = HookSlot::default();
+        self.profiler = Profiler::default();
+    }
+
+    /// Parameters the interpreter was created with.
+    pub(crate) fn interpreter_params(&self) -> &InterpreterParams {
+        &self.interpreter_params
+    }
+
+    /// Replace the interpreter parameters.
+    pub(crate) fn set_interpreter_params(
+        &mut self,
+        interpreter_params: InterpreterParams,
+    ) {
+        self.interpreter_params = interpreter_params;
+    }
+}
+
 impl<S, Tx> Interpreter<S, Tx>
 where
     S: Clone,
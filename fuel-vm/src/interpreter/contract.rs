@@ -12,6 +12,7 @@ use super::{
     memory::read_bytes,
     ExecutableTransaction,
     Interpreter,
+    Memory,
     RuntimeBalances,
 };
 use crate::{
@@ -19,7 +20,6 @@ use crate::{
         reg_key::*,
         CheckedMemConstLen,
     },
-    consts::*,
     context::Context,
     error::{
         IoResult,
@@ -31,6 +31,7 @@ use crate::{
         PanicContext,
     },
     storage::{
+        ChangesRecorder,
         ContractsAssetsStorage,
         ContractsRawCode,
         InterpreterStorage,
@@ -94,7 +95,10 @@ where
         let (SystemRegisters { fp, is, pc, .. }, _) =
             split_registers(&mut self.registers);
         let input = TransferCtx {
-            storage: &mut self.storage,
+            storage: &mut ChangesRecorder {
+                storage: &mut self.storage,
+                changes: &mut self.state_changes,
+            },
             memory: &mut self.memory,
             context: &self.context,
             balances: &mut self.balances,
@@ -119,7 +123,10 @@ where
         let (SystemRegisters { fp, is, pc, .. }, _) =
             split_registers(&mut self.registers);
         let input = TransferCtx {
-            storage: &mut self.storage,
+            storage: &mut ChangesRecorder {
+                storage: &mut self.storage,
+                changes: &mut self.state_changes,
+            },
             memory: &mut self.memory,
             context: &self.context,
             balances: &mut self.balances,
@@ -158,7 +165,7 @@ where
 
 struct ContractBalanceCtx<'vm, S, I> {
     storage: &'vm S,
-    memory: &'vm mut [u8; MEM_SIZE],
+    memory: &'vm mut Memory,
     pc: RegMut<'vm, PC>,
     input_contracts: InputContracts<'vm, I>,
 }
@@ -177,8 +184,8 @@ impl<'vm, S, I> ContractBalanceCtx<'vm, S, I> {
         let asset_id = CheckedMemConstLen::<{ AssetId::LEN }>::new(b)?;
         let contract = CheckedMemConstLen::<{ ContractId::LEN }>::new(c)?;
 
-        let asset_id = AssetId::from_bytes_ref(asset_id.read(self.memory));
-        let contract = ContractId::from_bytes_ref(contract.read(self.memory));
+        let asset_id = &AssetId::from(asset_id.read(self.memory));
+        let contract = &ContractId::from(contract.read(self.memory));
 
         self.input_contracts.check(contract)?;
 
@@ -191,7 +198,7 @@ impl<'vm, S, I> ContractBalanceCtx<'vm, S, I> {
 }
 struct TransferCtx<'vm, S, Tx> {
     storage: &'vm mut S,
-    memory: &'vm mut [u8; MEM_SIZE],
+    memory: &'vm mut Memory,
     context: &'vm Context,
     balances: &'vm mut RuntimeBalances,
     receipts: &'vm mut ReceiptsCtx,
@@ -234,7 +241,7 @@ impl<'vm, S, Tx> TransferCtx<'vm, S, Tx> {
         let internal_context = match internal_contract(self.context, self.fp, self.memory)
         {
             // optimistically attempt to load the internal contract id
-            Ok(source_contract) => Some(*source_contract),
+            Ok(source_contract) => Some(source_contract),
             // revert to external context if no internal contract is set
             Err(PanicReason::ExpectedInternalContext) => None,
             // bubble up any other kind of errors
@@ -302,7 +309,7 @@ impl<'vm, S, Tx> TransferCtx<'vm, S, Tx> {
         let internal_context = match internal_contract(self.context, self.fp, self.memory)
         {
             // optimistically attempt to load the internal contract id
-            Ok(source_contract) => Some(*source_contract),
+            Ok(source_contract) => Some(source_contract),
             // revert to external context if no internal contract is set
             Err(PanicReason::ExpectedInternalContext) => None,
             // bubble up any other kind of errors
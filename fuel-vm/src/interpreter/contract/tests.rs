@@ -3,6 +3,7 @@ use core::convert::Infallible;
 use alloc::vec;
 
 use crate::{
+    consts::*,
     interpreter::memory::Memory,
     storage::MemoryStorage,
 };
@@ -22,11 +23,9 @@ use test_case::test_case;
 
 #[test_case(0, 32 => Ok(()); "Can read contract balance")]
 fn test_contract_balance(b: Word, c: Word) -> IoResult<(), Infallible> {
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
-    memory[b as usize..(b as usize + AssetId::LEN)]
-        .copy_from_slice(&[2u8; AssetId::LEN][..]);
-    memory[c as usize..(c as usize + ContractId::LEN)]
-        .copy_from_slice(&[3u8; ContractId::LEN][..]);
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
+    memory.write(b as usize, &[2u8; AssetId::LEN][..]);
+    memory.write(c as usize, &[3u8; ContractId::LEN][..]);
     let contract_id = ContractId::from([3u8; 32]);
     let mut storage = MemoryStorage::new(Default::default(), Default::default());
     storage
@@ -88,14 +87,13 @@ fn test_transfer(
     let fp = 2048;
     let is = 0;
 
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
-    memory[real_contract_id_offset as usize
-        ..(real_contract_id_offset as usize + ContractId::LEN)]
-        .copy_from_slice(RECIPIENT_CONTRACT_ID.as_ref());
-    memory[real_asset_id_offset as usize..(real_asset_id_offset as usize + AssetId::LEN)]
-        .copy_from_slice(ASSET_ID.as_ref());
-    memory[fp as usize..(fp as usize + ContractId::LEN)]
-        .copy_from_slice(SOURCE_CONTRACT_ID.as_ref());
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
+    memory.write(
+        real_contract_id_offset as usize,
+        RECIPIENT_CONTRACT_ID.as_ref(),
+    );
+    memory.write(real_asset_id_offset as usize, ASSET_ID.as_ref());
+    memory.write(fp as usize, SOURCE_CONTRACT_ID.as_ref());
 
     let mut storage = MemoryStorage::new(Default::default(), Default::default());
 
@@ -223,15 +221,11 @@ fn test_transfer_output(
     let fp = 2048;
     let is = 0;
 
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
 
-    memory
-        [real_recipient_offset as usize..(real_recipient_offset as usize + Address::LEN)]
-        .copy_from_slice(RECIPIENT_ADDRESS.as_ref());
-    memory[real_asset_id_offset as usize..(real_asset_id_offset as usize + AssetId::LEN)]
-        .copy_from_slice(ASSET_ID.as_ref());
-    memory[fp as usize..(fp as usize + ContractId::LEN)]
-        .copy_from_slice(SOURCE_CONTRACT_ID.as_ref());
+    memory.write(real_recipient_offset as usize, RECIPIENT_ADDRESS.as_ref());
+    memory.write(real_asset_id_offset as usize, ASSET_ID.as_ref());
+    memory.write(fp as usize, SOURCE_CONTRACT_ID.as_ref());
 
     let mut storage = MemoryStorage::new(Default::default(), Default::default());
 
@@ -311,7 +305,7 @@ fn test_transfer_output(
 
     assert_eq!(pc, 8);
 
-    let output_bytes: &[u8] = &memory[output_range.start..output_range.end];
+    let output_bytes: &[u8] = &memory.read(output_range.start..output_range.end)[..];
     let output = Output::from_bytes(output_bytes).unwrap();
     let output_amount = output.amount().unwrap();
     assert_eq!(output_amount, transfer_amount);
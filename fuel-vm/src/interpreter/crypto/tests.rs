@@ -7,6 +7,7 @@ use rand::{
 };
 
 use crate::{
+    consts::*,
     context::Context,
     interpreter::memory::Memory,
 };
@@ -16,7 +17,7 @@ use super::*;
 #[cfg(feature = "random")]
 #[test]
 fn test_recover_secp256k1() -> SimpleResult<()> {
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let owner = OwnershipRegisters {
         sp: 1000,
         ssp: 1000,
@@ -38,8 +39,8 @@ fn test_recover_secp256k1() -> SimpleResult<()> {
     let message = Message::new([3u8; 100]);
     let signature = Signature::sign(&secret, &message);
 
-    memory[sig_address..sig_address + Signature::LEN].copy_from_slice(signature.as_ref());
-    memory[msg_address..msg_address + Message::LEN].copy_from_slice(message.as_ref());
+    memory.write(sig_address, signature.as_ref());
+    memory.write(msg_address, message.as_ref());
 
     secp256k1_recover(
         &mut memory,
@@ -53,7 +54,7 @@ fn test_recover_secp256k1() -> SimpleResult<()> {
     assert_eq!(pc, 8);
     assert_eq!(err, 0);
     assert_eq!(
-        &memory[recovered as usize..recovered as usize + PublicKey::LEN],
+        &memory.read(recovered as usize..recovered as usize + PublicKey::LEN)[..],
         public_key.as_ref()
     );
     Ok(())
@@ -66,7 +67,7 @@ fn test_recover_secp256r1() -> SimpleResult<()> {
 
     let mut rng = &mut StdRng::seed_from_u64(8586);
 
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let owner = OwnershipRegisters {
         sp: 1000,
         ssp: 1000,
@@ -90,8 +91,8 @@ fn test_recover_secp256r1() -> SimpleResult<()> {
     let signature = fuel_crypto::secp256r1::sign_prehashed(&signing_key, &message)
         .expect("Signing failed");
 
-    memory[sig_address..sig_address + Bytes64::LEN].copy_from_slice(&*signature);
-    memory[msg_address..msg_address + Message::LEN].copy_from_slice(message.as_ref());
+    memory.write(sig_address, &*signature);
+    memory.write(msg_address, message.as_ref());
 
     secp256r1_recover(
         &mut memory,
@@ -105,7 +106,7 @@ fn test_recover_secp256r1() -> SimpleResult<()> {
     assert_eq!(pc, 8);
     assert_eq!(err, 0);
     assert_eq!(
-        &memory[recovered as usize..recovered as usize + Bytes64::LEN],
+        &memory.read(recovered as usize..recovered as usize + Bytes64::LEN)[..],
         &encode_pubkey(*verifying_key)
     );
     Ok(())
@@ -115,7 +116,7 @@ fn test_recover_secp256r1() -> SimpleResult<()> {
 fn test_verify_ed25519() -> SimpleResult<()> {
     use ed25519_dalek::Signer;
 
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let mut err = 0;
     let mut pc = 4;
 
@@ -129,11 +130,9 @@ fn test_verify_ed25519() -> SimpleResult<()> {
     let message = Message::new([3u8; 100]);
     let signature = signing_key.sign(&*message);
 
-    memory[sig_address..sig_address + Signature::LEN]
-        .copy_from_slice(&signature.to_bytes());
-    memory[msg_address..msg_address + Message::LEN].copy_from_slice(message.as_ref());
-    memory[pubkey_address..pubkey_address + Bytes32::LEN]
-        .copy_from_slice(signing_key.verifying_key().as_ref());
+    memory.write(sig_address, &signature.to_bytes());
+    memory.write(msg_address, message.as_ref());
+    memory.write(pubkey_address, signing_key.verifying_key().as_ref());
 
     ed25519_verify(
         &mut memory,
@@ -150,7 +149,7 @@ fn test_verify_ed25519() -> SimpleResult<()> {
 
 #[test]
 fn test_keccak256() -> SimpleResult<()> {
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let owner = OwnershipRegisters {
         sp: 1000,
         ssp: 1000,
@@ -173,13 +172,16 @@ fn test_keccak256() -> SimpleResult<()> {
         num_bytes,
     )?;
     assert_eq!(pc, 8);
-    assert_ne!(&memory[hash as usize..hash as usize + 32], &[1u8; 32][..]);
+    assert_ne!(
+        &memory.read(hash as usize..hash as usize + 32)[..],
+        &[1u8; 32][..]
+    );
     Ok(())
 }
 
 #[test]
 fn test_sha256() -> SimpleResult<()> {
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let owner = OwnershipRegisters {
         sp: 1000,
         ssp: 1000,
@@ -202,6 +204,9 @@ fn test_sha256() -> SimpleResult<()> {
         num_bytes,
     )?;
     assert_eq!(pc, 8);
-    assert_ne!(&memory[hash as usize..hash as usize + 32], &[1u8; 32][..]);
+    assert_ne!(
+        &memory.read(hash as usize..hash as usize + 32)[..],
+        &[1u8; 32][..]
+    );
     Ok(())
 }
@@ -1,12 +1,28 @@
 #[cfg(test)]
-use alloc::{
-    vec,
-    vec::Vec,
-};
+use alloc::vec;
+use alloc::vec::Vec;
 
-use super::Interpreter;
-use crate::prelude::*;
-use fuel_asm::RegId;
+use super::{
+    Interpreter,
+    Memory,
+};
+use crate::{
+    consts::{
+        MEM_SIZE,
+        VM_REGISTER_COUNT,
+    },
+    prelude::*,
+    state::{
+        BreakCondition,
+        ExecuteState,
+        Watchpoint,
+    },
+    storage::InterpreterStorage,
+};
+use fuel_asm::{
+    RawInstruction,
+    RegId,
+};
 
 impl<S, Tx> Interpreter<S, Tx>
 where
@@ -32,13 +48,102 @@ where
         self.debugger.remove_breakpoint(breakpoint)
     }
 
-    pub(crate) fn eval_debugger_state(&mut self) -> DebugEval {
-        let debugger = &mut self.debugger;
+    /// Set a new breakpoint that breaks the program only if the condition holds.
+    pub fn set_conditional_breakpoint(
+        &mut self,
+        breakpoint: Breakpoint,
+        condition: BreakCondition,
+    ) {
+        self.debugger
+            .set_conditional_breakpoint(breakpoint, condition)
+    }
+
+    /// Watch a location, breaking the program after any instruction that changes it.
+    pub fn set_watchpoint(&mut self, watchpoint: Watchpoint) -> Result<(), PanicReason> {
+        self.debugger.set_watchpoint(watchpoint)
+    }
 
-        let contract = self.frames.last().map(CallFrame::to);
+    /// Remove a previously set watchpoint.
+    pub fn remove_watchpoint(&mut self, watchpoint: &Watchpoint) {
+        self.debugger.remove_watchpoint(watchpoint)
+    }
+
+    /// Returns `true` if the VM is suspended by a debug event.
+    pub fn is_debug_stopped(&self) -> bool {
+        matches!(self.debugger_last_state(), Some(state) if state.is_debug())
+    }
+
+    /// Mutable access to the registers, available only while the VM is suspended by
+    /// a debug event.
+    pub fn debug_registers_mut(&mut self) -> Option<&mut [Word; VM_REGISTER_COUNT]> {
+        self.is_debug_stopped().then_some(&mut self.registers)
+    }
+
+    /// Mutable access to the memory, available only while the VM is suspended by a
+    /// debug event.
+    pub fn debug_memory_mut(&mut self) -> Option<&mut Memory> {
+        self.is_debug_stopped().then_some(&mut self.memory)
+    }
+
+    /// Location of the next instruction to be executed.
+    pub(crate) fn debug_location(&self) -> Breakpoint {
+        let contract = self.frames.last().map(CallFrame::to).copied();
         let pc = self.registers[RegId::PC].saturating_sub(self.registers[RegId::IS]);
 
-        debugger.eval_state(contract, pc)
+        Breakpoint::raw(contract.unwrap_or_default(), pc)
+    }
+
+    pub(crate) fn eval_debugger_state(&mut self) -> DebugEval {
+        let current = self.debug_location();
+        let contract = *current.contract();
+        let pc = current.pc();
+        let depth = self.frames.len();
+
+        // The VM is resuming from a break in the current location
+        if matches!(self.debugger.take_last_state(), Some(s) if s == current) {
+            return DebugEval::Continue
+        }
+
+        let step_reached = self
+            .debugger
+            .step()
+            .is_some_and(|step| step.is_reached(depth));
+
+        let should_break = self.debugger.single_stepping()
+            || step_reached
+            || match self.debugger.breakpoint_at(contract, pc) {
+                Some(Some(condition)) => self.eval_break_condition(condition),
+                Some(None) => true,
+                None => false,
+            };
+
+        if should_break {
+            self.debugger.clear_step();
+            current.into()
+        } else {
+            DebugEval::Continue
+        }
+    }
+
+    fn eval_break_condition(&self, condition: &BreakCondition) -> bool {
+        match condition {
+            BreakCondition::Register {
+                register,
+                comparison,
+                value,
+            } => self
+                .registers
+                .get(*register)
+                .is_some_and(|r| comparison.eval(*r, *value)),
+            BreakCondition::Memory { address, bytes } => usize::try_from(*address)
+                .ok()
+                .and_then(|start| Some(start..start.checked_add(bytes.len())?))
+                .filter(|range| range.end <= MEM_SIZE)
+                .is_some_and(|range| self.memory.read(range)[..] == bytes[..]),
+            BreakCondition::GasBelow(threshold) => {
+                self.registers[RegId::GGAS] < *threshold
+            }
+        }
     }
 
     pub(crate) fn debugger_set_last_state(&mut self, state: ProgramState) {
@@ -50,6 +155,85 @@ where
     }
 }
 
+/// Value of a watched location.
+#[derive(Debug, PartialEq, Eq)]
+enum WatchValue {
+    Memory(Vec<u8>),
+    Storage(Option<Bytes32>),
+}
+
+impl<S, Tx> Interpreter<S, Tx>
+where
+    S: InterpreterStorage,
+    Tx: ExecutableTransaction,
+{
+    /// Execute an instruction, breaking the program if it changed a watched location.
+    pub(crate) fn watched_instruction(
+        &mut self,
+        raw: RawInstruction,
+    ) -> Result<ExecuteState, InterpreterError<S::DataError>> {
+        let before = self.watched_values()?;
+        let in_call = !self.frames.is_empty();
+
+        let state = self.dispatch_instruction(raw)?;
+
+        // Terminal states are never overridden by a watchpoint
+        let terminal = match state {
+            ExecuteState::Revert(_) => true,
+            ExecuteState::Proceed => false,
+            _ => !in_call,
+        };
+        if terminal {
+            return Ok(state)
+        }
+
+        let after = self.watched_values()?;
+        let changed = self
+            .debugger
+            .watchpoints()
+            .iter()
+            .zip(before.iter().zip(&after))
+            .find_map(|(watchpoint, (before, after))| {
+                (before != after).then_some(*watchpoint)
+            });
+
+        Ok(match changed {
+            Some(watchpoint) => {
+                self.debugger.clear_step();
+
+                DebugEval::Watchpoint {
+                    breakpoint: self.debug_location(),
+                    watchpoint,
+                }
+                .into()
+            }
+            None => state,
+        })
+    }
+
+    fn watched_values(&self) -> Result<Vec<WatchValue>, InterpreterError<S::DataError>> {
+        self.debugger
+            .watchpoints()
+            .iter()
+            .map(|watchpoint| match watchpoint {
+                Watchpoint::Memory { address, len } => {
+                    // Bounds are checked when the watchpoint is set
+                    let start = *address as usize;
+                    let end = start + *len as usize;
+                    Ok(WatchValue::Memory(
+                        self.memory.read(start..end).into_owned(),
+                    ))
+                }
+                Watchpoint::Storage { contract, key } => self
+                    .storage
+                    .merkle_contract_state(contract, key)
+                    .map(|value| WatchValue::Storage(value.map(|v| *v)))
+                    .map_err(InterpreterError::Storage),
+            })
+            .collect()
+    }
+}
+
 #[test]
 fn breakpoint_script() {
     use fuel_asm::op;
@@ -1,8 +1,11 @@
 //! # VM State Differences
 //! This module provides the ability to generate diffs between two VMs internal states.
 //! The diff can then be used to invert a VM to the original state.
-//! This module is experimental work in progress and currently only used in testing
-//! although it could potentially stabilize to be used in production.
+//!
+//! During a debug run, the interpreter can record the diff of every executed
+//! instruction in a bounded history, and rewind or replay them with
+//! [`Interpreter::step_back`] and [`Interpreter::goto`]. This requires the storage to
+//! be wrapped with [`Interpreter::add_recording`].
 
 use alloc::{
     sync::Arc,
@@ -52,6 +55,11 @@ use super::{
 };
 use storage::*;
 
+pub use storage::Record;
+
+pub(crate) use history::History;
+
+mod history;
 mod storage;
 
 #[cfg(test)]
@@ -322,7 +330,10 @@ where
 impl<S, Tx> Interpreter<S, Tx> {
     /// The diff function generates a diff of VM state, represented by the Diff struct,
     /// between two VMs internal states.
-    pub fn diff(&self, other: &Self) -> Diff<Deltas>
+    ///
+    /// The storages of the VMs are not compared, and don't need to be of the same
+    /// type.
+    pub fn diff<S2>(&self, other: &Interpreter<S2, Tx>) -> Diff<Deltas>
     where
         Tx: PartialEq + Clone + Debug + 'static,
     {
@@ -351,23 +362,17 @@ impl<S, Tx> Interpreter<S, Tx> {
         );
         diff.changes.extend(balances);
 
-        let mut memory = self.memory.iter().enumerate().zip(other.memory.iter());
-
-        while let Some(((start, s_from), s_to)) = memory
-            .by_ref()
-            .find(|((_, a), b)| a != b)
-            .map(|((n, a), b)| ((n, *a), *b))
-        {
-            let (mut from, mut to): (Vec<_>, Vec<_>) = memory
-                .by_ref()
-                .take_while(|((_, a), b)| a != b)
-                .map(|((_, a), b)| (*a, *b))
-                .unzip();
-            from.splice(..0, core::iter::once(s_from)).next();
-            to.splice(..0, core::iter::once(s_to)).next();
+        for range in self.memory.diff_ranges(&other.memory) {
+            let start = range.start;
             diff.changes.push(Change::Memory(Delta {
-                from: Memory { start, bytes: from },
-                to: Memory { start, bytes: to },
+                from: Memory {
+                    start,
+                    bytes: self.memory.read(range.clone()).into_owned(),
+                },
+                to: Memory {
+                    start,
+                    bytes: other.memory.read(range).into_owned(),
+                },
             }));
         }
 
@@ -408,7 +413,7 @@ impl<S, Tx> Interpreter<S, Tx> {
             }
             Change::Balance(Previous(value)) => invert_map(self.balances.as_mut(), value),
             Change::Memory(Previous(Memory { start, bytes })) => {
-                self.memory[*start..(*start + bytes.len())].copy_from_slice(&bytes[..])
+                self.memory.write(*start, bytes)
             }
             Change::Context(Previous(value)) => self.context = value.clone(),
             Change::PanicContext(Previous(value)) => self.panic_context = value.clone(),
@@ -518,6 +523,35 @@ impl<T> From<Delta<T>> for Previous<T> {
     }
 }
 
+impl<T: Clone> Delta<T> {
+    /// The state before the change, or after it if `forward` is set.
+    fn side(&self, forward: bool) -> Previous<T> {
+        if forward {
+            Previous(self.to.clone())
+        } else {
+            Previous(self.from.clone())
+        }
+    }
+}
+
+impl Change<Deltas> {
+    /// The change that sets the state before this one, or after it if `forward` is
+    /// set.
+    fn side(&self, forward: bool) -> Change<InitialVmState> {
+        match self {
+            Change::Register(v) => Change::Register(v.side(forward)),
+            Change::Memory(v) => Change::Memory(v.side(forward)),
+            Change::Storage(v) => Change::Storage(v.side(forward)),
+            Change::Frame(v) => Change::Frame(v.side(forward)),
+            Change::Receipt(v) => Change::Receipt(v.side(forward)),
+            Change::Balance(v) => Change::Balance(v.side(forward)),
+            Change::Context(v) => Change::Context(v.side(forward)),
+            Change::PanicContext(v) => Change::PanicContext(v.side(forward)),
+            Change::Txn(v) => Change::Txn(v.side(forward)),
+        }
+    }
+}
+
 impl<T: VmStateCapture + Clone> AddAssign for Diff<T> {
     fn add_assign(&mut self, rhs: Self) {
         self.changes.extend(rhs.changes);
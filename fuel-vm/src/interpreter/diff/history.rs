@@ -0,0 +1,327 @@
+use alloc::{
+    collections::VecDeque,
+    vec::Vec,
+};
+use core::{
+    fmt,
+    ops::RangeInclusive,
+};
+
+use fuel_asm::RawInstruction;
+
+use crate::{
+    error::InterpreterError,
+    hook::HookSlot,
+    profiler::Profiler,
+    state::{
+        DebugEval,
+        Debugger,
+        ExecuteState,
+        ProgramState,
+        StateChanges,
+        ValueChange,
+    },
+    storage::InterpreterStorage,
+};
+
+use super::{
+    storage::{
+        Record,
+        StorageDelta,
+    },
+    Deltas,
+    Diff,
+    ExecutableTransaction,
+    Interpreter,
+};
+
+/// Changes made by a single executed instruction.
+#[derive(Debug, Clone)]
+struct HistoryStep {
+    vm: Diff<Deltas>,
+    storage: Vec<StorageDelta>,
+    /// Recorded state changes around the instruction, if it wrote to the storage.
+    state_changes: Option<ValueChange<StateChanges>>,
+}
+
+/// Computes the changes made to a VM since its snapshot.
+type SnapshotDiff<S, Tx> = fn(&Interpreter<(), Tx>, &Interpreter<S, Tx>) -> Diff<Deltas>;
+
+/// Bounded log of the changes made by each executed instruction.
+///
+/// Instructions are indexed by their execution order, starting at `0`. The
+/// position of the VM is the number of instructions it executed, so stepping back
+/// from position `n` undoes the instruction `n - 1`.
+#[derive(Clone)]
+pub(crate) struct History<S, Tx> {
+    capacity: usize,
+    /// Recorded steps, oldest first. The last `undone` ones were rewound.
+    steps: VecDeque<HistoryStep>,
+    undone: usize,
+    /// Index of the first recorded step.
+    first: usize,
+    /// State changes of the VM at the current position.
+    state_changes: StateChanges,
+    /// Storage changes recorded by the storage.
+    storage_log: fn(&S) -> &[StorageDelta],
+    /// Computes the changes to the VM, which requires stricter bounds on the
+    /// transaction than the execution.
+    diff: SnapshotDiff<S, Tx>,
+}
+
+impl<S, Tx> History<S, Tx> {
+    /// Reset the history, keeping the recording enabled.
+    pub(crate) fn clear(&mut self) {
+        self.steps.clear();
+        self.undone = 0;
+        self.first = 0;
+        self.state_changes.clear();
+    }
+
+    fn position(&self) -> usize {
+        self.first + self.steps.len() - self.undone
+    }
+
+    fn range(&self) -> RangeInclusive<usize> {
+        self.first..=self.first + self.steps.len()
+    }
+
+    /// Record a new step, discarding the rewound ones.
+    fn push(&mut self, step: HistoryStep) {
+        self.steps.truncate(self.steps.len() - self.undone);
+        self.undone = 0;
+
+        self.steps.push_back(step);
+        if self.steps.len() > self.capacity {
+            self.steps.pop_front();
+            self.first += 1;
+        }
+    }
+}
+
+impl<S, Tx> fmt::Debug for History<S, Tx> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("History")
+            .field("capacity", &self.capacity)
+            .field("steps", &self.steps)
+            .field("undone", &self.undone)
+            .field("first", &self.first)
+            .finish()
+    }
+}
+
+impl<S, Tx> Interpreter<S, Tx>
+where
+    S: InterpreterStorage,
+    Tx: ExecutableTransaction,
+{
+    /// Execute an instruction, recording its changes in the history.
+    pub(crate) fn recorded_instruction(
+        &mut self,
+        raw: RawInstruction,
+    ) -> Result<ExecuteState, InterpreterError<S::DataError>> {
+        let Some(mut history) = self.history.take() else {
+            return self.run_instruction(raw)
+        };
+
+        let before = self.snapshot();
+        let storage_len = (history.storage_log)(&self.storage).len();
+
+        let result = self.run_instruction(raw);
+
+        let vm = (history.diff)(&before, self);
+        let storage = (history.storage_log)(&self.storage)
+            .get(storage_len..)
+            .unwrap_or_default()
+            .to_vec();
+        // Only the instructions writing to the storage change the state changes
+        let state_changes = (!storage.is_empty()).then(|| ValueChange {
+            old: core::mem::replace(
+                &mut history.state_changes,
+                self.state_changes.clone(),
+            ),
+            new: self.state_changes.clone(),
+        });
+        history.push(HistoryStep {
+            vm,
+            storage,
+            state_changes,
+        });
+        self.history = Some(history);
+
+        result
+    }
+
+    /// Copy of the VM state, without the storage.
+    fn snapshot(&self) -> Interpreter<(), Tx> {
+        Interpreter {
+            registers: self.registers,
+            memory: self.memory.clone(),
+            frames: self.frames.clone(),
+            receipts: self.receipts.clone(),
+            tx: self.tx.clone(),
+            initial_balances: self.initial_balances.clone(),
+            storage: (),
+            debugger: Debugger::default(),
+            state_changes: StateChanges::default(),
+            storage_checkpoint: self.storage_checkpoint,
+            context: self.context.clone(),
+            balances: self.balances.clone(),
+            profiler: Profiler::default(),
+            execution_hook: HookSlot::default(),
+            history: None,
+            interpreter_params: self.interpreter_params.clone(),
+            panic_context: self.panic_context.clone(),
+        }
+    }
+}
+
+impl<S, Tx> Interpreter<Record<S>, Tx>
+where
+    S: InterpreterStorage,
+    Tx: ExecutableTransaction + PartialEq + fmt::Debug + 'static,
+{
+    /// Record the changes made by each executed instruction, keeping the ones of the
+    /// last `capacity` instructions.
+    ///
+    /// While the VM is suspended by a debug event, the recorded instructions can be
+    /// undone and replayed with [`Self::step_back`] and [`Self::goto`], along with
+    /// their storage writes and [`StateChanges`]. The history is cleared when a new
+    /// transaction is initialized.
+    pub fn record_history(&mut self, capacity: usize) {
+        self.history = Some(History {
+            capacity,
+            steps: VecDeque::new(),
+            undone: 0,
+            first: 0,
+            state_changes: self.state_changes.clone(),
+            storage_log: |storage| &storage.1,
+            diff: Interpreter::diff,
+        });
+    }
+
+    /// Stop recording the changes made by the executed instructions, discarding the
+    /// history.
+    pub fn remove_history(&mut self) {
+        self.history = None;
+    }
+
+    /// Number of instructions executed since the initialization of the transaction,
+    /// if the history is recorded.
+    pub fn history_position(&self) -> Option<usize> {
+        self.history.as_ref().map(History::position)
+    }
+
+    /// Positions the VM can travel to with [`Self::goto`], if the history is
+    /// recorded.
+    pub fn history_range(&self) -> Option<RangeInclusive<usize>> {
+        self.history.as_ref().map(History::range)
+    }
+
+    /// Undo the last executed instruction, suspending the VM before it.
+    pub fn step_back(&mut self) -> Result<ProgramState, InterpreterError<S::DataError>> {
+        let position = self
+            .history_position()
+            .ok_or(InterpreterError::HistoryUnavailable)?;
+        let index = position
+            .checked_sub(1)
+            .ok_or(InterpreterError::HistoryUnavailable)?;
+
+        self.goto(index)
+    }
+
+    /// Undo or replay the recorded instructions until `index` instructions are
+    /// executed, suspending the VM there.
+    ///
+    /// Resuming the execution from a rewound position discards the undone
+    /// instructions from the history.
+    pub fn goto(
+        &mut self,
+        index: usize,
+    ) -> Result<ProgramState, InterpreterError<S::DataError>> {
+        if !self.is_debug_stopped() {
+            return Err(InterpreterError::DebugStateNotInitialized)
+        }
+
+        let mut history = self
+            .history
+            .take()
+            .ok_or(InterpreterError::HistoryUnavailable)?;
+        let result = self.travel(&mut history, index);
+        self.history = Some(history);
+        result?;
+
+        let debug = DebugEval::Breakpoint(self.debug_location());
+        let state = if self.context.is_predicate() {
+            ProgramState::VerifyPredicate(debug)
+        } else {
+            ProgramState::RunProgram(debug)
+        };
+        self.debugger_set_last_state(state);
+
+        Ok(state)
+    }
+
+    fn travel(
+        &mut self,
+        history: &mut History<Record<S>, Tx>,
+        index: usize,
+    ) -> Result<(), InterpreterError<S::DataError>> {
+        if !history.range().contains(&index) {
+            return Err(InterpreterError::HistoryUnavailable)
+        }
+
+        while history.position() > index {
+            history.undone += 1;
+            let step = &history.steps[history.steps.len() - history.undone];
+            self.apply_step(step, false)?;
+        }
+
+        while history.position() < index {
+            let step = &history.steps[history.steps.len() - history.undone];
+            self.apply_step(step, true)?;
+            history.undone -= 1;
+        }
+
+        history.state_changes.clone_from(&self.state_changes);
+        Ok(())
+    }
+
+    /// Set the VM to its state before the step, or after it if `forward` is set.
+    fn apply_step(
+        &mut self,
+        step: &HistoryStep,
+        forward: bool,
+    ) -> Result<(), InterpreterError<S::DataError>> {
+        for change in &step.vm.changes {
+            self.inverse_inner(&change.side(forward));
+        }
+
+        if let Some(state_changes) = &step.state_changes {
+            let state_changes = match forward {
+                true => &state_changes.new,
+                false => &state_changes.old,
+            };
+            self.state_changes.clone_from(state_changes);
+        }
+
+        let log = &mut self.storage.1;
+        if forward {
+            for delta in &step.storage {
+                delta
+                    .apply(&mut self.storage.0, true)
+                    .map_err(InterpreterError::Storage)?;
+            }
+            log.extend(step.storage.iter().cloned());
+        } else {
+            for delta in step.storage.iter().rev() {
+                delta
+                    .apply(&mut self.storage.0, false)
+                    .map_err(InterpreterError::Storage)?;
+            }
+            log.truncate(log.len().saturating_sub(step.storage.len()));
+        }
+
+        Ok(())
+    }
+}
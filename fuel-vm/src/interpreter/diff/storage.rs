@@ -12,10 +12,12 @@ use fuel_types::{
 };
 
 use crate::storage::{
+    AccessList,
     ContractsAssetKey,
     ContractsAssetsStorage,
     ContractsStateKey,
     InterpreterStorage,
+    StorageCheckpoint,
 };
 
 use super::{
@@ -24,7 +26,7 @@ use super::{
     *,
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 /// The set of state changes that are recorded.
 pub(super) enum StorageDelta {
     State(MappableDelta<ContractsStateKey, Bytes32>),
@@ -42,7 +44,7 @@ pub(super) enum StorageState {
     RawCode(MappableState<ContractId, Contract>),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 /// A [`Mappable`] type that has changed.
 pub(super) enum MappableDelta<Key, Value> {
     Insert(Key, Value, Option<Value>),
@@ -70,7 +72,15 @@ pub(super) trait StorageType: Mappable {
 }
 
 #[derive(Debug)]
-pub struct Record<S>(pub(super) S, pub(super) Vec<StorageDelta>)
+/// Storage wrapper recording the changes made to the contracts.
+///
+/// The third field holds the length of the log at each storage checkpoint, so the
+/// changes undone by reverting to a checkpoint are dropped from the log.
+pub struct Record<S>(
+    pub(super) S,
+    pub(super) Vec<StorageDelta>,
+    pub(super) Vec<usize>,
+)
 where
     S: InterpreterStorage;
 
@@ -92,10 +102,14 @@ where
             initial_balances: self.initial_balances,
             storage: self.storage.0,
             debugger: self.debugger,
+            state_changes: self.state_changes,
+            storage_checkpoint: self.storage_checkpoint,
             context: self.context,
             balances: self.balances,
             panic_context: self.panic_context,
             profiler: self.profiler,
+            execution_hook: self.execution_hook,
+            history: None,
             interpreter_params: self.interpreter_params,
         }
     }
@@ -165,10 +179,14 @@ where
             initial_balances: self.initial_balances,
             storage: Record::new(self.storage),
             debugger: self.debugger,
+            state_changes: self.state_changes,
+            storage_checkpoint: self.storage_checkpoint,
             context: self.context,
             balances: self.balances,
             panic_context: self.panic_context,
             profiler: self.profiler,
+            execution_hook: self.execution_hook,
+            history: None,
             interpreter_params: self.interpreter_params,
         }
     }
@@ -183,51 +201,109 @@ where
             if let Change::Storage(Previous(from)) = change {
                 match from {
                     StorageState::State(MappableState { key, value }) => {
-                        if let Some(value) = value {
-                            StorageMutate::<ContractsState>::insert(
-                                &mut self.storage,
-                                key,
-                                value,
-                            )
-                            .unwrap();
-                        }
+                        set_mappable::<ContractsState, _>(
+                            &mut self.storage,
+                            key,
+                            value.as_ref(),
+                        )
                     }
                     StorageState::Assets(MappableState { key, value }) => {
-                        if let Some(value) = value {
-                            StorageMutate::<ContractsAssets>::insert(
-                                &mut self.storage,
-                                key,
-                                value,
-                            )
-                            .unwrap();
-                        }
+                        set_mappable::<ContractsAssets, _>(
+                            &mut self.storage,
+                            key,
+                            value.as_ref(),
+                        )
                     }
                     StorageState::Info(MappableState { key, value }) => {
-                        if let Some(value) = value {
-                            StorageMutate::<ContractsInfo>::insert(
-                                &mut self.storage,
-                                key,
-                                value,
-                            )
-                            .unwrap();
-                        }
+                        set_mappable::<ContractsInfo, _>(
+                            &mut self.storage,
+                            key,
+                            value.as_ref(),
+                        )
                     }
                     StorageState::RawCode(MappableState { key, value }) => {
-                        if let Some(value) = value {
-                            StorageMutate::<ContractsRawCode>::insert(
-                                &mut self.storage,
-                                key,
-                                value.as_ref(),
-                            )
-                            .unwrap();
-                        }
+                        set_mappable::<ContractsRawCode, _>(
+                            &mut self.storage,
+                            key,
+                            value.as_ref().map(AsRef::as_ref),
+                        )
                     }
                 }
+                .unwrap();
             }
         }
     }
 }
 
+impl StorageDelta {
+    /// Set the storage to its state before this change, or after it if `forward` is
+    /// set.
+    pub(super) fn apply<S>(
+        &self,
+        storage: &mut S,
+        forward: bool,
+    ) -> Result<(), S::DataError>
+    where
+        S: InterpreterStorage,
+    {
+        match self {
+            StorageDelta::State(delta) => {
+                delta.apply::<ContractsState, _>(storage, forward, |value| value)
+            }
+            StorageDelta::Assets(delta) => {
+                delta.apply::<ContractsAssets, _>(storage, forward, |value| value)
+            }
+            StorageDelta::Info(delta) => {
+                delta.apply::<ContractsInfo, _>(storage, forward, |value| value)
+            }
+            StorageDelta::RawCode(delta) => {
+                delta.apply::<ContractsRawCode, _>(storage, forward, |value| {
+                    value.as_ref()
+                })
+            }
+        }
+    }
+}
+
+impl<Key, Value> MappableDelta<Key, Value> {
+    /// Set the storage to its state before this change, or after it if `forward` is
+    /// set.
+    fn apply<Type, S>(
+        &self,
+        storage: &mut S,
+        forward: bool,
+        as_value: fn(&Value) -> &Type::Value,
+    ) -> Result<(), S::Error>
+    where
+        Type: Mappable<Key = Key>,
+        S: StorageMutate<Type>,
+    {
+        let (key, value) = match (self, forward) {
+            (MappableDelta::Insert(key, value, _), true) => (key, Some(value)),
+            (MappableDelta::Insert(key, _, existing), false) => (key, existing.as_ref()),
+            (MappableDelta::Remove(key, _), true) => (key, None),
+            (MappableDelta::Remove(key, existing), false) => (key, Some(existing)),
+        };
+        set_mappable::<Type, S>(storage, key, value.map(as_value))
+    }
+}
+
+/// Insert the value of a key, or remove the key if there is no value.
+fn set_mappable<Type, S>(
+    storage: &mut S,
+    key: &Type::Key,
+    value: Option<&Type::Value>,
+) -> Result<(), S::Error>
+where
+    Type: Mappable,
+    S: StorageMutate<Type>,
+{
+    match value {
+        Some(value) => storage.insert(key, value).map(|_| ()),
+        None => storage.remove(key).map(|_| ()),
+    }
+}
+
 fn mappable_delta_to_hashmap<'value, K, V>(
     state: &mut Delta<HashMap<K, &'value V>>,
     delta: &'value MappableDelta<K, V>,
@@ -439,6 +515,32 @@ where
         self.0
             .merkle_contract_state_remove_range(contract, start_key, range)
     }
+
+    fn checkpoint(&mut self) -> Result<StorageCheckpoint, S::DataError> {
+        let checkpoint = self.0.checkpoint()?;
+        self.2.truncate(checkpoint.depth());
+        self.2.push(self.1.len());
+        Ok(checkpoint)
+    }
+
+    fn revert_to(&mut self, checkpoint: StorageCheckpoint) -> Result<(), S::DataError> {
+        self.0.revert_to(checkpoint)?;
+        if let Some(&len) = self.2.get(checkpoint.depth()) {
+            self.1.truncate(len);
+        }
+        self.2.truncate(checkpoint.depth());
+        Ok(())
+    }
+
+    fn release(&mut self, checkpoint: StorageCheckpoint) -> Result<(), S::DataError> {
+        self.0.release(checkpoint)?;
+        self.2.truncate(checkpoint.depth());
+        Ok(())
+    }
+
+    fn prefetch(&mut self, access_list: &AccessList) -> Result<(), S::DataError> {
+        self.0.prefetch(access_list)
+    }
 }
 
 impl StorageType for ContractsState {
@@ -504,7 +606,8 @@ impl<S> Record<S>
 where
     S: InterpreterStorage,
 {
+    /// Wrap a storage, recording the changes made to it from now on.
     pub fn new(s: S) -> Self {
-        Self(s, Vec::new())
+        Self(s, Vec::new(), Vec::new())
     }
 }
@@ -74,6 +74,13 @@ fn record_and_invert_storage() {
     assert_ne!(a, b);
     b.reset_vm_state(&diff);
     assert_eq!(a, b);
+    assert!(
+        !<Record<_> as StorageInspect<ContractsAssets>>::contains_key(
+            &b.storage,
+            &(&ContractId::default(), &AssetId::default()).into(),
+        )
+        .unwrap()
+    );
 
     let c = Interpreter::<_, Script>::with_memory_storage();
     let mut d = Interpreter::<_, Script>::with_memory_storage();
@@ -214,7 +221,7 @@ fn test_invert_map(v: &[(u32, u32)], key: u32, value: Option<u32>) -> Vec<(u32,
 fn reset_vm_memory() {
     let a = Interpreter::<_, Script>::with_memory_storage();
     let mut b = Interpreter::<_, Script>::with_memory_storage();
-    b.memory[100..132].copy_from_slice(&[1u8; 32]);
+    b.memory.write(100, &[1u8; 32]);
     let diff: Diff<InitialVmState> = a.diff(&b).into();
     assert_ne!(a, b);
     b.reset_vm_state(&diff);
@@ -4,7 +4,10 @@ use crate::{
         ExecutableTransaction,
         Interpreter,
     },
-    state::ProgramState,
+    state::{
+        ProgramState,
+        Step,
+    },
     storage::InterpreterStorage,
 };
 
@@ -29,12 +32,50 @@ where
             ProgramState::RunProgram(_) => self.run_program(),
 
             ProgramState::VerifyPredicate(_) => unimplemented!(),
-        }?;
+        };
+        let state = match state {
+            Ok(state) => state,
+            Err(e) => {
+                // The error is returned even if the storage can't be reverted.
+                let _ = self.resolve_storage_checkpoint(true);
+                return Err(e)
+            }
+        };
 
-        if state.is_debug() {
-            self.debugger_set_last_state(state);
+        if !state.is_debug() {
+            self.debugger.clear_step();
+            let revert = matches!(state, ProgramState::Revert(_));
+            self.resolve_storage_checkpoint(revert)?;
         }
+        self.debugger_set_last_state(state);
 
         Ok(state)
     }
+
+    /// Continue the execution and break on the next instruction, entering calls.
+    pub fn step_into(&mut self) -> Result<ProgramState, InterpreterError<S::DataError>> {
+        self.debugger.set_step(Step::Into);
+        self.resume()
+    }
+
+    /// Continue the execution and break on the next instruction of the current call
+    /// frame, executing a `CALL` without breaking inside of it.
+    ///
+    /// Breakpoints and watchpoints reached inside of the call still break the
+    /// program.
+    pub fn step_over(&mut self) -> Result<ProgramState, InterpreterError<S::DataError>> {
+        let depth = self.frames.len();
+        self.debugger.set_step(Step::Over { depth });
+        self.resume()
+    }
+
+    /// Continue the execution and break once the current call frame returns.
+    ///
+    /// Breakpoints and watchpoints reached before the return still break the
+    /// program.
+    pub fn step_out(&mut self) -> Result<ProgramState, InterpreterError<S::DataError>> {
+        let depth = self.frames.len();
+        self.debugger.set_step(Step::Out { depth });
+        self.resume()
+    }
 }
@@ -1,10 +1,16 @@
 use crate::{
     constraints::reg_key::ProgramRegistersSegment,
+    consts::MEM_SIZE,
     error::{
         InterpreterError,
         IoResult,
         RuntimeError,
     },
+    hook::{
+        ExecutionHook,
+        InstructionOutcome,
+        InstructionStep,
+    },
     interpreter::{
         alu,
         flow::{
@@ -45,13 +51,13 @@ where
 
     /// Reads the current instruction located in `$m[$pc]`,
     /// returning `None` on any memory access violation.
-    fn fetch_instruction(&self) -> Option<RawInstruction> {
+    pub(crate) fn fetch_instruction(&self) -> Option<RawInstruction> {
         let start: usize = self.registers[RegId::PC].try_into().ok()?;
         let end = start.checked_add(Instruction::SIZE)?;
-        let bytes = self.memory.get(start..end)?;
-        Some(RawInstruction::from_be_bytes(
-            bytes.try_into().expect("Slice len mismatch"),
-        ))
+        if end > MEM_SIZE {
+            return None
+        }
+        Some(RawInstruction::from_be_bytes(self.memory.read_bytes(start)))
     }
 
     /// Execute a provided instruction
@@ -66,8 +72,86 @@ where
             }
         }
 
-        self.instruction_inner(raw.into())
-            .map_err(|e| InterpreterError::from_runtime(e, raw.into()))
+        if self.history.is_some() {
+            return self.recorded_instruction(raw.into())
+        }
+
+        self.run_instruction(raw.into())
+    }
+
+    /// Execute a provided instruction, checking the watchpoints if any.
+    pub(crate) fn run_instruction(
+        &mut self,
+        raw: RawInstruction,
+    ) -> Result<ExecuteState, InterpreterError<S::DataError>> {
+        if self.debugger.is_active() && !self.debugger.watchpoints().is_empty() {
+            return self.watched_instruction(raw)
+        }
+
+        self.dispatch_instruction(raw)
+    }
+
+    /// Execute a provided instruction, without evaluating the debugger state.
+    pub(crate) fn dispatch_instruction(
+        &mut self,
+        raw: RawInstruction,
+    ) -> Result<ExecuteState, InterpreterError<S::DataError>> {
+        if let Some(mut hook) = self.execution_hook.take() {
+            let result = self.hooked_instruction(hook.as_mut(), raw);
+            self.execution_hook.set(hook);
+            return result
+        }
+
+        self.instruction_inner(raw)
+            .map_err(|e| InterpreterError::from_runtime(e, raw))
+    }
+
+    /// Execute a provided instruction, reporting it to the execution hook.
+    fn hooked_instruction(
+        &mut self,
+        hook: &mut dyn ExecutionHook,
+        raw: RawInstruction,
+    ) -> Result<ExecuteState, InterpreterError<S::DataError>> {
+        // An invalid instruction is still reported, so the hook sees the panic.
+        let instruction = Instruction::try_from(raw).ok();
+        let pc = self.registers[RegId::PC];
+        let gas = self.registers[RegId::GGAS];
+        let receipts = self.receipts.len();
+
+        hook.before_instruction(&self.instruction_step(raw, instruction, pc));
+
+        let result = self
+            .instruction_inner(raw)
+            .map_err(|e| InterpreterError::from_runtime(e, raw));
+
+        let outcome = InstructionOutcome {
+            gas_charged: gas.saturating_sub(self.registers[RegId::GGAS]),
+            receipts: &self.receipts.as_ref()[receipts..],
+            panic_reason: result
+                .as_ref()
+                .err()
+                .and_then(InterpreterError::panic_reason),
+        };
+        hook.after_instruction(&self.instruction_step(raw, instruction, pc), &outcome);
+
+        result
+    }
+
+    fn instruction_step(
+        &self,
+        raw: RawInstruction,
+        instruction: Option<Instruction>,
+        pc: Word,
+    ) -> InstructionStep<'_> {
+        InstructionStep {
+            raw,
+            instruction,
+            pc,
+            context: &self.context,
+            registers: &self.registers,
+            contract: self.contract_id(),
+            call_depth: self.frames.len(),
+        }
     }
 
     fn instruction_inner(
@@ -19,12 +19,14 @@ use crate::{
         PredicateVerificationFailed,
     },
     interpreter::{
+        contract::balance_increase,
         CheckedMetadata,
         ExecutableTransaction,
         InitialBalances,
         Interpreter,
         RuntimeBalances,
     },
+    pool::InterpreterPool,
     predicate::RuntimePredicate,
     prelude::{
         BugVariant,
@@ -33,10 +35,14 @@ use crate::{
     state::{
         ExecuteState,
         ProgramState,
+        StateChanges,
         StateTransition,
         StateTransitionRef,
     },
     storage::{
+        ChangesRecorder,
+        ContractsAssets,
+        ContractsState,
         InterpreterStorage,
         PredicateStorage,
     },
@@ -50,8 +56,13 @@ use fuel_asm::{
     PanicReason,
     RegId,
 };
+use fuel_storage::MerkleRootStorage;
 use fuel_tx::{
     field::{
+        InputContract,
+        MintAmount,
+        MintAssetId,
+        OutputContract,
         ReceiptsRoot,
         Salt,
         Script as ScriptField,
@@ -69,11 +80,14 @@ use fuel_tx::{
     Create,
     FeeParameters,
     Input,
+    Mint,
     Receipt,
     ScriptExecutionResult,
 };
 use fuel_types::{
     AssetId,
+    Bytes32,
+    ContractId,
     Word,
 };
 
@@ -132,7 +146,25 @@ impl<T> Interpreter<PredicateStorage, T> {
         <Tx as IntoChecked>::Metadata: CheckedMetadata,
     {
         let tx = checked.transaction();
-        Self::run_predicate(PredicateRunKind::Verifying(tx), params)
+        Self::run_predicate(PredicateRunKind::Verifying(tx), params, None)
+    }
+
+    /// Initialize the VM with the provided transaction and check all predicates defined
+    /// in the inputs, recycling the interpreters of the provided `pool`.
+    ///
+    /// The storage provider is not used since contract opcodes are not allowed for
+    /// predicates.
+    pub fn check_predicates_with_pool<Tx>(
+        checked: &Checked<Tx>,
+        params: &CheckPredicateParams,
+        pool: &mut InterpreterPool<Tx>,
+    ) -> Result<PredicatesChecked, PredicateVerificationFailed>
+    where
+        Tx: ExecutableTransaction,
+        <Tx as IntoChecked>::Metadata: CheckedMetadata,
+    {
+        let tx = checked.transaction();
+        Self::run_predicate(PredicateRunKind::Verifying(tx), params, Some(pool))
     }
 
     /// Initialize the VM with the provided transaction and check all predicates defined
@@ -171,7 +203,7 @@ impl<T> Interpreter<PredicateStorage, T> {
     where
         Tx: ExecutableTransaction,
     {
-        Self::run_predicate(PredicateRunKind::Estimating(transaction), params)?;
+        Self::run_predicate(PredicateRunKind::Estimating(transaction), params, None)?;
         Ok(())
     }
 
@@ -220,6 +252,7 @@ impl<T> Interpreter<PredicateStorage, T> {
 
                 let verify_task = E::create_task(move || {
                     Self::check_predicate(
+                        None,
                         tx,
                         index,
                         predicate_action,
@@ -240,6 +273,7 @@ impl<T> Interpreter<PredicateStorage, T> {
     fn run_predicate<Tx>(
         kind: PredicateRunKind<'_, Tx>,
         params: &CheckPredicateParams,
+        mut pool: Option<&mut InterpreterPool<Tx>>,
     ) -> Result<PredicatesChecked, PredicateVerificationFailed>
     where
         Tx: ExecutableTransaction,
@@ -254,6 +288,7 @@ impl<T> Interpreter<PredicateStorage, T> {
                 RuntimePredicate::from_tx(&tx, params.tx_offset, index)
             {
                 checks.push(Self::check_predicate(
+                    pool.as_deref_mut(),
                     tx,
                     index,
                     predicate_action,
@@ -272,6 +307,7 @@ impl<T> Interpreter<PredicateStorage, T> {
     }
 
     fn check_predicate<Tx>(
+        mut pool: Option<&mut InterpreterPool<Tx>>,
         tx: Tx,
         index: usize,
         predicate_action: PredicateAction,
@@ -308,8 +344,38 @@ impl<T> Interpreter<PredicateStorage, T> {
         let max_gas_per_predicate = params.max_gas_per_predicate;
         let interpreter_params = params.into();
 
-        let mut vm = Interpreter::with_storage(PredicateStorage {}, interpreter_params);
+        let mut vm = match pool.as_mut() {
+            Some(pool) => pool.acquire(PredicateStorage {}, interpreter_params),
+            None => Interpreter::with_storage(PredicateStorage {}, interpreter_params),
+        };
+        let result = Self::run_predicate_vm(
+            &mut vm,
+            tx,
+            index,
+            predicate_action,
+            predicate,
+            max_gas_per_tx,
+            max_gas_per_predicate,
+        );
+        if let Some(pool) = pool {
+            pool.release(vm);
+        }
 
+        result
+    }
+
+    fn run_predicate_vm<Tx>(
+        vm: &mut Interpreter<PredicateStorage, Tx>,
+        tx: Tx,
+        index: usize,
+        predicate_action: PredicateAction,
+        predicate: RuntimePredicate,
+        max_gas_per_tx: Word,
+        max_gas_per_predicate: Word,
+    ) -> Result<(Word, usize), PredicateVerificationFailed>
+    where
+        Tx: ExecutableTransaction,
+    {
         let available_gas = match predicate_action {
             PredicateAction::Verifying => {
                 let context = Context::PredicateVerification { program: predicate };
@@ -421,6 +487,7 @@ where
     fn deploy_inner(
         create: &mut Create,
         storage: &mut S,
+        state_changes: &mut StateChanges,
         initial_balances: InitialBalances,
         fee_params: &FeeParameters,
         base_asset_id: &AssetId,
@@ -466,9 +533,12 @@ where
             ))
         }
 
-        storage
-            .deploy_contract_with_id(salt, storage_slots, &contract, &root, &id)
-            .map_err(RuntimeError::Storage)?;
+        ChangesRecorder {
+            storage,
+            changes: state_changes,
+        }
+        .deploy_contract_with_id(salt, storage_slots, &contract, &root, &id)
+        .map_err(RuntimeError::Storage)?;
         Self::finalize_outputs(
             create,
             fee_params,
@@ -503,6 +573,7 @@ where
             Self::deploy_inner(
                 create,
                 &mut self.storage,
+                &mut self.state_changes,
                 self.initial_balances.clone(),
                 &fee_params,
                 &base_asset_id,
@@ -510,106 +581,175 @@ where
             self.update_transaction_outputs()?;
             ProgramState::Return(1)
         } else {
-            if self.transaction().inputs().iter().any(|input| {
-                if let Input::Contract(contract) = input {
-                    !self
-                        .check_contract_exists(&contract.contract_id)
-                        .unwrap_or(false)
-                } else {
-                    false
-                }
-            }) {
-                return Err(InterpreterError::Panic(PanicReason::ContractNotFound))
-            }
+            self.prepare_script()?;
 
-            if let Some(script) = self.transaction().as_script() {
-                let offset = (self.tx_offset() + script.script_offset()) as Word;
+            let program = if self.has_script_code() {
+                self.run_program()
+            } else {
+                self.run_empty_script()
+            };
 
-                self.registers[RegId::PC] = offset;
-                self.registers[RegId::IS] = offset;
-            }
+            self.finalize_script(program)?
+        };
 
-            // TODO set tree balance
+        Ok(state)
+    }
 
-            // `Interpreter` supports only `Create` and `Script` transactions. It is not
-            // `Create` -> it is `Script`.
-            let program = if !self
-                .transaction()
-                .as_script()
-                .expect("It should be `Script` transaction")
-                .script()
-                .is_empty()
-            {
-                self.run_program()
+    /// Check the input contracts, point the program counter to the script and take
+    /// the storage checkpoint of the script.
+    pub(crate) fn prepare_script(
+        &mut self,
+    ) -> Result<(), InterpreterError<S::DataError>> {
+        if self.transaction().inputs().iter().any(|input| {
+            if let Input::Contract(contract) = input {
+                !self
+                    .check_contract_exists(&contract.contract_id)
+                    .unwrap_or(false)
             } else {
-                // Return `1` as successful execution.
-                let return_val = 1;
-                self.ret(return_val)?;
-                Ok(ProgramState::Return(return_val))
-            };
+                false
+            }
+        }) {
+            return Err(InterpreterError::Panic(PanicReason::ContractNotFound))
+        }
 
-            let gas_used = self
-                .transaction()
-                .limit()
-                .checked_sub(self.remaining_gas())
-                .ok_or_else(|| Bug::new(BugVariant::GlobalGasUnderflow))?;
-
-            // Catch VM panic and don't propagate, generating a receipt
-            let (status, program) = match program {
-                Ok(s) => {
-                    // either a revert or success
-                    let res = if let ProgramState::Revert(_) = &s {
-                        ScriptExecutionResult::Revert
-                    } else {
-                        ScriptExecutionResult::Success
-                    };
-                    (res, s)
-                }
+        if let Some(script) = self.transaction().as_script() {
+            let offset = (self.tx_offset() + script.script_offset()) as Word;
 
-                Err(e) => match e.instruction_result() {
-                    Some(result) => {
-                        self.append_panic_receipt(result);
+            self.registers[RegId::PC] = offset;
+            self.registers[RegId::IS] = offset;
+        }
 
-                        (ScriptExecutionResult::Panic, ProgramState::Revert(0))
-                    }
+        // TODO set tree balance
 
-                    // This isn't a specified case of an erroneous program and should be
-                    // propagated. If applicable, OS errors will fall into this category.
-                    None => return Err(e),
-                },
-            };
+        // The storage changes of the script are undone if it reverts or panics.
+        let checkpoint = self
+            .storage
+            .checkpoint()
+            .map_err(InterpreterError::Storage)?;
+        self.storage_checkpoint = Some(checkpoint);
 
-            let receipt = Receipt::script_result(status, gas_used);
+        Ok(())
+    }
 
-            self.append_receipt(receipt);
+    /// Whether the script has instructions to run.
+    pub(crate) fn has_script_code(&self) -> bool {
+        // `Interpreter` supports only `Create` and `Script` transactions. It is not
+        // `Create` -> it is `Script`.
+        !self
+            .transaction()
+            .as_script()
+            .expect("It should be `Script` transaction")
+            .script()
+            .is_empty()
+    }
 
-            if program.is_debug() {
-                self.debugger_set_last_state(program);
-            }
+    /// Return from a script without instructions.
+    pub(crate) fn run_empty_script(
+        &mut self,
+    ) -> Result<ProgramState, InterpreterError<S::DataError>> {
+        // Return `1` as successful execution.
+        let return_val = 1;
+        self.ret(return_val)?;
+        Ok(ProgramState::Return(return_val))
+    }
+
+    /// Resolve the storage checkpoint of the script, and append the receipt of the
+    /// `program` to the transaction with its outputs.
+    pub(crate) fn finalize_script(
+        &mut self,
+        program: Result<ProgramState, InterpreterError<S::DataError>>,
+    ) -> Result<ProgramState, InterpreterError<S::DataError>> {
+        let base_asset_id = *self.base_asset_id();
+        let gas_used = self
+            .transaction()
+            .limit()
+            .checked_sub(self.remaining_gas())
+            .ok_or_else(|| Bug::new(BugVariant::GlobalGasUnderflow))?;
 
-            if let Some(script) = self.tx.as_script_mut() {
-                let receipts_root = self.receipts.root();
-                *script.receipts_root_mut() = receipts_root;
+        // Catch VM panic and don't propagate, generating a receipt
+        let (status, program) = match program {
+            Ok(s) => {
+                // either a revert or success
+                let res = if let ProgramState::Revert(_) = &s {
+                    ScriptExecutionResult::Revert
+                } else {
+                    ScriptExecutionResult::Success
+                };
+                (res, s)
             }
 
-            let revert = matches!(program, ProgramState::Revert(_));
-            let remaining_gas = self.remaining_gas();
-            let fee_params = *self.fee_params();
-            Self::finalize_outputs(
-                &mut self.tx,
-                &fee_params,
-                &base_asset_id,
-                revert,
-                remaining_gas,
-                &self.initial_balances,
-                &self.balances,
-            )?;
-            self.update_transaction_outputs()?;
+            Err(e) => match e.instruction_result() {
+                Some(result) => {
+                    self.append_panic_receipt(result);
 
-            program
+                    (ScriptExecutionResult::Panic, ProgramState::Revert(0))
+                }
+
+                // This isn't a specified case of an erroneous program and should be
+                // propagated. If applicable, OS errors will fall into this category.
+                None => {
+                    // The error is returned even if the storage can't be reverted.
+                    let _ = self.resolve_storage_checkpoint(true);
+                    return Err(e)
+                }
+            },
         };
 
-        Ok(state)
+        if !program.is_debug() {
+            let revert = matches!(program, ProgramState::Revert(_));
+            self.resolve_storage_checkpoint(revert)?;
+        }
+
+        let receipt = Receipt::script_result(status, gas_used);
+
+        self.append_receipt(receipt);
+
+        if program.is_debug() {
+            self.debugger_set_last_state(program);
+        }
+
+        if let Some(script) = self.tx.as_script_mut() {
+            let receipts_root = self.receipts.root();
+            *script.receipts_root_mut() = receipts_root;
+        }
+
+        let revert = matches!(program, ProgramState::Revert(_));
+        let remaining_gas = self.remaining_gas();
+        let fee_params = *self.fee_params();
+        Self::finalize_outputs(
+            &mut self.tx,
+            &fee_params,
+            &base_asset_id,
+            revert,
+            remaining_gas,
+            &self.initial_balances,
+            &self.balances,
+        )?;
+        self.update_transaction_outputs()?;
+
+        Ok(program)
+    }
+
+    /// Resolve the storage checkpoint taken before running the script, if any: undo
+    /// the storage changes of the script if `revert` is set, keep them otherwise.
+    pub(crate) fn resolve_storage_checkpoint(
+        &mut self,
+        revert: bool,
+    ) -> Result<(), InterpreterError<S::DataError>> {
+        let Some(checkpoint) = self.storage_checkpoint.take() else {
+            return Ok(())
+        };
+        if revert {
+            self.storage
+                .revert_to(checkpoint)
+                .map_err(InterpreterError::Storage)?;
+            self.state_changes.clear();
+        } else {
+            self.storage
+                .release(checkpoint)
+                .map_err(InterpreterError::Storage)?;
+        }
+        Ok(())
     }
 
     pub(crate) fn run_program(
@@ -622,9 +762,12 @@ where
             let state = self.execute()?;
 
             if in_call {
-                // Only reverts should terminate execution from a call context
-                if let ExecuteState::Revert(r) = state {
-                    return Ok(ProgramState::Revert(r))
+                // Only reverts and debug events should interrupt execution from a call
+                // context
+                match state {
+                    ExecuteState::Revert(r) => return Ok(ProgramState::Revert(r)),
+                    ExecuteState::DebugEvent(d) => return Ok(ProgramState::RunProgram(d)),
+                    _ => (),
                 }
             } else {
                 match state {
@@ -662,7 +805,12 @@ where
             .transact(tx)
             .map(ProgramState::from)
             .map(|state| {
-                StateTransition::new(state, interpreter.tx, interpreter.receipts.into())
+                StateTransition::new(
+                    state,
+                    interpreter.tx,
+                    interpreter.receipts.into(),
+                    interpreter.state_changes,
+                )
             })
     }
 
@@ -690,6 +838,7 @@ where
             state,
             self.transaction(),
             self.receipts(),
+            self.state_changes(),
         ))
     }
 }
@@ -712,10 +861,84 @@ where
         Self::deploy_inner(
             &mut create,
             &mut self.storage,
+            // The changes of the last executed transaction are kept
+            &mut StateChanges::default(),
             metadata.balances(),
             &fee_params,
             &base_asset_id,
         )?;
         Ok(create)
     }
+
+    /// Executes a `Mint` transaction without initialization VM and without invalidation
+    /// of the last state of execution of the `Script` transaction.
+    ///
+    /// The minted amount is credited to the contract of the input, as a transfer from
+    /// the script, which is the receipt of the execution. The balance and the minted
+    /// amount are recorded in the [`StateChanges`], and the balance and state roots of
+    /// the contract input and output are set to the ones before and after the
+    /// execution.
+    pub fn execute_mint(
+        &mut self,
+        tx: Checked<Mint>,
+    ) -> Result<StateTransition<Mint>, InterpreterError<S::DataError>> {
+        let (mut mint, _) = tx.into();
+        let contract_id = mint.input_contract().contract_id;
+        let amount = *mint.mint_amount();
+        let asset_id = *mint.mint_asset_id();
+
+        let input = mint.input_contract_mut();
+        (input.balance_root, input.state_root) = self.contract_roots(&contract_id)?;
+
+        let mut state_changes = StateChanges::default();
+        if amount > 0 {
+            balance_increase(
+                &mut ChangesRecorder {
+                    storage: &mut self.storage,
+                    changes: &mut state_changes,
+                },
+                &contract_id,
+                &asset_id,
+                amount,
+            )?;
+            state_changes.record_mint(&asset_id, amount);
+        }
+
+        let output = mint.output_contract_mut();
+        (output.balance_root, output.state_root) = self.contract_roots(&contract_id)?;
+
+        let receipts = vec![Receipt::transfer(
+            ContractId::zeroed(),
+            contract_id,
+            amount,
+            asset_id,
+            0,
+            0,
+        )];
+
+        Ok(StateTransition::new(
+            ProgramState::Return(1),
+            mint,
+            receipts,
+            state_changes,
+        ))
+    }
+
+    /// Roots of the balances and the state of a contract.
+    fn contract_roots(
+        &self,
+        contract: &ContractId,
+    ) -> Result<(Bytes32, Bytes32), InterpreterError<S::DataError>> {
+        let balance_root = MerkleRootStorage::<ContractId, ContractsAssets>::root(
+            &self.storage,
+            contract,
+        )
+        .map_err(InterpreterError::Storage)?;
+        let state_root = MerkleRootStorage::<ContractId, ContractsState>::root(
+            &self.storage,
+            contract,
+        )
+        .map_err(InterpreterError::Storage)?;
+        Ok((balance_root.into(), state_root.into()))
+    }
 }
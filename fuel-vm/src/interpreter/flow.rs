@@ -19,6 +19,7 @@ use super::{
     },
     ExecutableTransaction,
     Interpreter,
+    Memory,
     MemoryRange,
     RuntimeBalances,
 };
@@ -50,12 +51,16 @@ use crate::{
     },
     profiler::Profiler,
     storage::{
+        ChangesRecorder,
         ContractsAssetsStorage,
         ContractsRawCode,
         InterpreterStorage,
     },
 };
-use alloc::vec::Vec;
+use alloc::{
+    vec,
+    vec::Vec,
+};
 use core::cmp;
 use fuel_asm::{
     Instruction,
@@ -99,8 +104,7 @@ where
 
     pub(crate) fn ret(&mut self, a: Word) -> SimpleResult<()> {
         let current_contract =
-            current_contract(&self.context, self.registers.fp(), self.memory.as_ref())?
-                .copied();
+            current_contract(&self.context, self.registers.fp(), &self.memory)?;
         let tx_offset = self.tx_offset();
         let input = RetCtx {
             append: AppendReceipt {
@@ -119,8 +123,7 @@ where
 
     pub(crate) fn ret_data(&mut self, a: Word, b: Word) -> SimpleResult<Bytes32> {
         let current_contract =
-            current_contract(&self.context, self.registers.fp(), self.memory.as_ref())?
-                .copied();
+            current_contract(&self.context, self.registers.fp(), &self.memory)?;
         let tx_offset = self.tx_offset();
         let input = RetCtx {
             append: AppendReceipt {
@@ -139,8 +142,8 @@ where
 
     pub(crate) fn revert(&mut self, a: Word) {
         let current_contract =
-            current_contract(&self.context, self.registers.fp(), self.memory.as_ref())
-                .map_or_else(|_| Some(ContractId::zeroed()), Option::<&_>::copied);
+            current_contract(&self.context, self.registers.fp(), &self.memory)
+                .unwrap_or_else(|_| Some(ContractId::zeroed()));
         let tx_offset = self.tx_offset();
         let append = AppendReceipt {
             receipts: &mut self.receipts,
@@ -240,7 +243,7 @@ impl RetCtx<'_> {
             a,
             self.registers[RegId::PC],
             self.registers[RegId::IS],
-            self.append.memory[range.usizes()].to_vec(),
+            self.append.memory.read(range.usizes()).into_owned(),
         );
         let digest = *receipt
             .digest()
@@ -389,9 +392,8 @@ where
         };
         let gas_cost = self.gas_costs().call;
         let current_contract =
-            current_contract(&self.context, self.registers.fp(), self.memory.as_ref())?
-                .copied();
-        let memory = PrepareCallMemory::try_from((self.memory.as_mut(), &params))?;
+            current_contract(&self.context, self.registers.fp(), &self.memory)?;
+        let memory = PrepareCallMemory::try_from((&mut self.memory, &params))?;
         let input_contracts = self.tx.input_contracts().copied().collect::<Vec<_>>();
 
         PrepareCallCtx {
@@ -401,7 +403,10 @@ where
             context: &mut self.context,
             gas_cost,
             runtime_balances: &mut self.balances,
-            storage: &mut self.storage,
+            storage: &mut ChangesRecorder {
+                storage: &mut self.storage,
+                changes: &mut self.state_changes,
+            },
             input_contracts: InputContracts::new(
                 input_contracts.iter(),
                 &mut self.panic_context,
@@ -464,7 +469,7 @@ impl<'a> PrepareCallRegisters<'a> {
 }
 
 struct PrepareCallMemory<'a> {
-    memory: &'a mut [u8; MEM_SIZE],
+    memory: &'a mut Memory,
     call_params: CheckedMemValue<Call>,
     asset_id: CheckedMemValue<AssetId>,
 }
@@ -636,7 +641,7 @@ fn write_call_to_memory<S>(
     frame: &CallFrame,
     frame_bytes: Vec<u8>,
     code_mem_range: MemoryRange,
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     storage: &S,
 ) -> IoResult<Word, S::Error>
 where
@@ -645,19 +650,18 @@ where
     let mut code_frame_range = code_mem_range.clone();
     // Addition is safe because code size + padding is always less than len
     code_frame_range.shrink_end((frame.code_size() + frame.code_size_padding()) as usize);
-    code_frame_range
-        .clone()
-        .write(memory)
-        .copy_from_slice(&frame_bytes);
+    code_frame_range.clone().write(memory, &frame_bytes);
 
     let mut code_range = code_mem_range.clone();
     code_range.grow_start(CallFrame::serialized_size());
     code_range.shrink_end(frame.code_size_padding() as usize);
+    let mut code = vec![0u8; code_range.len()];
     let bytes_read = storage
         .storage::<ContractsRawCode>()
-        .read(frame.to(), code_range.write(memory))
+        .read(frame.to(), &mut code)
         .map_err(RuntimeError::Storage)?
         .ok_or(PanicReason::ContractNotFound)?;
+    memory.write(code_range.start, &code[..bytes_read]);
     if bytes_read as Word != frame.code_size() {
         return Err(PanicReason::ContractMismatch.into())
     }
@@ -666,7 +670,7 @@ where
         let mut padding_range = code_mem_range;
         padding_range
             .grow_start(CallFrame::serialized_size() + frame.code_size() as usize);
-        padding_range.write(memory).fill(0);
+        memory.fill(padding_range.usizes(), 0);
     }
     Ok(code_frame_range.end as Word)
 }
@@ -758,13 +762,11 @@ impl<'reg> From<SystemRegisters<'reg>>
     }
 }
 
-impl<'mem> TryFrom<(&'mem mut [u8; MEM_SIZE], &PrepareCallParams)>
-    for PrepareCallMemory<'mem>
-{
+impl<'mem> TryFrom<(&'mem mut Memory, &PrepareCallParams)> for PrepareCallMemory<'mem> {
     type Error = PanicReason;
 
     fn try_from(
-        (memory, params): (&'mem mut [u8; MEM_SIZE], &PrepareCallParams),
+        (memory, params): (&'mem mut Memory, &PrepareCallParams),
     ) -> Result<Self, Self::Error> {
         Ok(Self {
             memory,
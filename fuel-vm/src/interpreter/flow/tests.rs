@@ -30,7 +30,7 @@ struct Input {
     balance: Vec<(AssetId, Word)>,
     input_contracts: Vec<ContractId>,
     storage_balance: Vec<(AssetId, Word)>,
-    memory: Memory<MEM_SIZE>,
+    memory: Memory,
     gas_cost: DependentCost,
     storage_contract: Vec<(ContractId, Vec<u8>)>,
     script: Option<Script>,
@@ -78,7 +78,7 @@ struct RegInput {
 #[derive(PartialEq, Eq)]
 enum CheckMem {
     Check(Vec<(usize, Vec<u8>)>),
-    Mem(Memory<MEM_SIZE>),
+    Mem(Memory),
 }
 
 #[derive(PartialEq, Eq)]
@@ -144,10 +144,10 @@ impl Default for Output {
     }
 }
 
-fn mem(set: &[(usize, Vec<u8>)]) -> Memory<MEM_SIZE> {
-    let mut memory: Memory<MEM_SIZE> = vec![0u8; MEM_SIZE].try_into().unwrap();
+fn mem(set: &[(usize, Vec<u8>)]) -> Memory {
+    let mut memory: Memory = vec![0u8; MEM_SIZE].try_into().unwrap();
     for (addr, data) in set {
-        memory[*addr..*addr + data.len()].copy_from_slice(data);
+        memory.write(*addr, data);
     }
     memory
 }
@@ -352,7 +352,7 @@ fn test_prepare_call(input: Input) -> Result<Output, RuntimeError<Infallible>> {
     registers.system_registers.bal = RegMut::new(&mut reg.bal);
     registers.system_registers.cgas = RegMut::new(&mut reg.cgas);
     registers.system_registers.ggas = RegMut::new(&mut reg.ggas);
-    let memory = PrepareCallMemory::try_from((mem.as_mut(), &params))?;
+    let memory = PrepareCallMemory::try_from((&mut mem, &params))?;
     let mut runtime_balances =
         RuntimeBalances::try_from_iter(balance).expect("Balance should be valid");
     let mut storage = MemoryStorage::new(Default::default(), Default::default());
@@ -411,8 +411,8 @@ fn check_output(
                 (CheckMem::Check(e), CheckMem::Mem(r)) => {
                     for (i, bytes) in e {
                         assert_eq!(
-                            r[i..i + bytes.len()],
-                            bytes,
+                            r.read(i..i + bytes.len())[..],
+                            bytes[..],
                             "memory mismatch at {i}"
                         );
                     }
@@ -446,26 +446,28 @@ fn test_write_call_to_memory(
     StorageAsMut::storage::<ContractsRawCode>(&mut storage)
         .insert(call_frame.to(), &code)
         .unwrap();
-    let mut memory: Memory<MEM_SIZE> = vec![0u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![0u8; MEM_SIZE].try_into().unwrap();
     let end = write_call_to_memory(
         &call_frame,
         frame_bytes,
         code_mem_range,
-        memory.as_mut(),
+        &mut memory,
         &storage,
     )?;
     check_memory(memory, call_frame, code);
     Ok(end)
 }
 
-fn check_memory(result: Memory<MEM_SIZE>, expected: CallFrame, code: Vec<u8>) {
+fn check_memory(result: Memory, expected: CallFrame, code: Vec<u8>) {
     let frame = CheckedMemValue::<CallFrame>::new::<{ CallFrame::serialized_size() }>(0)
         .unwrap()
         .inspect(&result);
     assert_eq!(frame, expected);
     assert_eq!(
-        &result[CallFrame::serialized_size()
-            ..(CallFrame::serialized_size() + frame.total_code_size() as usize)],
+        &result.read(
+            CallFrame::serialized_size()
+                ..(CallFrame::serialized_size() + frame.total_code_size() as usize)
+        )[..],
         &code[..]
     );
 }
@@ -46,6 +46,18 @@ where
 
         self.frames.clear();
         self.receipts.clear();
+        self.state_changes.clear();
+        // A checkpoint is left open when the previous execution was suspended by the
+        // debugger and abandoned. Its changes were already applied, so they are kept.
+        if let Some(checkpoint) = self.storage_checkpoint.take() {
+            self.storage
+                .release(checkpoint)
+                .map_err(RuntimeError::Storage)?;
+        }
+
+        if let Some(history) = &mut self.history {
+            history.clear();
+        }
 
         // Optimized for memset
         self.registers.iter_mut().for_each(|r| *r = 0);
@@ -2,6 +2,7 @@ use super::{
     receipts::ReceiptsCtx,
     ExecutableTransaction,
     Interpreter,
+    Memory,
     MemoryRange,
     RuntimeBalances,
 };
@@ -10,7 +11,6 @@ use crate::{
         reg_key::*,
         CheckedMemConstLen,
     },
-    consts::*,
     context::Context,
     error::SimpleResult,
 };
@@ -39,6 +39,7 @@ use fuel_types::{
     Word,
 };
 
+use alloc::vec;
 use core::mem;
 
 #[cfg(test)]
@@ -73,7 +74,7 @@ where
 /// and the serialized tx in vm memory.
 pub(crate) fn set_variable_output<Tx: ExecutableTransaction>(
     tx: &mut Tx,
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     tx_offset: usize,
     idx: usize,
     variable: Output,
@@ -104,20 +105,21 @@ pub(crate) fn absolute_output_mem_range<Tx: Outputs>(
 
 pub(crate) fn update_memory_output<Tx: ExecutableTransaction>(
     tx: &mut Tx,
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     tx_offset: usize,
     idx: usize,
 ) -> SimpleResult<()> {
     let mem_range = absolute_output_mem_range(tx, tx_offset, idx)?
         .ok_or(PanicReason::OutputNotFound)?;
-    let mut mem = mem_range.write(memory);
     let output = tx
         .outputs_mut()
         .get_mut(idx)
         .expect("Invalid output index; checked above");
+    let mut buf = vec![0u8; mem_range.len()];
     output
-        .encode(&mut mem)
+        .encode(&mut buf.as_mut_slice())
         .expect("Unable to write output into given memory range");
+    mem_range.write(memory, &buf);
     Ok(())
 }
 
@@ -125,7 +127,7 @@ pub(crate) struct AppendReceipt<'vm> {
     pub receipts: &'vm mut ReceiptsCtx,
     pub script: Option<&'vm mut Script>,
     pub tx_offset: usize,
-    pub memory: &'vm mut [u8; MEM_SIZE],
+    pub memory: &'vm mut Memory,
 }
 
 pub(crate) fn append_receipt(input: AppendReceipt, receipt: Receipt) {
@@ -149,7 +151,7 @@ pub(crate) fn append_receipt(input: AppendReceipt, receipt: Receipt) {
 
         // Transaction memory space length is already checked on initialization so its
         // guaranteed to fit
-        memory[offset..offset + Bytes32::LEN].copy_from_slice(&root[..]);
+        memory.write(offset, &root[..]);
     }
 }
 
@@ -167,8 +169,7 @@ impl<S, Tx> Interpreter<S, Tx> {
     pub(crate) fn push_stack(&mut self, data: &[u8]) -> SimpleResult<()> {
         let ssp = self.reserve_stack(data.len() as Word)?;
 
-        self.memory[ssp as usize..self.registers[RegId::SSP] as usize]
-            .copy_from_slice(data);
+        self.memory.write(ssp as usize, data);
 
         Ok(())
     }
@@ -193,16 +194,12 @@ impl<S, Tx> Interpreter<S, Tx> {
         )
     }
 
-    pub(crate) fn internal_contract(&self) -> Result<&ContractId, PanicReason> {
+    pub(crate) fn internal_contract(&self) -> Result<ContractId, PanicReason> {
         internal_contract(&self.context, self.registers.fp(), &self.memory)
     }
 
     pub(crate) fn internal_contract_or_default(&self) -> ContractId {
-        internal_contract_or_default(
-            &self.context,
-            self.registers.fp(),
-            self.memory.as_ref(),
-        )
+        internal_contract_or_default(&self.context, self.registers.fp(), &self.memory)
     }
 
     pub(crate) fn get_block_height(&self) -> Result<BlockHeight, PanicReason> {
@@ -240,19 +237,16 @@ pub(crate) fn inc_pc(mut pc: RegMut<PC>) -> Result<(), PanicReason> {
         .map(|i| *pc = i)
 }
 
-pub(crate) fn tx_id(memory: &[u8; MEM_SIZE]) -> &Bytes32 {
-    let memory = (&memory[..Bytes32::LEN])
-        .try_into()
-        .expect("Bytes32::LEN < MEM_SIZE");
+pub(crate) fn tx_id(memory: &Memory) -> Bytes32 {
     // Safety: vm parameters guarantees enough space for txid
-    Bytes32::from_bytes_ref(memory)
+    Bytes32::from(memory.read_bytes(0))
 }
 
 /// Reduces the unspent balance of the base asset
 pub(crate) fn base_asset_balance_sub(
     base_asset_id: &AssetId,
     balances: &mut RuntimeBalances,
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     value: Word,
 ) -> SimpleResult<()> {
     external_asset_id_balance_sub(balances, memory, base_asset_id, value)
@@ -261,7 +255,7 @@ pub(crate) fn base_asset_balance_sub(
 /// Reduces the unspent balance of a given asset ID
 pub(crate) fn external_asset_id_balance_sub(
     balances: &mut RuntimeBalances,
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     asset_id: &AssetId,
     value: Word,
 ) -> SimpleResult<()> {
@@ -275,17 +269,16 @@ pub(crate) fn external_asset_id_balance_sub(
 pub(crate) fn internal_contract_or_default(
     context: &Context,
     register: Reg<FP>,
-    memory: &[u8; MEM_SIZE],
+    memory: &Memory,
 ) -> ContractId {
-    internal_contract(context, register, memory)
-        .map_or(Default::default(), |contract| *contract)
+    internal_contract(context, register, memory).unwrap_or_default()
 }
 
-pub(crate) fn current_contract<'a>(
+pub(crate) fn current_contract(
     context: &Context,
     fp: Reg<FP>,
-    memory: &'a [u8; MEM_SIZE],
-) -> Result<Option<&'a ContractId>, PanicReason> {
+    memory: &Memory,
+) -> Result<Option<ContractId>, PanicReason> {
     if context.is_internal() {
         Ok(Some(internal_contract(context, fp, memory)?))
     } else {
@@ -293,15 +286,15 @@ pub(crate) fn current_contract<'a>(
     }
 }
 
-pub(crate) fn internal_contract<'a>(
+pub(crate) fn internal_contract(
     context: &Context,
     register: Reg<FP>,
-    memory: &'a [u8; MEM_SIZE],
-) -> Result<&'a ContractId, PanicReason> {
+    memory: &Memory,
+) -> Result<ContractId, PanicReason> {
     let range = internal_contract_bounds(context, register)?;
 
     // Safety: Memory bounds logically verified by the interpreter
-    let contract = ContractId::from_bytes_ref(range.read(memory));
+    let contract = ContractId::from(range.read(memory));
 
     Ok(contract)
 }
@@ -17,7 +17,10 @@ use fuel_tx::{
     ConsensusParameters,
     TransactionBuilder,
 };
-use fuel_types::canonical::Deserialize;
+use fuel_types::canonical::{
+    Deserialize,
+    Serialize,
+};
 use rand::{
     rngs::StdRng,
     Rng,
@@ -145,7 +148,9 @@ fn variable_output_updates_in_memory() {
     // verify the vm memory is updated properly
     let position = vm.tx_offset() + vm.transaction().outputs_offset_at(0).unwrap();
 
-    let mem_output = Output::decode(&mut &vm.memory()[position..]).unwrap();
+    let size = vm.transaction().outputs()[0].size();
+    let mem_output =
+        Output::decode(&mut &vm.memory().read(position..position + size)[..]).unwrap();
     assert_eq!(vm.transaction().outputs()[0], mem_output);
 }
 
@@ -8,11 +8,11 @@ use super::{
     receipts::ReceiptsCtx,
     ExecutableTransaction,
     Interpreter,
+    Memory,
     MemoryRange,
 };
 use crate::{
     constraints::reg_key::*,
-    consts::*,
     context::Context,
     error::SimpleResult,
 };
@@ -72,7 +72,7 @@ where
 }
 
 struct LogInput<'vm> {
-    memory: &'vm mut [u8; MEM_SIZE],
+    memory: &'vm mut Memory,
     tx_offset: usize,
     context: &'vm Context,
     receipts: &'vm mut ReceiptsCtx,
@@ -117,7 +117,7 @@ impl LogInput<'_> {
             c,
             *self.pc,
             *self.is,
-            self.memory[range.usizes()].to_vec(),
+            self.memory.read(range.usizes()).into_owned(),
         );
 
         append_receipt(
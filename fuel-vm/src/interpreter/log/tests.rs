@@ -1,12 +1,15 @@
 use alloc::vec;
 
-use crate::interpreter::memory::Memory;
+use crate::{
+    consts::*,
+    interpreter::memory::Memory,
+};
 
 use super::*;
 
 #[test]
 fn test_log() -> SimpleResult<()> {
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let context = Context::Script {
         block_height: Default::default(),
     };
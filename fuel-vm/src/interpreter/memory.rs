@@ -20,13 +20,353 @@ use fuel_types::{
     Word,
 };
 
-use alloc::boxed::Box;
+use alloc::{
+    borrow::Cow,
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
 use core::{
+    fmt,
     ops,
     ops::Range,
 };
 
-pub type Memory<const SIZE: usize> = Box<[u8; SIZE]>;
+type Page = [u8; MEM_PAGE_SIZE];
+
+const PAGE_COUNT: usize = MEM_SIZE / MEM_PAGE_SIZE;
+
+static_assertions::const_assert_eq!(MEM_SIZE % MEM_PAGE_SIZE, 0);
+
+/// Backing page of every address that was never written.
+static ZERO_PAGE: Page = [0; MEM_PAGE_SIZE];
+
+/// The VM memory.
+///
+/// The address space is split into pages of [`MEM_PAGE_SIZE`] bytes. A page is only
+/// allocated on its first write, and reads of unallocated pages yield zeroes. Pages are
+/// reference counted, so cloning the memory is cheap and the pages are copied only when
+/// one of the clones writes to them.
+#[derive(Clone)]
+pub struct Memory {
+    pages: Vec<Option<Arc<Page>>>,
+}
+
+impl Default for Memory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Memory {
+    /// Create a new zeroed memory without allocating any page.
+    pub fn new() -> Self {
+        Self {
+            pages: vec![None; PAGE_COUNT],
+        }
+    }
+
+    /// The size of the memory address space, in bytes.
+    pub const fn len(&self) -> usize {
+        MEM_SIZE
+    }
+
+    /// The memory address space is never empty.
+    pub const fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Number of pages that are currently allocated.
+    pub fn allocated_pages(&self) -> usize {
+        self.pages.iter().filter(|page| page.is_some()).count()
+    }
+
+    /// Read the bytes of `range`.
+    ///
+    /// The bytes are borrowed if the range is contained in a single page, and copied
+    /// otherwise. Panics if the range is out of the memory bounds.
+    pub fn read(&self, range: Range<usize>) -> Cow<'_, [u8]> {
+        assert_in_bounds(&range);
+
+        if range.is_empty() {
+            return Cow::Borrowed(&[])
+        }
+
+        let first = range.start / MEM_PAGE_SIZE;
+        let last = (range.end - 1) / MEM_PAGE_SIZE;
+
+        if first == last {
+            let offset = range.start % MEM_PAGE_SIZE;
+            Cow::Borrowed(&self.page(first)[offset..offset + range.len()])
+        } else {
+            let mut buf = vec![0u8; range.len()];
+            self.read_into(range.start, &mut buf);
+            Cow::Owned(buf)
+        }
+    }
+
+    /// Copy the bytes starting at `addr` into `buf`.
+    ///
+    /// Panics if the range is out of the memory bounds.
+    pub fn read_into(&self, addr: usize, buf: &mut [u8]) {
+        for (page, in_page, in_buf) in chunks(addr, buf.len()) {
+            buf[in_buf].copy_from_slice(&self.page(page)[in_page]);
+        }
+    }
+
+    /// Read a constant-sized byte array starting at `addr`.
+    ///
+    /// Panics if the range is out of the memory bounds.
+    pub fn read_bytes<const COUNT: usize>(&self, addr: usize) -> [u8; COUNT] {
+        let mut bytes = [0u8; COUNT];
+        self.read_into(addr, &mut bytes);
+        bytes
+    }
+
+    /// Write `data` starting at `addr`, allocating the touched pages if needed.
+    ///
+    /// Panics if the range is out of the memory bounds.
+    pub fn write(&mut self, addr: usize, data: &[u8]) {
+        for (page, in_page, in_buf) in chunks(addr, data.len()) {
+            self.page_mut(page)[in_page].copy_from_slice(&data[in_buf]);
+        }
+    }
+
+    /// Set all bytes of `range` to `value`.
+    ///
+    /// Zeroing never allocates: unallocated pages are skipped and fully zeroed pages are
+    /// released. Panics if the range is out of the memory bounds.
+    pub fn fill(&mut self, range: Range<usize>, value: u8) {
+        assert_in_bounds(&range);
+
+        for (page, in_page, _) in chunks(range.start, range.len()) {
+            if value == 0 {
+                if self.pages[page].is_none() {
+                    continue
+                }
+
+                if in_page.len() == MEM_PAGE_SIZE {
+                    self.pages[page] = None;
+                    continue
+                }
+            }
+
+            self.page_mut(page)[in_page].fill(value);
+        }
+    }
+
+    /// Copy the bytes of `src` to the range starting at `dst`. The ranges may overlap.
+    ///
+    /// The copy is done page by page, without buffering the range. When the destination
+    /// starts inside the source, the chunks are copied from the end so every byte is read
+    /// before it's overwritten. Panics if any of the ranges is out of the memory bounds.
+    pub fn copy_within(&mut self, src: Range<usize>, dst: usize) {
+        assert_in_bounds(&src);
+        let len = src.len();
+        let dst_end = dst.checked_add(len).expect("Memory address overflow");
+        assert_in_bounds(&(dst..dst_end));
+
+        let backwards = dst > src.start && dst < src.end;
+        let mut copied = 0;
+        while copied < len {
+            let remaining = len - copied;
+            let (from, to, size) = if backwards {
+                let from_end = src.end - copied;
+                let to_end = dst_end - copied;
+                let size = remaining
+                    .min((from_end - 1) % MEM_PAGE_SIZE + 1)
+                    .min((to_end - 1) % MEM_PAGE_SIZE + 1);
+                (from_end - size, to_end - size, size)
+            } else {
+                let from = src.start + copied;
+                let to = dst + copied;
+                let size = remaining
+                    .min(MEM_PAGE_SIZE - from % MEM_PAGE_SIZE)
+                    .min(MEM_PAGE_SIZE - to % MEM_PAGE_SIZE);
+                (from, to, size)
+            };
+
+            self.copy_in_pages(from, to, size);
+            copied += size;
+        }
+    }
+
+    /// Copy `size` bytes from `from` to `to`, both ranges lying within a single page.
+    fn copy_in_pages(&mut self, from: usize, to: usize, size: usize) {
+        let (from_page, from_offset) = (from / MEM_PAGE_SIZE, from % MEM_PAGE_SIZE);
+        let (to_page, to_offset) = (to / MEM_PAGE_SIZE, to % MEM_PAGE_SIZE);
+
+        if from_page == to_page {
+            // An unallocated page only copies zeroes onto itself.
+            if self.pages[from_page].is_some() {
+                self.page_mut(from_page)
+                    .copy_within(from_offset..from_offset + size, to_offset);
+            }
+            return
+        }
+
+        // Sharing the source page is a reference count bump, not a copy.
+        match self.pages[from_page].clone() {
+            Some(page) => self.page_mut(to_page)[to_offset..to_offset + size]
+                .copy_from_slice(&page[from_offset..from_offset + size]),
+            None => self.fill(to..to + size, 0),
+        }
+    }
+
+    /// Release all pages, zeroing the whole memory.
+    pub fn clear(&mut self) {
+        self.pages.iter_mut().for_each(|page| *page = None);
+    }
+
+    /// Copy the whole memory into a contiguous buffer of [`MEM_SIZE`] bytes.
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut buf = vec![0u8; MEM_SIZE];
+        for (page, chunk) in self.pages.iter().zip(buf.chunks_exact_mut(MEM_PAGE_SIZE)) {
+            if let Some(page) = page {
+                chunk.copy_from_slice(&page[..]);
+            }
+        }
+        buf
+    }
+
+    /// Maximal ranges of addresses whose bytes differ between `self` and `other`.
+    ///
+    /// Pages that are shared between both memories, or unallocated in both, are skipped
+    /// without being compared.
+    pub(crate) fn diff_ranges(&self, other: &Self) -> Vec<Range<usize>> {
+        let mut ranges = Vec::new();
+        let mut current: Option<Range<usize>> = None;
+
+        for (index, (a, b)) in self.pages.iter().zip(other.pages.iter()).enumerate() {
+            let identical = match (a, b) {
+                (None, None) => true,
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                _ => false,
+            };
+
+            if identical {
+                ranges.extend(current.take());
+                continue
+            }
+
+            let base = index * MEM_PAGE_SIZE;
+            let a = self.page(index);
+            let b = other.page(index);
+
+            for (offset, (a, b)) in a.iter().zip(b.iter()).enumerate() {
+                let addr = base + offset;
+                match (&mut current, a != b) {
+                    (Some(range), true) => range.end = addr + 1,
+                    (None, true) => current = Some(addr..addr + 1),
+                    (Some(_), false) => ranges.extend(current.take()),
+                    (None, false) => (),
+                }
+            }
+        }
+
+        ranges.extend(current);
+        ranges
+    }
+
+    fn page(&self, index: usize) -> &Page {
+        self.pages[index].as_deref().unwrap_or(&ZERO_PAGE)
+    }
+
+    fn page_mut(&mut self, index: usize) -> &mut Page {
+        Arc::make_mut(
+            self.pages[index].get_or_insert_with(|| Arc::new([0; MEM_PAGE_SIZE])),
+        )
+    }
+}
+
+fn assert_in_bounds(range: &Range<usize>) {
+    assert!(
+        range.start <= range.end && range.end <= MEM_SIZE,
+        "Memory range {range:?} out of bounds"
+    );
+}
+
+/// Split the `len` bytes starting at `addr` into per-page chunks of
+/// `(page index, range within the page, range relative to addr)`.
+fn chunks(
+    addr: usize,
+    len: usize,
+) -> impl Iterator<Item = (usize, Range<usize>, Range<usize>)> {
+    let end = addr.checked_add(len).expect("Memory address overflow");
+    assert_in_bounds(&(addr..end));
+
+    let mut cursor = addr;
+    core::iter::from_fn(move || {
+        if cursor >= end {
+            return None
+        }
+
+        let page = cursor / MEM_PAGE_SIZE;
+        let offset = cursor % MEM_PAGE_SIZE;
+        let size = (MEM_PAGE_SIZE - offset).min(end - cursor);
+        let relative = cursor - addr;
+        cursor += size;
+
+        Some((page, offset..offset + size, relative..relative + size))
+    })
+}
+
+impl PartialEq for Memory {
+    fn eq(&self, other: &Self) -> bool {
+        self.pages
+            .iter()
+            .zip(other.pages.iter())
+            .enumerate()
+            .all(|(index, pages)| match pages {
+                (None, None) => true,
+                (Some(a), Some(b)) if Arc::ptr_eq(a, b) => true,
+                _ => self.page(index) == other.page(index),
+            })
+    }
+}
+
+impl Eq for Memory {}
+
+impl fmt::Debug for Memory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Memory")
+            .field("allocated_pages", &self.allocated_pages())
+            .finish()
+    }
+}
+
+impl ops::Index<usize> for Memory {
+    type Output = u8;
+
+    fn index(&self, addr: usize) -> &Self::Output {
+        &self.page(addr / MEM_PAGE_SIZE)[addr % MEM_PAGE_SIZE]
+    }
+}
+
+impl TryFrom<Vec<u8>> for Memory {
+    type Error = Vec<u8>;
+
+    /// Build a memory from a buffer of exactly [`MEM_SIZE`] bytes. Pages that are
+    /// fully zeroed are left unallocated.
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        if bytes.len() != MEM_SIZE {
+            return Err(bytes)
+        }
+
+        let pages = bytes
+            .chunks_exact(MEM_PAGE_SIZE)
+            .map(|chunk| {
+                chunk.iter().any(|b| *b != 0).then(|| {
+                    let mut page = [0; MEM_PAGE_SIZE];
+                    page.copy_from_slice(chunk);
+                    Arc::new(page)
+                })
+            })
+            .collect();
+
+        Ok(Self { pages })
+    }
+}
 
 #[cfg(test)]
 mod tests;
@@ -37,6 +377,9 @@ mod allocation_tests;
 #[cfg(test)]
 mod stack_tests;
 
+#[cfg(test)]
+mod paging_tests;
+
 /// Used to handle `Word` to `usize` conversions for memory addresses,
 /// as well as checking that the resulting value is withing the VM ram boundaries.
 pub trait ToAddr {
@@ -189,14 +532,17 @@ impl MemoryRange {
         self.0 = self.0.start.saturating_add(by)..self.0.end;
     }
 
-    /// Get the memory slice for this range.
-    pub fn read(self, memory: &[u8; MEM_SIZE]) -> &[u8] {
-        &memory[self.0]
+    /// Get the memory bytes for this range.
+    pub fn read(self, memory: &Memory) -> Cow<'_, [u8]> {
+        memory.read(self.0)
     }
 
-    /// Get the mutable memory slice for this range.
-    pub fn write(self, memory: &mut [u8; MEM_SIZE]) -> &mut [u8] {
-        &mut memory[self.0]
+    /// Overwrite the memory bytes of this range with `data`.
+    ///
+    /// Panics if the length of `data` doesn't match the length of the range.
+    pub fn write(self, memory: &mut Memory, data: &[u8]) {
+        assert_eq!(self.len(), data.len(), "Memory range length mismatch");
+        memory.write(self.start, data)
     }
 }
 
@@ -367,7 +713,7 @@ where
 
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn push_selected_registers(
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     sp: RegMut<SP>,
     ssp: Reg<SSP>,
     hp: Reg<HP>,
@@ -384,13 +730,13 @@ pub(crate) fn push_selected_registers(
     try_update_stack_pointer(sp, ssp, hp, stack_range.words().end)?;
 
     // Write the registers to the stack
-    let mut it = memory[stack_range.usizes()].chunks_exact_mut(8);
+    let mut it = stack_range.usizes().step_by(8);
     for (i, reg) in program_regs.segment(segment).iter().enumerate() {
         if (bitmask & (1 << i)) != 0 {
-            let item = it
+            let addr = it
                 .next()
                 .expect("Memory range mismatched with register count");
-            item.copy_from_slice(&reg.to_be_bytes());
+            memory.write(addr, &reg.to_be_bytes());
         }
     }
 
@@ -399,7 +745,7 @@ pub(crate) fn push_selected_registers(
 
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn pop_selected_registers(
-    memory: &[u8; MEM_SIZE],
+    memory: &Memory,
     sp: RegMut<SP>,
     ssp: Reg<SSP>,
     hp: Reg<HP>,
@@ -420,12 +766,11 @@ pub(crate) fn pop_selected_registers(
     let stack_range = MemoryRange::new(new_sp, size_in_stack)?.usizes();
 
     // Restore registers from the stack
-    let mut it = memory[stack_range].chunks_exact(8);
+    let mut it = stack_range.step_by(8);
     for (i, reg) in program_regs.segment_mut(segment).iter_mut().enumerate() {
         if (bitmask & (1 << i)) != 0 {
-            let mut buf = [0u8; 8];
-            buf.copy_from_slice(it.next().expect("Count mismatch"));
-            *reg = Word::from_be_bytes(buf);
+            let addr = it.next().expect("Count mismatch");
+            *reg = Word::from_be_bytes(memory.read_bytes(addr));
         }
     }
 
@@ -433,7 +778,7 @@ pub(crate) fn pop_selected_registers(
 }
 
 pub(crate) fn load_byte(
-    memory: &[u8; MEM_SIZE],
+    memory: &Memory,
     pc: RegMut<PC>,
     result: &mut Word,
     b: Word,
@@ -445,7 +790,7 @@ pub(crate) fn load_byte(
 }
 
 pub(crate) fn load_word(
-    memory: &[u8; MEM_SIZE],
+    memory: &Memory,
     pc: RegMut<PC>,
     result: &mut Word,
     b: Word,
@@ -459,7 +804,7 @@ pub(crate) fn load_word(
 }
 
 pub(crate) fn store_byte(
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     owner: OwnershipRegisters,
     pc: RegMut<PC>,
     a: Word,
@@ -471,7 +816,7 @@ pub(crate) fn store_byte(
 }
 
 pub(crate) fn store_word(
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     owner: OwnershipRegisters,
     pc: RegMut<PC>,
     a: Word,
@@ -503,7 +848,7 @@ pub(crate) fn malloc(
 }
 
 pub(crate) fn memclear(
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     owner: OwnershipRegisters,
     pc: RegMut<PC>,
     a: Word,
@@ -511,12 +856,12 @@ pub(crate) fn memclear(
 ) -> SimpleResult<()> {
     let range = MemoryRange::new(a, b)?;
     owner.verify_ownership(&range)?;
-    memory[range.usizes()].fill(0);
+    memory.fill(range.usizes(), 0);
     Ok(inc_pc(pc)?)
 }
 
 pub(crate) fn memcopy(
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     owner: OwnershipRegisters,
     pc: RegMut<PC>,
     a: Word,
@@ -536,20 +881,13 @@ pub(crate) fn memcopy(
         return Err(PanicReason::MemoryWriteOverlap.into())
     }
 
-    let len = src_range.len();
-    if a <= b {
-        let (dst, src) = memory.split_at_mut(src_range.start);
-        dst[dst_range.usizes()].copy_from_slice(&src[..len]);
-    } else {
-        let (src, dst) = memory.split_at_mut(dst_range.start);
-        dst[..len].copy_from_slice(&src[src_range.usizes()]);
-    }
+    memory.copy_within(src_range.usizes(), dst_range.start);
 
     Ok(inc_pc(pc)?)
 }
 
 pub(crate) fn memeq(
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     result: &mut Word,
     pc: RegMut<PC>,
     b: Word,
@@ -558,7 +896,7 @@ pub(crate) fn memeq(
 ) -> SimpleResult<()> {
     let range1 = MemoryRange::new(b, d)?;
     let range2 = MemoryRange::new(c, d)?;
-    *result = (memory[range1.usizes()] == memory[range2.usizes()]) as Word;
+    *result = (memory.read(range1.usizes()) == memory.read(range2.usizes())) as Word;
     Ok(inc_pc(pc)?)
 }
 
@@ -652,11 +990,11 @@ pub(crate) fn try_mem_write<A: ToAddr>(
     addr: A,
     data: &[u8],
     owner: OwnershipRegisters,
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
 ) -> SimpleResult<()> {
     let range = MemoryRange::new(addr, data.len())?;
     owner.verify_ownership(&range)?;
-    memory[range.usizes()].copy_from_slice(data);
+    memory.write(range.start, data);
     Ok(())
 }
 
@@ -664,43 +1002,42 @@ pub(crate) fn try_zeroize<A: ToAddr, B: ToAddr>(
     addr: A,
     len: B,
     owner: OwnershipRegisters,
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
 ) -> SimpleResult<()> {
     let range = MemoryRange::new(addr, len)?;
     owner.verify_ownership(&range)?;
-    memory[range.usizes()].fill(0);
+    memory.fill(range.usizes(), 0);
     Ok(())
 }
 
 /// Reads a constant-sized byte array from memory, performing overflow and memory range
 /// checks.
 pub(crate) fn read_bytes<const COUNT: usize>(
-    memory: &[u8; MEM_SIZE],
+    memory: &Memory,
     addr: Word,
 ) -> Result<[u8; COUNT], PanicReason> {
     let range = MemoryRange::new_const::<_, COUNT>(addr)?;
-    Ok(<[u8; COUNT]>::try_from(&memory[range.usizes()])
-        .unwrap_or_else(|_| unreachable!()))
+    Ok(memory.read_bytes(range.start))
 }
 
 /// Writes a constant-sized byte array to memory, performing overflow, memory range and
 /// ownership checks.
 pub(crate) fn write_bytes<const COUNT: usize>(
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     owner: OwnershipRegisters,
     addr: Word,
     bytes: [u8; COUNT],
 ) -> SimpleResult<()> {
     let range = MemoryRange::new_const::<_, COUNT>(addr)?;
     owner.verify_ownership(&range)?;
-    memory[range.usizes()].copy_from_slice(&bytes);
+    memory.write(range.start, &bytes);
     Ok(())
 }
 
 /// Attempt copy from slice to memory, filling zero bytes when exceeding slice boundaries.
 /// Performs overflow and memory range checks, but no ownership checks.
 pub(crate) fn copy_from_slice_zero_fill_noownerchecks<A: ToAddr, B: ToAddr>(
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     src: &[u8],
     dst_addr: A,
     src_offset: usize,
@@ -711,8 +1048,8 @@ pub(crate) fn copy_from_slice_zero_fill_noownerchecks<A: ToAddr, B: ToAddr>(
     let src_end = src_offset.saturating_add(range.len()).min(src.len());
     let data = src.get(src_offset..src_end).unwrap_or_default();
     let (r_data, r_zero) = range.split_at_offset(data.len());
-    memory[r_data.usizes()].copy_from_slice(data);
-    memory[r_zero.usizes()].fill(0);
+    memory.write(r_data.start, data);
+    memory.fill(r_zero.usizes(), 0);
 
     Ok(())
 }
@@ -28,7 +28,7 @@ fn test_malloc(mut hp: Word, sp: Word, a: Word) -> SimpleResult<Word> {
 #[test_case(true, MEM_SIZE as Word - 10, 10 => Ok(()); "Memory range ends at last address")]
 #[test_case(true, 1, VM_MAX_RAM + 1 => Err(PanicOrBug::Panic(PanicReason::MemoryOverflow)); "Memory range size exceeds limit")]
 fn test_memclear(has_ownership: bool, a: Word, b: Word) -> SimpleResult<()> {
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let mut pc = 4;
     let mut owner = OwnershipRegisters {
         sp: 0,
@@ -49,7 +49,7 @@ fn test_memclear(has_ownership: bool, a: Word, b: Word) -> SimpleResult<()> {
     assert_eq!(pc, 8);
     let expected = vec![0u8; b as usize];
     let ab = a.checked_add(b).unwrap();
-    assert_eq!(memory[a as usize..ab as usize], expected[..]);
+    assert_eq!(memory.read(a as usize..ab as usize)[..], expected[..]);
 
     Ok(())
 }
@@ -62,8 +62,8 @@ fn test_memclear(has_ownership: bool, a: Word, b: Word) -> SimpleResult<()> {
 #[test_case(true, 21, 22, 10 => Err(PanicReason::MemoryWriteOverlap.into()); "a <= b < ac")]
 #[test_case(true, 21, 20, 10 => Err(PanicReason::MemoryWriteOverlap.into()); "a < bc <= ac")]
 fn test_memcopy(has_ownership: bool, a: Word, b: Word, c: Word) -> SimpleResult<()> {
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
-    memory[b as usize..b as usize + c as usize].copy_from_slice(&vec![2u8; c as usize]);
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
+    memory.write(b as usize, &vec![2u8; c as usize]);
     let mut pc = 4;
     let mut owner = OwnershipRegisters {
         sp: 0,
@@ -83,7 +83,10 @@ fn test_memcopy(has_ownership: bool, a: Word, b: Word, c: Word) -> SimpleResult<
 
     assert_eq!(pc, 8);
     let expected = vec![2u8; c as usize];
-    assert_eq!(memory[a as usize..a as usize + c as usize], expected[..]);
+    assert_eq!(
+        memory.read(a as usize..a as usize + c as usize)[..],
+        expected[..]
+    );
 
     Ok(())
 }
@@ -97,13 +100,13 @@ fn test_memcopy(has_ownership: bool, a: Word, b: Word, c: Word) -> SimpleResult<
 #[test_case(0, 0, 0 => Ok(()); "smallest input values")]
 #[test_case(0, VM_MAX_RAM/2, VM_MAX_RAM/2 => Ok(()); "maximum range of addressable memory")]
 fn test_memeq(b: Word, c: Word, d: Word) -> SimpleResult<()> {
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let r = (b as usize).min(MEM_SIZE)
         ..((b as usize).min(MEM_SIZE) + (d as usize).min(MEM_SIZE)).min(MEM_SIZE);
-    memory[r].fill(2u8);
+    memory.fill(r, 2u8);
     let r = (c as usize).min(MEM_SIZE)
         ..((c as usize).min(MEM_SIZE) + (d as usize).min(MEM_SIZE)).min(MEM_SIZE);
-    memory[r].fill(2u8);
+    memory.fill(r, 2u8);
     let mut pc = 4;
     let mut result = 0;
 
@@ -162,8 +165,8 @@ fn test_stack_pointer_overflow(
 #[test_case(0, VM_MAX_RAM - 1 => Ok(()); "c eq VM_MAX_RAM - 1")]
 #[test_case(u32::MAX as u64, u32::MAX as u64 => Err(PanicOrBug::Panic(PanicReason::MemoryOverflow)); "b + c overflow")]
 fn test_load_byte(b: Word, c: Word) -> SimpleResult<()> {
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
-    memory[((b + c) as usize).min(MEM_SIZE - 1)] = 2;
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
+    memory.write(((b + c) as usize).min(MEM_SIZE - 1), &[2]);
     let mut pc = 4;
     let mut result = 0;
 
@@ -179,7 +182,7 @@ fn test_load_byte(b: Word, c: Word) -> SimpleResult<()> {
 #[test_case(VM_MAX_RAM, 1 => Err(PanicOrBug::Panic(PanicReason::MemoryOverflow)); "b + 8 * c gteq VM_MAX_RAM")]
 fn test_load_word(b: Word, c: Word) -> SimpleResult<()> {
     // create a mutable memory with size `MEM_SIZE`
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
 
     // calculate start location where 8 bytes of value will be stored based on `b` and `c`
     // values.
@@ -187,7 +190,7 @@ fn test_load_word(b: Word, c: Word) -> SimpleResult<()> {
 
     // write 2u8 to a slice of memory (starting at the 'start' location with a length of
     // 8)
-    memory[start..start + 8].copy_from_slice(&[2u8; 8]);
+    memory.write(start, &[2u8; 8]);
 
     // initialize pc to 4 and result to 0
     let mut pc = 4;
@@ -210,7 +213,7 @@ fn test_load_word(b: Word, c: Word) -> SimpleResult<()> {
 #[test_case(false, 0, 100, VM_MAX_RAM - 1 => Err(PanicOrBug::Panic(PanicReason::MemoryOwnership)); "Memory overflow on stack")]
 #[test_case(true, VM_MAX_RAM, 1, 1 => Err(PanicOrBug::Panic(PanicReason::MemoryOverflow)); "Memory overflow by address range")]
 fn test_store_byte(has_ownership: bool, a: Word, b: Word, c: Word) -> SimpleResult<()> {
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let mut pc = 4;
     let mut owner = OwnershipRegisters {
         sp: 0,
@@ -240,7 +243,7 @@ fn test_store_byte_more(
     #[values(0, 1, 0xff, 0x100)] b: Word,
     #[values(0, 1, 2)] c: Word,
 ) -> SimpleResult<()> {
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let mut pc = 4;
 
     // Full ownership in heap
@@ -273,7 +276,7 @@ fn test_store_byte_more(
 #[test_case(true, 20, 30, VM_MAX_RAM => Err(PanicOrBug::Panic(PanicReason::MemoryOverflow)); "Fails due to memory overflow")]
 #[test_case(false, 20, 30, 40 => Err(PanicOrBug::Panic(PanicReason::MemoryOwnership)); "Fails due to not having ownership of the range")]
 fn test_store_word(has_ownership: bool, a: Word, b: Word, c: Word) -> SimpleResult<()> {
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let mut pc = 4;
     let mut owner = OwnershipRegisters {
         sp: 0,
@@ -293,7 +296,7 @@ fn test_store_word(has_ownership: bool, a: Word, b: Word, c: Word) -> SimpleResu
 
     assert_eq!(pc, 8);
     let start = (a + c * 8) as usize;
-    assert_eq!(memory[start..start + 8], b.to_be_bytes()[..]);
+    assert_eq!(memory.read(start..start + 8)[..], b.to_be_bytes()[..]);
 
     Ok(())
 }
@@ -0,0 +1,171 @@
+use alloc::vec;
+
+use super::*;
+use test_case::test_case;
+
+#[test]
+fn new_memory_is_zeroed_without_allocating() {
+    let memory = Memory::new();
+
+    assert_eq!(memory.allocated_pages(), 0);
+    assert_eq!(memory.read(0..64)[..], [0u8; 64]);
+    assert_eq!(memory.read(MEM_SIZE - 64..MEM_SIZE)[..], [0u8; 64]);
+    assert_eq!(memory[MEM_SIZE - 1], 0);
+}
+
+#[test_case(0, 8 => 1; "start of the first page")]
+#[test_case(MEM_PAGE_SIZE - 8, 8 => 1; "end of the first page")]
+#[test_case(MEM_PAGE_SIZE - 4, 8 => 2; "across a page boundary")]
+#[test_case(MEM_PAGE_SIZE - 4, 2 * MEM_PAGE_SIZE => 3; "spanning a whole page")]
+#[test_case(MEM_SIZE - 8, 8 => 1; "end of the memory")]
+fn write_allocates_only_touched_pages(addr: usize, len: usize) -> usize {
+    let mut memory = Memory::new();
+    let data: Vec<u8> = (0..len).map(|i| (i % 255) as u8 + 1).collect();
+
+    memory.write(addr, &data);
+
+    assert_eq!(memory.read(addr..addr + len)[..], data[..]);
+    assert_eq!(memory[addr.saturating_sub(1)], (addr == 0) as u8);
+    memory.allocated_pages()
+}
+
+#[test]
+fn read_borrows_within_a_single_page() {
+    let mut memory = Memory::new();
+    memory.write(MEM_PAGE_SIZE - 4, &[1; 8]);
+
+    assert!(matches!(memory.read(0..MEM_PAGE_SIZE), Cow::Borrowed(_)));
+    assert!(matches!(
+        memory.read(MEM_PAGE_SIZE - 4..MEM_PAGE_SIZE + 4),
+        Cow::Owned(_)
+    ));
+}
+
+#[test]
+fn clone_is_copy_on_write() {
+    let mut original = Memory::new();
+    original.write(0, &[1; 32]);
+    original.write(MEM_PAGE_SIZE, &[2; 32]);
+
+    let mut clone = original.clone();
+    assert_eq!(original, clone);
+
+    clone.write(8, &[3; 8]);
+
+    assert_eq!(original.read(0..32)[..], [1; 32]);
+    assert_eq!(clone.read(8..16)[..], [3; 8]);
+    assert_eq!(
+        original.read(MEM_PAGE_SIZE..MEM_PAGE_SIZE + 32)[..],
+        [2; 32]
+    );
+    assert_eq!(clone.read(MEM_PAGE_SIZE..MEM_PAGE_SIZE + 32)[..], [2; 32]);
+    assert_eq!(original.diff_ranges(&clone), vec![8..16]);
+}
+
+#[test]
+fn zero_fill_releases_whole_pages() {
+    let mut memory = Memory::new();
+    memory.write(0, &[1; 3 * MEM_PAGE_SIZE]);
+    assert_eq!(memory.allocated_pages(), 3);
+
+    memory.fill(8..2 * MEM_PAGE_SIZE, 0);
+
+    assert_eq!(memory.allocated_pages(), 2);
+    assert_eq!(memory.read(0..8)[..], [1; 8]);
+    assert_eq!(memory[8], 0);
+    assert_eq!(memory[2 * MEM_PAGE_SIZE], 1);
+
+    memory.fill(4 * MEM_PAGE_SIZE..5 * MEM_PAGE_SIZE + 1, 0);
+    assert_eq!(memory.allocated_pages(), 2);
+}
+
+#[test]
+fn allocated_zeroes_equal_unallocated_pages() {
+    let mut a = Memory::new();
+    let b = Memory::new();
+
+    a.write(100, &[0; 32]);
+
+    assert_eq!(a.allocated_pages(), 1);
+    assert_eq!(a, b);
+    assert!(a.diff_ranges(&b).is_empty());
+}
+
+#[test]
+fn diff_ranges_merge_across_page_boundaries() {
+    let a = Memory::new();
+    let mut b = Memory::new();
+
+    b.write(MEM_PAGE_SIZE - 2, &[1; 4]);
+    b.write(MEM_PAGE_SIZE + 8, &[1; 2]);
+    b.write(MEM_SIZE - 1, &[1]);
+
+    assert_eq!(
+        a.diff_ranges(&b),
+        vec![
+            MEM_PAGE_SIZE - 2..MEM_PAGE_SIZE + 2,
+            MEM_PAGE_SIZE + 8..MEM_PAGE_SIZE + 10,
+            MEM_SIZE - 1..MEM_SIZE,
+        ]
+    );
+}
+
+#[test]
+fn copy_within_handles_overlapping_ranges() {
+    let mut memory = Memory::new();
+    memory.write(MEM_PAGE_SIZE - 2, &[1, 2, 3, 4]);
+
+    memory.copy_within(MEM_PAGE_SIZE - 2..MEM_PAGE_SIZE + 2, MEM_PAGE_SIZE);
+
+    assert_eq!(
+        memory.read(MEM_PAGE_SIZE - 2..MEM_PAGE_SIZE + 4)[..],
+        [1, 2, 1, 2, 3, 4]
+    );
+}
+
+#[test]
+fn to_vec_matches_try_from() {
+    let mut memory = Memory::new();
+    memory.write(MEM_PAGE_SIZE - 2, &[7; 4]);
+
+    let bytes = memory.to_vec();
+    assert_eq!(bytes.len(), MEM_SIZE);
+
+    let restored = Memory::try_from(bytes).expect("Buffer has the memory size");
+    assert_eq!(restored, memory);
+    assert_eq!(restored.allocated_pages(), 2);
+    assert!(Memory::try_from(vec![0u8; 10]).is_err());
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn read_out_of_bounds_panics() {
+    let _ = Memory::new().read(MEM_SIZE - 1..MEM_SIZE + 1);
+}
+
+#[test_case(0, MEM_PAGE_SIZE + 3; "forwards")]
+#[test_case(MEM_PAGE_SIZE + 3, 0; "backwards")]
+#[test_case(5, 7; "overlapping, destination after the source")]
+#[test_case(7, 5; "overlapping, destination before the source")]
+fn copy_within_matches_a_buffered_copy(src: usize, dst: usize) {
+    let len = 3 * MEM_PAGE_SIZE;
+    let mut memory = Memory::new();
+    let data: Vec<u8> = (0..len).map(|i| (i % 251) as u8 + 1).collect();
+    memory.write(src, &data);
+
+    let mut expected = memory.to_vec();
+    expected.copy_within(src..src + len, dst);
+
+    memory.copy_within(src..src + len, dst);
+
+    assert_eq!(memory.to_vec(), expected);
+}
+
+#[test]
+fn copy_within_from_unallocated_pages_does_not_allocate() {
+    let mut memory = Memory::new();
+
+    memory.copy_within(0..2 * MEM_PAGE_SIZE, 4 * MEM_PAGE_SIZE);
+
+    assert_eq!(memory.allocated_pages(), 0);
+}
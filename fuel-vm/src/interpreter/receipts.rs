@@ -32,6 +32,14 @@ impl ReceiptsCtx {
         self.receipts.len()
     }
 
+    /// Remove the receipts after the first `len` ones.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.receipts.len() {
+            self.receipts.truncate(len);
+            self.recalculate_root();
+        }
+    }
+
     pub fn root(&self) -> Bytes32 {
         self.receipts_tree.root().into()
     }
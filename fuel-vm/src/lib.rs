@@ -15,6 +15,7 @@ extern crate libm as _; // Not needed with stdlib
 
 pub mod arith;
 pub mod backtrace;
+pub mod block_executor;
 pub mod call;
 pub mod checked_transaction;
 pub mod constraints;
@@ -22,8 +23,10 @@ pub mod consts;
 pub mod context;
 pub mod crypto;
 pub mod error;
+pub mod hook;
 pub mod interpreter;
 pub mod memory_client;
+pub mod pool;
 pub mod predicate;
 pub mod state;
 pub mod storage;
@@ -131,9 +134,11 @@ pub mod prelude {
         interpreter::{
             ExecutableTransaction,
             Interpreter,
+            Memory,
             MemoryRange,
         },
         memory_client::MemoryClient,
+        pool::InterpreterPool,
         predicate::RuntimePredicate,
         state::{
             Debugger,
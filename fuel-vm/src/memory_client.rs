@@ -3,18 +3,32 @@
 use crate::{
     backtrace::Backtrace,
     checked_transaction::Checked,
+    error::InterpreterError,
+    hook::CallTree,
     state::StateTransitionRef,
     storage::MemoryStorage,
-    transactor::Transactor,
+    transactor::{
+        EstimateScriptGasError,
+        ScriptGasEstimate,
+        Simulation,
+        SimulationOptions,
+        Transactor,
+    },
 };
 
 use crate::interpreter::InterpreterParams;
+use core::convert::Infallible;
 use fuel_tx::{
+    ConsensusParameters,
     Create,
     GasCosts,
     Receipt,
     Script,
 };
+use fuel_types::{
+    BlockHeight,
+    Word,
+};
 
 #[derive(Default, Debug)]
 /// Client implementation with in-memory storage backend.
@@ -53,6 +67,19 @@ impl MemoryClient {
         self.transactor.backtrace()
     }
 
+    /// Record the [`CallTree`] of the executed transactions.
+    pub fn with_call_tracer(&mut self) -> &mut Self {
+        self.transactor.with_call_tracer();
+        self
+    }
+
+    /// Call tree of the last executed transaction.
+    ///
+    /// Will be `None` if call tracing wasn't enabled with [`Self::with_call_tracer`].
+    pub fn call_tree(&self) -> Option<&CallTree> {
+        self.transactor.call_tree()
+    }
+
     /// If a transaction was successfully executed, returns the produced
     /// receipts; return `None` otherwise.
     pub fn receipts(&self) -> Option<&[Receipt]> {
@@ -94,6 +121,33 @@ impl MemoryClient {
         self.transactor.receipts().unwrap_or_default()
     }
 
+    /// Estimate the smallest gas limit the script transaction succeeds with, without
+    /// modifying the storage.
+    ///
+    /// See [`Transactor::estimate_script_gas`].
+    pub fn estimate_script_gas(
+        &self,
+        tx: &Checked<Script>,
+        max_gas_per_tx: Word,
+    ) -> Result<ScriptGasEstimate, EstimateScriptGasError<Infallible>> {
+        self.transactor.estimate_script_gas(tx, max_gas_per_tx)
+    }
+
+    /// Execute a script transaction without requiring it to pass the signature checks,
+    /// and without modifying the storage.
+    ///
+    /// See [`Transactor::simulate`].
+    pub fn simulate(
+        &self,
+        tx: Script,
+        block_height: BlockHeight,
+        consensus_params: &ConsensusParameters,
+        options: SimulationOptions,
+    ) -> Result<Simulation, InterpreterError<Infallible>> {
+        self.transactor
+            .simulate(tx, block_height, consensus_params, options)
+    }
+
     /// Persist the changes caused by [`Self::transact`].
     pub fn persist(&mut self) {
         self.as_mut().persist();
@@ -0,0 +1,105 @@
+//! Pool of reusable interpreters.
+
+use alloc::vec::Vec;
+
+use crate::interpreter::{
+    ExecutableTransaction,
+    Interpreter,
+    InterpreterParams,
+};
+
+/// Pool of recycled [`Interpreter`] instances.
+///
+/// Building an interpreter allocates its memory page table, receipts and call frames.
+/// The pool keeps released interpreters around and resets them with
+/// [`Interpreter::reset_for_tx`], so consecutive transactions reuse these buffers
+/// while observing the state of a fresh interpreter. The memory pages themselves are
+/// released on reset.
+///
+/// Released interpreters are stripped of their execution hook, profiler receiver and
+/// history, so they must be configured again after being acquired.
+///
+/// The pool is not bound to a storage type: the storage is provided on
+/// [`Self::acquire`] and handed back on [`Self::release`].
+#[derive(Debug, Clone)]
+pub struct InterpreterPool<Tx> {
+    idle: Vec<Interpreter<(), Tx>>,
+    max_idle: usize,
+}
+
+impl<Tx> InterpreterPool<Tx>
+where
+    Tx: ExecutableTransaction,
+{
+    /// Default maximum number of idle interpreters kept by the pool.
+    pub const DEFAULT_MAX_IDLE: usize = 16;
+
+    /// Create an empty pool that keeps up to [`Self::DEFAULT_MAX_IDLE`] idle
+    /// interpreters.
+    pub fn new() -> Self {
+        Self::with_max_idle(Self::DEFAULT_MAX_IDLE)
+    }
+
+    /// Create an empty pool that keeps up to `max_idle` idle interpreters.
+    pub fn with_max_idle(max_idle: usize) -> Self {
+        Self {
+            idle: Vec::new(),
+            max_idle,
+        }
+    }
+
+    /// Number of idle interpreters ready to be acquired.
+    pub fn idle(&self) -> usize {
+        self.idle.len()
+    }
+
+    /// Maximum number of idle interpreters kept by the pool.
+    pub const fn max_idle(&self) -> usize {
+        self.max_idle
+    }
+
+    /// Take an interpreter out of the pool, or build a new one if the pool is empty.
+    ///
+    /// The returned interpreter behaves like one built by
+    /// [`Interpreter::with_storage`] with the same arguments.
+    pub fn acquire<S>(
+        &mut self,
+        storage: S,
+        interpreter_params: InterpreterParams,
+    ) -> Interpreter<S, Tx> {
+        match self.idle.pop() {
+            Some(idle) => {
+                let (mut interpreter, _) = idle.replace_storage(storage);
+                interpreter.set_interpreter_params(interpreter_params);
+                interpreter
+            }
+            None => Interpreter::with_storage(storage, interpreter_params),
+        }
+    }
+
+    /// Return an interpreter to the pool, handing back its storage.
+    ///
+    /// The interpreter is reset before being stored, so the memory it dirtied is
+    /// released right away. It is dropped if the pool already holds
+    /// [`Self::max_idle`] interpreters.
+    pub fn release<S>(&mut self, interpreter: Interpreter<S, Tx>) -> S {
+        let (mut interpreter, storage) = interpreter.replace_storage(());
+
+        if self.idle.len() < self.max_idle {
+            interpreter.reset_for_tx();
+            interpreter.detach_observers();
+            self.idle.push(interpreter);
+        }
+
+        storage
+    }
+}
+
+impl<Tx> Default for InterpreterPool<Tx>
+where
+    Tx: ExecutableTransaction,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
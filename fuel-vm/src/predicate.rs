@@ -164,10 +164,10 @@ mod tests {
                 .collect();
 
             let program = runtime.program();
-            let program = &interpreter.memory()[program.usizes()];
+            let program = interpreter.memory().read(program.usizes());
 
             // assert the program in the vm memory is the same of the input
-            assert_eq!(program, &padded_predicate);
+            assert_eq!(&program[..], &padded_predicate[..]);
         }
     }
 
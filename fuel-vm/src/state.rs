@@ -8,16 +8,28 @@ use fuel_types::{
     Word,
 };
 
+mod changes;
+
 mod debug;
 
 mod debugger;
 
+pub use changes::{
+    DeployedContract,
+    StateChanges,
+    ValueChange,
+};
+
 pub use debug::{
+    BreakCondition,
     Breakpoint,
+    Comparison,
     DebugEval,
+    Watchpoint,
 };
 
 pub use debugger::Debugger;
+pub(crate) use debugger::Step;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 /// Resulting state of an instruction set execution.
@@ -75,10 +87,7 @@ pub enum ProgramState {
 
 impl PartialEq<Breakpoint> for ProgramState {
     fn eq(&self, other: &Breakpoint) -> bool {
-        match self.debug_ref() {
-            Some(&DebugEval::Breakpoint(b)) => &b == other,
-            _ => false,
-        }
+        self.debug_ref().and_then(DebugEval::breakpoint) == Some(other)
     }
 }
 
@@ -105,15 +114,22 @@ pub struct StateTransition<Tx> {
     state: ProgramState,
     tx: Tx,
     receipts: Vec<Receipt>,
+    state_changes: StateChanges,
 }
 
 impl<Tx> StateTransition<Tx> {
     /// Create a new state transition representation.
-    pub const fn new(state: ProgramState, tx: Tx, receipts: Vec<Receipt>) -> Self {
+    pub const fn new(
+        state: ProgramState,
+        tx: Tx,
+        receipts: Vec<Receipt>,
+        state_changes: StateChanges,
+    ) -> Self {
         Self {
             state,
             tx,
             receipts,
+            state_changes,
         }
     }
 
@@ -139,6 +155,11 @@ impl<Tx> StateTransition<Tx> {
         self.receipts.as_slice()
     }
 
+    /// Changes made to the storage by the execution.
+    pub const fn state_changes(&self) -> &StateChanges {
+        &self.state_changes
+    }
+
     /// Convert this instance into its internal attributes.
     pub fn into_inner(self) -> (ProgramState, Tx, Vec<Receipt>) {
         (self.state, self.tx, self.receipts)
@@ -158,15 +179,22 @@ pub struct StateTransitionRef<'a, Tx> {
     state: ProgramState,
     tx: &'a Tx,
     receipts: &'a [Receipt],
+    state_changes: &'a StateChanges,
 }
 
 impl<'a, Tx> StateTransitionRef<'a, Tx> {
     /// Create a new by reference state transition representation.
-    pub const fn new(state: ProgramState, tx: &'a Tx, receipts: &'a [Receipt]) -> Self {
+    pub const fn new(
+        state: ProgramState,
+        tx: &'a Tx,
+        receipts: &'a [Receipt],
+        state_changes: &'a StateChanges,
+    ) -> Self {
         Self {
             state,
             tx,
             receipts,
+            state_changes,
         }
     }
 
@@ -185,6 +213,11 @@ impl<'a, Tx> StateTransitionRef<'a, Tx> {
         self.receipts
     }
 
+    /// Changes made to the storage by the execution.
+    pub const fn state_changes(&self) -> &StateChanges {
+        self.state_changes
+    }
+
     /// Flag whether the client should revert after execution.
     pub fn should_revert(&self) -> bool {
         self.receipts
@@ -199,6 +232,7 @@ impl<'a, Tx> From<&'a StateTransition<Tx>> for StateTransitionRef<'a, Tx> {
             state: *t.state(),
             tx: t.tx(),
             receipts: t.receipts(),
+            state_changes: t.state_changes(),
         }
     }
 }
@@ -209,6 +243,7 @@ impl<'a, Tx: Clone> From<StateTransitionRef<'a, Tx>> for StateTransition<Tx> {
             state: *t.state(),
             tx: t.tx().clone(),
             receipts: t.receipts().to_vec(),
+            state_changes: t.state_changes().clone(),
         }
     }
 }
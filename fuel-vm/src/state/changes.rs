@@ -0,0 +1,282 @@
+use alloc::collections::{
+    btree_map::Entry,
+    BTreeMap,
+};
+
+use fuel_storage::StorageMutate;
+use fuel_tx::Contract;
+use fuel_types::{
+    AssetId,
+    Bytes32,
+    ContractId,
+    Salt,
+    Word,
+};
+
+use crate::storage::{
+    ContractsAssets,
+    ContractsInfo,
+    ContractsRawCode,
+    ContractsState,
+    InterpreterStorage,
+};
+
+/// Changes made to the storage by the execution of a transaction.
+///
+/// The interpreter rolls back the storage of reverted and panicked scripts to the
+/// checkpoint taken before running them, and discards their changes with it. The
+/// changes of a transaction can still be undone with [`Self::revert`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StateChanges {
+    /// Storage slots written, by contract and key. A `None` value is an unset slot.
+    pub contract_state:
+        BTreeMap<ContractId, BTreeMap<Bytes32, ValueChange<Option<Bytes32>>>>,
+    /// Asset balances written, by contract and asset. A `None` value is an unset
+    /// balance, which differs from a zero balance in the balance root.
+    pub balances: BTreeMap<ContractId, BTreeMap<AssetId, ValueChange<Option<Word>>>>,
+    /// Deployed contracts, by contract. Their initial storage slots are part of
+    /// [`Self::contract_state`].
+    pub deployed_contracts: BTreeMap<ContractId, DeployedContract>,
+    /// Amounts minted by the contracts, by asset.
+    pub minted: BTreeMap<AssetId, Word>,
+    /// Amounts burned by the contracts, by asset.
+    pub burned: BTreeMap<AssetId, Word>,
+}
+
+/// Value of a storage entry before and after the execution.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValueChange<T> {
+    /// The value before the execution.
+    pub old: T,
+    /// The value after the execution.
+    pub new: T,
+}
+
+/// A contract deployed by a `Create` transaction.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeployedContract {
+    /// Bytecode of the contract.
+    pub bytecode: Contract,
+    /// Salt used to compute the contract id.
+    pub salt: Salt,
+    /// Root of the bytecode.
+    pub root: Bytes32,
+}
+
+impl StateChanges {
+    /// Returns true if no change was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.contract_state.is_empty()
+            && self.balances.is_empty()
+            && self.deployed_contracts.is_empty()
+            && self.minted.is_empty()
+            && self.burned.is_empty()
+    }
+
+    /// Remove all the recorded changes.
+    pub fn clear(&mut self) {
+        self.contract_state.clear();
+        self.balances.clear();
+        self.deployed_contracts.clear();
+        self.minted.clear();
+        self.burned.clear();
+    }
+
+    /// Replay the changes on `storage`, setting the new value of every entry.
+    pub fn apply<S>(&self, storage: &mut S) -> Result<(), S::DataError>
+    where
+        S: InterpreterStorage,
+    {
+        for (id, contract) in &self.deployed_contracts {
+            storage.storage_contract_insert(id, &contract.bytecode)?;
+            storage.storage_contract_root_insert(id, &contract.salt, &contract.root)?;
+        }
+
+        for (contract, slots) in &self.contract_state {
+            for (key, change) in slots {
+                set_state(storage, contract, key, change.new.as_ref())?;
+            }
+        }
+
+        for (contract, balances) in &self.balances {
+            for (asset_id, change) in balances {
+                set_balance(storage, contract, asset_id, change.new)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Undo the changes on `storage`, setting the old value of every entry and
+    /// removing the deployed contracts.
+    pub fn revert<S>(&self, storage: &mut S) -> Result<(), S::DataError>
+    where
+        S: InterpreterStorage,
+    {
+        for id in self.deployed_contracts.keys() {
+            StorageMutate::<ContractsRawCode>::remove(storage, id)?;
+            StorageMutate::<ContractsInfo>::remove(storage, id)?;
+        }
+
+        for (contract, slots) in &self.contract_state {
+            for (key, change) in slots {
+                set_state(storage, contract, key, change.old.as_ref())?;
+            }
+        }
+
+        for (contract, balances) in &self.balances {
+            for (asset_id, change) in balances {
+                set_balance(storage, contract, asset_id, change.old)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Record the `changes` made after the ones already recorded.
+    pub(crate) fn extend(&mut self, changes: StateChanges) {
+        for (contract, slots) in changes.contract_state {
+            let entries = self.contract_state.entry(contract).or_default();
+            for (key, change) in slots {
+                Self::record(entries, key, change.old, change.new);
+            }
+        }
+
+        for (contract, balances) in changes.balances {
+            let entries = self.balances.entry(contract).or_default();
+            for (asset_id, change) in balances {
+                Self::record(entries, asset_id, change.old, change.new);
+            }
+        }
+
+        self.deployed_contracts.extend(changes.deployed_contracts);
+
+        for (asset_id, amount) in changes.minted {
+            self.record_mint(&asset_id, amount);
+        }
+
+        for (asset_id, amount) in changes.burned {
+            self.record_burn(&asset_id, amount);
+        }
+    }
+
+    /// Record a write to a storage slot.
+    pub(crate) fn record_state(
+        &mut self,
+        contract: &ContractId,
+        key: &Bytes32,
+        old: Option<Bytes32>,
+        new: Option<Bytes32>,
+    ) {
+        Self::record(
+            self.contract_state.entry(*contract).or_default(),
+            *key,
+            old,
+            new,
+        )
+    }
+
+    /// Record a write to an asset balance.
+    pub(crate) fn record_balance(
+        &mut self,
+        contract: &ContractId,
+        asset_id: &AssetId,
+        old: Option<Word>,
+        new: Option<Word>,
+    ) {
+        Self::record(
+            self.balances.entry(*contract).or_default(),
+            *asset_id,
+            old,
+            new,
+        )
+    }
+
+    /// Record the bytecode of a deployed contract.
+    pub(crate) fn record_bytecode(&mut self, id: &ContractId, bytecode: Contract) {
+        self.deployed_contracts.entry(*id).or_default().bytecode = bytecode;
+    }
+
+    /// Record the salt and root of a deployed contract.
+    pub(crate) fn record_contract_info(
+        &mut self,
+        id: &ContractId,
+        salt: &Salt,
+        root: &Bytes32,
+    ) {
+        let contract = self.deployed_contracts.entry(*id).or_default();
+        contract.salt = *salt;
+        contract.root = *root;
+    }
+
+    /// Record a minted amount.
+    pub(crate) fn record_mint(&mut self, asset_id: &AssetId, amount: Word) {
+        let minted = self.minted.entry(*asset_id).or_default();
+        *minted = minted.saturating_add(amount);
+    }
+
+    /// Record a burned amount.
+    pub(crate) fn record_burn(&mut self, asset_id: &AssetId, amount: Word) {
+        let burned = self.burned.entry(*asset_id).or_default();
+        *burned = burned.saturating_add(amount);
+    }
+
+    /// Keep the first old value and the last new value of an entry.
+    fn record<K: Ord, T>(
+        entries: &mut BTreeMap<K, ValueChange<T>>,
+        key: K,
+        old: T,
+        new: T,
+    ) {
+        match entries.entry(key) {
+            Entry::Occupied(mut entry) => entry.get_mut().new = new,
+            Entry::Vacant(entry) => {
+                entry.insert(ValueChange { old, new });
+            }
+        }
+    }
+}
+
+/// Insert the value of a storage slot, or remove the slot if there is no value.
+fn set_state<S>(
+    storage: &mut S,
+    contract: &ContractId,
+    key: &Bytes32,
+    value: Option<&Bytes32>,
+) -> Result<(), S::DataError>
+where
+    S: InterpreterStorage,
+{
+    match value {
+        Some(value) => storage.merkle_contract_state_insert(contract, key, value)?,
+        None => {
+            StorageMutate::<ContractsState>::remove(storage, &(contract, key).into())?
+        }
+    };
+    Ok(())
+}
+
+/// Insert an asset balance, or remove it if there is no value.
+fn set_balance<S>(
+    storage: &mut S,
+    contract: &ContractId,
+    asset_id: &AssetId,
+    value: Option<Word>,
+) -> Result<(), S::DataError>
+where
+    S: InterpreterStorage,
+{
+    match value {
+        Some(value) => {
+            storage.merkle_contract_asset_id_balance_insert(contract, asset_id, value)?
+        }
+        None => StorageMutate::<ContractsAssets>::remove(
+            storage,
+            &(contract, asset_id).into(),
+        )?,
+    };
+    Ok(())
+}
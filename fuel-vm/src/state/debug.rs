@@ -1,6 +1,10 @@
+use alloc::vec::Vec;
+
 use fuel_asm::Instruction;
 use fuel_types::{
+    Bytes32,
     ContractId,
+    RegisterId,
     Word,
 };
 
@@ -54,6 +58,85 @@ impl Breakpoint {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Comparison applied by a [`BreakCondition`] on a register value.
+pub enum Comparison {
+    /// Equal to the value.
+    Eq,
+    /// Not equal to the value.
+    Ne,
+    /// Less than the value.
+    Lt,
+    /// Less than or equal to the value.
+    Le,
+    /// Greater than the value.
+    Gt,
+    /// Greater than or equal to the value.
+    Ge,
+}
+
+impl Comparison {
+    /// Compare `lhs` against `rhs`.
+    pub fn eval(&self, lhs: Word, rhs: Word) -> bool {
+        match self {
+            Self::Eq => lhs == rhs,
+            Self::Ne => lhs != rhs,
+            Self::Lt => lhs < rhs,
+            Self::Le => lhs <= rhs,
+            Self::Gt => lhs > rhs,
+            Self::Ge => lhs >= rhs,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Condition that must hold for a conditional breakpoint to break the program.
+///
+/// Conditions are evaluated before the instruction at the breakpoint is executed.
+pub enum BreakCondition {
+    /// The register compares to the value.
+    Register {
+        /// Register to compare.
+        register: RegisterId,
+        /// Comparison applied to the register value.
+        comparison: Comparison,
+        /// Value compared against.
+        value: Word,
+    },
+    /// The memory starting at `address` holds `bytes`.
+    Memory {
+        /// Start of the compared memory.
+        address: Word,
+        /// Expected content of the memory.
+        bytes: Vec<u8>,
+    },
+    /// The global gas `$ggas` is below the threshold.
+    GasBelow(Word),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Location watched by the debugger. The program breaks after any instruction that
+/// changes it.
+pub enum Watchpoint {
+    /// The memory range `address..address + len`.
+    Memory {
+        /// Start of the watched range.
+        address: Word,
+        /// Length of the watched range.
+        len: Word,
+    },
+    /// A storage slot of a contract.
+    Storage {
+        /// Contract owning the slot.
+        contract: ContractId,
+        /// Key of the slot.
+        key: Bytes32,
+    },
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// State evaluation of the interpreter that will describe if a program should
@@ -62,6 +145,14 @@ pub enum DebugEval {
     /// This evaluation should break the program in the location described in
     /// `Breakpoint`.
     Breakpoint(Breakpoint),
+    /// The last executed instruction changed a watched location. The program is
+    /// suspended before the instruction described in `breakpoint`.
+    Watchpoint {
+        /// Location of the next instruction.
+        breakpoint: Breakpoint,
+        /// The watchpoint that triggered.
+        watchpoint: Watchpoint,
+    },
     /// This evaluation should not break the program.
     Continue,
 }
@@ -89,6 +180,15 @@ impl DebugEval {
     pub const fn breakpoint(&self) -> Option<&Breakpoint> {
         match self {
             Self::Breakpoint(b) => Some(b),
+            Self::Watchpoint { breakpoint, .. } => Some(breakpoint),
+            Self::Continue => None,
+        }
+    }
+
+    /// Return the watchpoint that broke the program, if any.
+    pub const fn watchpoint(&self) -> Option<&Watchpoint> {
+        match self {
+            Self::Watchpoint { watchpoint, .. } => Some(watchpoint),
             _ => None,
         }
     }
@@ -1,9 +1,17 @@
-use crate::state::{
-    Breakpoint,
-    DebugEval,
-    ProgramState,
+use alloc::vec::Vec;
+
+use crate::{
+    consts::MEM_SIZE,
+    state::{
+        BreakCondition,
+        Breakpoint,
+        DebugEval,
+        ProgramState,
+        Watchpoint,
+    },
 };
 
+use fuel_asm::PanicReason;
 use fuel_types::{
     ContractId,
     Word,
@@ -22,9 +30,40 @@ pub struct Debugger {
     /// Single-stepping mode triggers a breakpoint after each instruction
     single_stepping: bool,
     breakpoints: HashMap<ContractId, HashSet<Word>>,
+    conditions: HashMap<Breakpoint, BreakCondition>,
+    watchpoints: Vec<Watchpoint>,
+    step: Option<Step>,
     last_state: Option<ProgramState>,
 }
 
+/// Pending stepping request, consumed when the program breaks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Step {
+    /// Break on the next instruction.
+    Into,
+    /// Break on the next instruction executed with at most `depth` call frames.
+    Over {
+        /// Call depth when the step was requested.
+        depth: usize,
+    },
+    /// Break on the next instruction executed with less than `depth` call frames.
+    Out {
+        /// Call depth when the step was requested.
+        depth: usize,
+    },
+}
+
+impl Step {
+    /// Returns `true` if the step is completed at the given call depth.
+    pub(crate) const fn is_reached(&self, depth: usize) -> bool {
+        match *self {
+            Self::Into => true,
+            Self::Over { depth: from } => depth <= from,
+            Self::Out { depth: from } => depth < from,
+        }
+    }
+}
+
 impl Debugger {
     /// Returns `true` if the `Debugger` is active and used.
     pub const fn is_active(&self) -> bool {
@@ -61,16 +100,110 @@ impl Debugger {
             });
     }
 
+    /// Set a new breakpoint in the provided location, that breaks the program only
+    /// if the condition holds.
+    pub fn set_conditional_breakpoint(
+        &mut self,
+        breakpoint: Breakpoint,
+        condition: BreakCondition,
+    ) {
+        self.set_breakpoint(breakpoint);
+        self.conditions.insert(breakpoint, condition);
+    }
+
     /// Remove a breakpoint, if existent.
     pub fn remove_breakpoint(&mut self, breakpoint: &Breakpoint) {
         self.is_active = true;
+        self.conditions.remove(breakpoint);
         self.breakpoints
             .get_mut(breakpoint.contract())
             .map(|set| set.remove(&breakpoint.pc()));
     }
 
+    /// Condition of a breakpoint; `None` if the breakpoint is unconditional or
+    /// doesn't exist.
+    pub fn breakpoint_condition(
+        &self,
+        breakpoint: &Breakpoint,
+    ) -> Option<&BreakCondition> {
+        self.conditions.get(breakpoint)
+    }
+
+    /// Look up a breakpoint at the provided location, returning its optional
+    /// condition.
+    pub(crate) fn breakpoint_at(
+        &self,
+        contract: ContractId,
+        pc: Word,
+    ) -> Option<Option<&BreakCondition>> {
+        self.breakpoints
+            .get(&contract)
+            .filter(|set| set.contains(&pc))
+            .map(|_| self.breakpoint_condition(&Breakpoint::raw(contract, pc)))
+    }
+
+    /// Watch a location, breaking the program after any instruction that changes it.
+    ///
+    /// Memory watchpoints must be non-empty and in the bounds of the VM memory.
+    pub fn set_watchpoint(&mut self, watchpoint: Watchpoint) -> Result<(), PanicReason> {
+        if let Watchpoint::Memory { address, len } = watchpoint {
+            let end = address
+                .checked_add(len)
+                .ok_or(PanicReason::MemoryOverflow)?;
+
+            if len == 0 || end > MEM_SIZE as Word {
+                return Err(PanicReason::MemoryOverflow)
+            }
+        }
+
+        self.is_active = true;
+        if !self.watchpoints.contains(&watchpoint) {
+            self.watchpoints.push(watchpoint);
+        }
+
+        Ok(())
+    }
+
+    /// Remove a watchpoint, if existent.
+    pub fn remove_watchpoint(&mut self, watchpoint: &Watchpoint) {
+        self.watchpoints.retain(|w| w != watchpoint);
+    }
+
+    /// Watched locations.
+    pub fn watchpoints(&self) -> &[Watchpoint] {
+        &self.watchpoints
+    }
+
+    /// Request a step, replacing any pending one.
+    pub(crate) fn set_step(&mut self, step: Step) {
+        self.is_active = true;
+        self.step = Some(step);
+    }
+
+    /// Pending step, if any.
+    pub(crate) const fn step(&self) -> Option<Step> {
+        self.step
+    }
+
+    /// Clear the pending step.
+    pub(crate) fn clear_step(&mut self) {
+        self.step = None;
+    }
+
+    /// Take the last known state of the VM.
+    pub(crate) fn take_last_state(&mut self) -> Option<ProgramState> {
+        self.last_state.take()
+    }
+
     /// Evaluate the current state of the interpreter whether or not a
     /// breakpoint was reached.
+    ///
+    /// Only the single-stepping mode and the unconditional breakpoints are
+    /// considered: the break conditions, watchpoints and steps depend on the
+    /// registers, memory and call frames, and are evaluated by the interpreter on
+    /// each instruction.
+    #[deprecated = "Ignores break conditions, watchpoints and steps; the interpreter \
+                    evaluates the debugger state on each instruction"]
     pub fn eval_state(&mut self, contract: Option<&ContractId>, pc: Word) -> DebugEval {
         // Default contract address maps to unset contract target
         let contract = contract.copied().unwrap_or_default();
@@ -10,16 +10,57 @@ use fuel_types::{
     Word,
 };
 
+mod access_list;
+mod async_storage;
+mod block_header;
+mod changes;
+#[cfg(feature = "file-storage")]
+mod file;
+mod fork;
 mod interpreter;
 mod memory;
 pub(crate) mod predicate;
+mod snapshot;
 
+pub use access_list::{
+    AccessList,
+    AccessRecorder,
+};
+pub use async_storage::{
+    AsyncInterpreterStorage,
+    AsyncStorage,
+    AsyncStorageError,
+    AsyncStorageInspect,
+};
+pub use block_header::{
+    BlockHeader,
+    BlockHeaderProvider,
+    MemoryBlockHeaders,
+};
+pub(crate) use changes::ChangesRecorder;
+#[cfg(feature = "file-storage")]
+pub use file::FileStorage;
+pub use fork::{
+    ForkProvider,
+    ForkedStorage,
+    ForkedStorageError,
+};
 pub use interpreter::{
     ContractsAssetsStorage,
     InterpreterStorage,
+    StorageCheckpoint,
 };
+#[cfg(feature = "std")]
+pub(crate) use memory::add_one;
 pub use memory::MemoryStorage;
 pub use predicate::PredicateStorage;
+pub use snapshot::{
+    import_state,
+    ContractBalance,
+    ContractSnapshot,
+    SnapshotError,
+    StorageSnapshot,
+};
 
 /// The storage table for contract's raw byte code.
 pub struct ContractsRawCode;
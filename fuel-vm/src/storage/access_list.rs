@@ -0,0 +1,433 @@
+//! Recording of the storage entries accessed by a transaction.
+
+use alloc::{
+    borrow::Cow,
+    collections::{
+        BTreeMap,
+        BTreeSet,
+    },
+    vec::Vec,
+};
+use core::cell::RefCell;
+
+use fuel_storage::{
+    Mappable,
+    MerkleRoot,
+    MerkleRootStorage,
+    StorageInspect,
+    StorageMutate,
+    StorageRead,
+    StorageSize,
+};
+use fuel_types::{
+    AssetId,
+    BlockHeight,
+    Bytes32,
+    ContractId,
+    Word,
+};
+
+use super::{
+    memory::add_one,
+    ContractsAssetKey,
+    ContractsAssets,
+    ContractsAssetsStorage,
+    ContractsInfo,
+    ContractsRawCode,
+    ContractsState,
+    ContractsStateKey,
+    InterpreterStorage,
+    StorageCheckpoint,
+};
+
+/// Storage entries accessed by the execution of transactions.
+///
+/// Every contract with an accessed storage slot or balance is part of
+/// [`Self::contracts`] as well.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccessList {
+    /// Contracts whose code, info, state or balances were accessed.
+    pub contracts: BTreeSet<ContractId>,
+    /// Storage slots accessed, by contract.
+    pub storage_slots: BTreeMap<ContractId, BTreeSet<Bytes32>>,
+    /// Asset balances accessed, by contract.
+    pub assets: BTreeMap<ContractId, BTreeSet<AssetId>>,
+}
+
+impl AccessList {
+    /// Returns true if no access was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.contracts.is_empty()
+    }
+
+    /// Add an access to a contract.
+    pub fn insert_contract(&mut self, contract: &ContractId) {
+        self.contracts.insert(*contract);
+    }
+
+    /// Add an access to a storage slot of a contract.
+    pub fn insert_storage_slot(&mut self, contract: &ContractId, key: &Bytes32) {
+        self.insert_contract(contract);
+        self.storage_slots
+            .entry(*contract)
+            .or_default()
+            .insert(*key);
+    }
+
+    /// Add an access to an asset balance of a contract.
+    pub fn insert_asset(&mut self, contract: &ContractId, asset_id: &AssetId) {
+        self.insert_contract(contract);
+        self.assets.entry(*contract).or_default().insert(*asset_id);
+    }
+
+    /// Returns true if an access to the contract was recorded.
+    pub fn contains_contract(&self, contract: &ContractId) -> bool {
+        self.contracts.contains(contract)
+    }
+
+    /// Returns true if an access to the storage slot of the contract was recorded.
+    pub fn contains_storage_slot(&self, contract: &ContractId, key: &Bytes32) -> bool {
+        self.storage_slots
+            .get(contract)
+            .is_some_and(|keys| keys.contains(key))
+    }
+
+    /// Returns true if an access to the asset balance of the contract was recorded.
+    pub fn contains_asset(&self, contract: &ContractId, asset_id: &AssetId) -> bool {
+        self.assets
+            .get(contract)
+            .is_some_and(|assets| assets.contains(asset_id))
+    }
+
+    /// Add the accesses of `other`.
+    pub fn extend(&mut self, other: &AccessList) {
+        self.contracts.extend(&other.contracts);
+        for (contract, keys) in &other.storage_slots {
+            self.storage_slots
+                .entry(*contract)
+                .or_default()
+                .extend(keys);
+        }
+        for (contract, assets) in &other.assets {
+            self.assets.entry(*contract).or_default().extend(assets);
+        }
+    }
+
+    /// Add an access to `range` consecutive storage slots of a contract.
+    pub(crate) fn insert_storage_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: usize,
+    ) {
+        self.insert_contract(contract);
+        self.storage_slots
+            .entry(*contract)
+            .or_default()
+            .extend(storage_range(start_key, range));
+    }
+}
+
+/// The keys of `range` consecutive storage slots, stopping at the last key.
+pub(crate) fn storage_range(
+    start_key: &Bytes32,
+    range: usize,
+) -> impl Iterator<Item = Bytes32> {
+    core::iter::successors(Some(**start_key), |key| {
+        let mut key = *key;
+        (!add_one(&mut key)).then_some(key)
+    })
+    .take(range)
+    .map(Bytes32::from)
+}
+
+/// Storage wrapper recording the [`AccessList`] of the entries read or written
+/// through it.
+#[derive(Debug, Clone)]
+pub struct AccessRecorder<S> {
+    storage: S,
+    access_list: RefCell<AccessList>,
+}
+
+impl<S> AccessRecorder<S> {
+    /// Wrap a storage, recording the accesses made to it from now on.
+    pub fn new(storage: S) -> Self {
+        Self {
+            storage,
+            access_list: Default::default(),
+        }
+    }
+
+    /// The accesses recorded so far.
+    pub fn access_list(&self) -> AccessList {
+        self.access_list.borrow().clone()
+    }
+
+    /// Return the accesses recorded so far, and start a new recording.
+    pub fn take_access_list(&mut self) -> AccessList {
+        core::mem::take(self.access_list.get_mut())
+    }
+
+    /// Remove the wrapper, returning the storage.
+    pub fn into_inner(self) -> S {
+        self.storage
+    }
+
+    fn record<Type: AccessedTable>(&self, key: &Type::Key) {
+        Type::record(&mut self.access_list.borrow_mut(), key)
+    }
+}
+
+impl<S> AsRef<S> for AccessRecorder<S> {
+    fn as_ref(&self) -> &S {
+        &self.storage
+    }
+}
+
+impl<S> AsMut<S> for AccessRecorder<S> {
+    fn as_mut(&mut self) -> &mut S {
+        &mut self.storage
+    }
+}
+
+/// A table whose accesses are part of the [`AccessList`].
+pub(crate) trait AccessedTable: Mappable {
+    /// Record an access to `key`.
+    fn record(access_list: &mut AccessList, key: &Self::Key);
+
+    /// Returns true if an access to `key` was recorded.
+    fn contains(access_list: &AccessList, key: &Self::Key) -> bool;
+}
+
+impl AccessedTable for ContractsRawCode {
+    fn record(access_list: &mut AccessList, key: &ContractId) {
+        access_list.insert_contract(key)
+    }
+
+    fn contains(access_list: &AccessList, key: &ContractId) -> bool {
+        access_list.contains_contract(key)
+    }
+}
+
+impl AccessedTable for ContractsInfo {
+    fn record(access_list: &mut AccessList, key: &ContractId) {
+        access_list.insert_contract(key)
+    }
+
+    fn contains(access_list: &AccessList, key: &ContractId) -> bool {
+        access_list.contains_contract(key)
+    }
+}
+
+impl AccessedTable for ContractsState {
+    fn record(access_list: &mut AccessList, key: &ContractsStateKey) {
+        access_list.insert_storage_slot(key.contract_id(), key.state_key())
+    }
+
+    fn contains(access_list: &AccessList, key: &ContractsStateKey) -> bool {
+        access_list.contains_storage_slot(key.contract_id(), key.state_key())
+    }
+}
+
+impl AccessedTable for ContractsAssets {
+    fn record(access_list: &mut AccessList, key: &ContractsAssetKey) {
+        access_list.insert_asset(key.contract_id(), key.asset_id())
+    }
+
+    fn contains(access_list: &AccessList, key: &ContractsAssetKey) -> bool {
+        access_list.contains_asset(key.contract_id(), key.asset_id())
+    }
+}
+
+impl<Type: AccessedTable, S> StorageInspect<Type> for AccessRecorder<S>
+where
+    S: StorageInspect<Type>,
+{
+    type Error = <S as StorageInspect<Type>>::Error;
+
+    fn get(
+        &self,
+        key: &<Type as Mappable>::Key,
+    ) -> Result<Option<Cow<'_, <Type as Mappable>::OwnedValue>>, Self::Error> {
+        self.record::<Type>(key);
+        <S as StorageInspect<Type>>::get(&self.storage, key)
+    }
+
+    fn contains_key(&self, key: &<Type as Mappable>::Key) -> Result<bool, Self::Error> {
+        self.record::<Type>(key);
+        <S as StorageInspect<Type>>::contains_key(&self.storage, key)
+    }
+}
+
+impl<Type: AccessedTable, S> StorageSize<Type> for AccessRecorder<S>
+where
+    S: StorageSize<Type>,
+{
+    fn size_of_value(
+        &self,
+        key: &<Type as Mappable>::Key,
+    ) -> Result<Option<usize>, Self::Error> {
+        self.record::<Type>(key);
+        <S as StorageSize<Type>>::size_of_value(&self.storage, key)
+    }
+}
+
+impl<Type: AccessedTable, S> StorageRead<Type> for AccessRecorder<S>
+where
+    S: StorageRead<Type>,
+{
+    fn read(
+        &self,
+        key: &<Type as Mappable>::Key,
+        buf: &mut [u8],
+    ) -> Result<Option<usize>, Self::Error> {
+        self.record::<Type>(key);
+        <S as StorageRead<Type>>::read(&self.storage, key, buf)
+    }
+
+    fn read_alloc(
+        &self,
+        key: &<Type as Mappable>::Key,
+    ) -> Result<Option<Vec<u8>>, Self::Error> {
+        self.record::<Type>(key);
+        <S as StorageRead<Type>>::read_alloc(&self.storage, key)
+    }
+}
+
+impl<Type: AccessedTable, S> StorageMutate<Type> for AccessRecorder<S>
+where
+    S: StorageMutate<Type>,
+{
+    fn insert(
+        &mut self,
+        key: &<Type as Mappable>::Key,
+        value: &<Type as Mappable>::Value,
+    ) -> Result<Option<<Type as Mappable>::OwnedValue>, Self::Error> {
+        self.record::<Type>(key);
+        <S as StorageMutate<Type>>::insert(&mut self.storage, key, value)
+    }
+
+    fn remove(
+        &mut self,
+        key: &<Type as Mappable>::Key,
+    ) -> Result<Option<<Type as Mappable>::OwnedValue>, Self::Error> {
+        self.record::<Type>(key);
+        <S as StorageMutate<Type>>::remove(&mut self.storage, key)
+    }
+}
+
+impl<Type: AccessedTable, S> MerkleRootStorage<ContractId, Type> for AccessRecorder<S>
+where
+    S: MerkleRootStorage<ContractId, Type>,
+{
+    fn root(&self, key: &ContractId) -> Result<MerkleRoot, Self::Error> {
+        self.access_list.borrow_mut().insert_contract(key);
+        <S as MerkleRootStorage<ContractId, Type>>::root(&self.storage, key)
+    }
+}
+
+impl<S: ContractsAssetsStorage> ContractsAssetsStorage for AccessRecorder<S> {}
+
+impl<S> InterpreterStorage for AccessRecorder<S>
+where
+    S: InterpreterStorage,
+{
+    type DataError = <S as InterpreterStorage>::DataError;
+
+    fn block_height(&self) -> Result<BlockHeight, Self::DataError> {
+        self.storage.block_height()
+    }
+
+    fn timestamp(&self, height: BlockHeight) -> Result<Word, Self::DataError> {
+        self.storage.timestamp(height)
+    }
+
+    fn block_hash(&self, block_height: BlockHeight) -> Result<Bytes32, Self::DataError> {
+        self.storage.block_hash(block_height)
+    }
+
+    fn coinbase(&self) -> Result<ContractId, Self::DataError> {
+        self.storage.coinbase()
+    }
+
+    fn storage_contract_size(
+        &self,
+        id: &ContractId,
+    ) -> Result<Option<usize>, Self::DataError> {
+        self.access_list.borrow_mut().insert_contract(id);
+        self.storage.storage_contract_size(id)
+    }
+
+    fn read_contract(
+        &self,
+        id: &ContractId,
+        writer: &mut [u8],
+    ) -> Result<Option<Word>, Self::DataError> {
+        self.access_list.borrow_mut().insert_contract(id);
+        self.storage.read_contract(id, writer)
+    }
+
+    fn merkle_contract_state_range(
+        &self,
+        id: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> Result<Vec<Option<Cow<'_, Bytes32>>>, Self::DataError> {
+        let values = self
+            .storage
+            .merkle_contract_state_range(id, start_key, range)?;
+        self.access_list
+            .borrow_mut()
+            .insert_storage_range(id, start_key, values.len());
+        Ok(values)
+    }
+
+    fn merkle_contract_state_insert_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        values: &[Bytes32],
+    ) -> Result<Option<()>, Self::DataError> {
+        self.access_list.get_mut().insert_storage_range(
+            contract,
+            start_key,
+            values.len(),
+        );
+        self.storage
+            .merkle_contract_state_insert_range(contract, start_key, values)
+    }
+
+    fn merkle_contract_state_remove_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> Result<Option<()>, Self::DataError> {
+        let range_len = usize::try_from(range).unwrap_or(usize::MAX);
+        self.access_list
+            .get_mut()
+            .insert_storage_range(contract, start_key, range_len);
+        self.storage
+            .merkle_contract_state_remove_range(contract, start_key, range)
+    }
+
+    fn checkpoint(&mut self) -> Result<StorageCheckpoint, Self::DataError> {
+        self.storage.checkpoint()
+    }
+
+    fn revert_to(
+        &mut self,
+        checkpoint: StorageCheckpoint,
+    ) -> Result<(), Self::DataError> {
+        self.storage.revert_to(checkpoint)
+    }
+
+    fn release(&mut self, checkpoint: StorageCheckpoint) -> Result<(), Self::DataError> {
+        self.storage.release(checkpoint)
+    }
+
+    fn prefetch(&mut self, access_list: &AccessList) -> Result<(), Self::DataError> {
+        self.storage.prefetch(access_list)
+    }
+}
@@ -0,0 +1,506 @@
+//! Storage backends behind async I/O.
+//!
+//! The interpreter is synchronous, so it executes against an [`AsyncStorage`], a
+//! cache of the entries fetched from an [`AsyncInterpreterStorage`]. Reading an
+//! entry that wasn't fetched yet fails with [`AsyncStorageError::NotFetched`] and
+//! records the entry, which is then fetched asynchronously by
+//! [`crate::interpreter::Interpreter::transact_async`].
+
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    collections::{
+        BTreeMap,
+        BTreeSet,
+    },
+    vec::Vec,
+};
+use core::{
+    cell::RefCell,
+    convert::Infallible,
+};
+
+use fuel_storage::{
+    Mappable,
+    MerkleRoot,
+    MerkleRootStorage,
+    StorageInspect,
+    StorageMutate,
+    StorageRead,
+    StorageSize,
+};
+use fuel_types::{
+    BlockHeight,
+    Bytes32,
+    ContractId,
+    Word,
+};
+
+use crate::prelude::{
+    InterpreterError,
+    RuntimeError,
+};
+
+use super::{
+    access_list::{
+        storage_range,
+        AccessedTable,
+    },
+    memory::StorageEntry,
+    AccessList,
+    ContractsAssetKey,
+    ContractsAssets,
+    ContractsAssetsStorage,
+    ContractsInfo,
+    ContractsRawCode,
+    ContractsState,
+    ContractsStateKey,
+    InterpreterStorage,
+    MemoryStorage,
+    StorageCheckpoint,
+};
+
+/// Async variant of [`StorageInspect`].
+#[async_trait::async_trait]
+pub trait AsyncStorageInspect<Type: Mappable> {
+    /// Error of the storage.
+    type Error;
+
+    /// Fetch the value of `key`.
+    async fn get(&self, key: &Type::Key)
+        -> Result<Option<Type::OwnedValue>, Self::Error>;
+}
+
+/// Async variant of [`InterpreterStorage`], reading the entries the interpreter
+/// needs from a backend behind async I/O.
+///
+/// The changes made by the interpreter aren't written to the backend: they are kept
+/// by the [`AsyncStorage`], and reported by the [`crate::state::StateChanges`] of the
+/// transactions.
+#[async_trait::async_trait]
+pub trait AsyncInterpreterStorage:
+    AsyncStorageInspect<ContractsRawCode, Error = Self::DataError>
+    + AsyncStorageInspect<ContractsInfo, Error = Self::DataError>
+    + AsyncStorageInspect<ContractsState, Error = Self::DataError>
+    + AsyncStorageInspect<ContractsAssets, Error = Self::DataError>
+    + Send
+    + Sync
+{
+    /// Error of the storage.
+    type DataError: core::fmt::Debug + Send;
+
+    /// Fetch the current block height.
+    async fn block_height(&self) -> Result<BlockHeight, Self::DataError>;
+
+    /// Fetch the timestamp of the block at `height`.
+    async fn timestamp(&self, height: BlockHeight) -> Result<Word, Self::DataError>;
+
+    /// Fetch the hash of the block at `height`.
+    async fn block_hash(&self, height: BlockHeight) -> Result<Bytes32, Self::DataError>;
+
+    /// Fetch the coinbase contract of the current block.
+    async fn coinbase(&self) -> Result<ContractId, Self::DataError>;
+}
+
+/// Error of an [`AsyncStorage`].
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+#[display(bound = "StorageError: core::fmt::Debug")]
+pub enum AsyncStorageError<StorageError> {
+    /// The entry wasn't fetched from the backend yet.
+    #[display(fmt = "The storage entry wasn't fetched yet")]
+    NotFetched,
+    /// The Merkle roots of the contracts can't be computed from the fetched entries.
+    #[display(fmt = "The Merkle roots are unavailable")]
+    RootUnavailable,
+    /// The backend failed to fetch an entry.
+    #[display(fmt = "Failed to fetch the storage entry: {_0:?}")]
+    Storage(StorageError),
+}
+
+impl<E> From<AsyncStorageError<E>> for InterpreterError<AsyncStorageError<E>> {
+    fn from(error: AsyncStorageError<E>) -> Self {
+        InterpreterError::Storage(error)
+    }
+}
+
+impl<E> From<AsyncStorageError<E>> for RuntimeError<AsyncStorageError<E>> {
+    fn from(error: AsyncStorageError<E>) -> Self {
+        RuntimeError::Storage(error)
+    }
+}
+
+/// Entries read by the interpreter that weren't fetched yet.
+#[derive(Debug, Default, Clone)]
+struct MissingEntries {
+    access_list: AccessList,
+    blocks: BTreeSet<BlockHeight>,
+}
+
+/// Synchronous cache of the entries fetched from an [`AsyncInterpreterStorage`],
+/// holding the changes made by the interpreter on top of them.
+///
+/// The block height and the coinbase are fetched when the cache is created.
+#[derive(Debug)]
+pub struct AsyncStorage<S> {
+    storage: S,
+    block_height: BlockHeight,
+    coinbase: ContractId,
+    /// The fetched entries and the changes made to them.
+    cache: MemoryStorage,
+    fetched: AccessList,
+    /// Timestamp and hash of the fetched blocks.
+    blocks: BTreeMap<BlockHeight, (Word, Bytes32)>,
+    missing: RefCell<MissingEntries>,
+}
+
+impl<S> AsyncStorage<S>
+where
+    S: AsyncInterpreterStorage,
+{
+    /// Create an empty cache of `storage`, fetching the block height and the
+    /// coinbase.
+    pub async fn new(storage: S) -> Result<Self, S::DataError> {
+        let block_height = storage.block_height().await?;
+        let coinbase = storage.coinbase().await?;
+
+        Ok(Self {
+            storage,
+            block_height,
+            coinbase,
+            cache: MemoryStorage::new(block_height, coinbase),
+            fetched: AccessList::default(),
+            blocks: BTreeMap::new(),
+            missing: RefCell::default(),
+        })
+    }
+
+    /// Fetch the entries of the access list that weren't fetched yet.
+    ///
+    /// The fetched entries aren't changes that checkpoints can undo, so they can be
+    /// fetched while the interpreter is suspended in the middle of a transaction.
+    ///
+    /// The interpreter fetches the entries it reads on its own. Fetching the entries
+    /// recorded by an [`super::AccessRecorder`] beforehand avoids interrupting the
+    /// execution to fetch them. The blocks read by `TIME` and `BHSH` aren't part of
+    /// the access lists, and are fetched with [`Self::fetch_block`].
+    pub async fn fetch(&mut self, access_list: &AccessList) -> Result<(), S::DataError> {
+        for contract in &access_list.contracts {
+            if self.fetched.contains_contract(contract) {
+                continue
+            }
+            let code =
+                AsyncStorageInspect::<ContractsRawCode>::get(&self.storage, contract)
+                    .await?;
+            let info = AsyncStorageInspect::<ContractsInfo>::get(&self.storage, contract)
+                .await?;
+            if code.is_some() {
+                self.cache
+                    .set_persisted_entry(StorageEntry::Contract(*contract, code));
+            }
+            if info.is_some() {
+                self.cache
+                    .set_persisted_entry(StorageEntry::ContractInfo(*contract, info));
+            }
+            self.fetched.insert_contract(contract);
+        }
+
+        for (contract, keys) in &access_list.storage_slots {
+            for key in keys {
+                if self.fetched.contains_storage_slot(contract, key) {
+                    continue
+                }
+                let key = ContractsStateKey::new(contract, key);
+                let value =
+                    AsyncStorageInspect::<ContractsState>::get(&self.storage, &key)
+                        .await?;
+                if value.is_some() {
+                    self.cache
+                        .set_persisted_entry(StorageEntry::State(key, value));
+                }
+                self.fetched
+                    .insert_storage_slot(key.contract_id(), key.state_key());
+            }
+        }
+
+        for (contract, assets) in &access_list.assets {
+            for asset_id in assets {
+                if self.fetched.contains_asset(contract, asset_id) {
+                    continue
+                }
+                let key = ContractsAssetKey::new(contract, asset_id);
+                let value =
+                    AsyncStorageInspect::<ContractsAssets>::get(&self.storage, &key)
+                        .await?;
+                if value.is_some() {
+                    self.cache
+                        .set_persisted_entry(StorageEntry::Balance(key, value));
+                }
+                self.fetched.insert_asset(contract, asset_id);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fetch the entries read by the interpreter that weren't fetched.
+    pub(crate) async fn fetch_missing(&mut self) -> Result<(), S::DataError> {
+        let missing = self.missing.take();
+
+        for height in missing.blocks {
+            self.fetch_block(height).await?;
+        }
+
+        self.fetch(&missing.access_list).await
+    }
+
+    /// Fetch the timestamp and the hash of the block at `height`, read by `TIME` and
+    /// `BHSH`, if they weren't fetched yet.
+    pub async fn fetch_block(&mut self, height: BlockHeight) -> Result<(), S::DataError> {
+        if !self.blocks.contains_key(&height) {
+            let timestamp = self.storage.timestamp(height).await?;
+            let hash = self.storage.block_hash(height).await?;
+            self.blocks.insert(height, (timestamp, hash));
+        }
+        Ok(())
+    }
+}
+
+impl<S> AsyncStorage<S> {
+    /// The entries fetched from the backend.
+    pub fn fetched(&self) -> &AccessList {
+        &self.fetched
+    }
+
+    /// The backend of the cache.
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// Remove the cache, returning the backend.
+    pub fn into_inner(self) -> S {
+        self.storage
+    }
+
+    /// Returns true if an entry that wasn't fetched was read since the last fetch.
+    pub(crate) fn has_missing_entries(&self) -> bool {
+        let missing = self.missing.borrow();
+        !missing.access_list.is_empty() || !missing.blocks.is_empty()
+    }
+
+    /// Check that `key` was fetched, recording it as missing otherwise.
+    fn require<Type: AccessedTable, E>(
+        &self,
+        key: &Type::Key,
+    ) -> Result<(), AsyncStorageError<E>> {
+        if Type::contains(&self.fetched, key) {
+            Ok(())
+        } else {
+            Type::record(&mut self.missing.borrow_mut().access_list, key);
+            Err(AsyncStorageError::NotFetched)
+        }
+    }
+
+    /// Check that `range` consecutive storage slots of the contract were fetched,
+    /// recording the missing ones otherwise.
+    fn require_range<E>(
+        &self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> Result<(), AsyncStorageError<E>> {
+        let range = usize::try_from(range).unwrap_or(usize::MAX);
+        let mut fetched = true;
+        for key in storage_range(start_key, range) {
+            if !self.fetched.contains_storage_slot(contract, &key) {
+                self.missing
+                    .borrow_mut()
+                    .access_list
+                    .insert_storage_slot(contract, &key);
+                fetched = false;
+            }
+        }
+
+        if fetched {
+            Ok(())
+        } else {
+            Err(AsyncStorageError::NotFetched)
+        }
+    }
+
+    /// The timestamp and hash of the block at `height`, recorded as missing if it
+    /// wasn't fetched.
+    fn block<E>(
+        &self,
+        height: BlockHeight,
+    ) -> Result<(Word, Bytes32), AsyncStorageError<E>> {
+        self.blocks.get(&height).copied().ok_or_else(|| {
+            self.missing.borrow_mut().blocks.insert(height);
+            AsyncStorageError::NotFetched
+        })
+    }
+}
+
+impl<Type: AccessedTable, S> StorageInspect<Type> for AsyncStorage<S>
+where
+    S: AsyncInterpreterStorage,
+    MemoryStorage: StorageInspect<Type, Error = Infallible>,
+{
+    type Error = AsyncStorageError<S::DataError>;
+
+    fn get(
+        &self,
+        key: &Type::Key,
+    ) -> Result<Option<Cow<'_, Type::OwnedValue>>, Self::Error> {
+        self.require::<Type, _>(key)?;
+        Ok(StorageInspect::<Type>::get(&self.cache, key).expect("Infallible"))
+    }
+
+    fn contains_key(&self, key: &Type::Key) -> Result<bool, Self::Error> {
+        self.require::<Type, _>(key)?;
+        Ok(StorageInspect::<Type>::contains_key(&self.cache, key).expect("Infallible"))
+    }
+}
+
+impl<Type: AccessedTable, S> StorageMutate<Type> for AsyncStorage<S>
+where
+    S: AsyncInterpreterStorage,
+    MemoryStorage: StorageMutate<Type> + StorageInspect<Type, Error = Infallible>,
+{
+    fn insert(
+        &mut self,
+        key: &Type::Key,
+        value: &Type::Value,
+    ) -> Result<Option<Type::OwnedValue>, Self::Error> {
+        self.require::<Type, _>(key)?;
+        Ok(StorageMutate::<Type>::insert(&mut self.cache, key, value)
+            .expect("Infallible"))
+    }
+
+    fn remove(
+        &mut self,
+        key: &Type::Key,
+    ) -> Result<Option<Type::OwnedValue>, Self::Error> {
+        self.require::<Type, _>(key)?;
+        Ok(StorageMutate::<Type>::remove(&mut self.cache, key).expect("Infallible"))
+    }
+}
+
+impl<S> StorageSize<ContractsRawCode> for AsyncStorage<S>
+where
+    S: AsyncInterpreterStorage,
+{
+    fn size_of_value(&self, key: &ContractId) -> Result<Option<usize>, Self::Error> {
+        self.require::<ContractsRawCode, _>(key)?;
+        Ok(self.cache.size_of_value(key).expect("Infallible"))
+    }
+}
+
+impl<S> StorageRead<ContractsRawCode> for AsyncStorage<S>
+where
+    S: AsyncInterpreterStorage,
+{
+    fn read(
+        &self,
+        key: &ContractId,
+        buf: &mut [u8],
+    ) -> Result<Option<usize>, Self::Error> {
+        self.require::<ContractsRawCode, _>(key)?;
+        Ok(self.cache.read(key, buf).expect("Infallible"))
+    }
+
+    fn read_alloc(&self, key: &ContractId) -> Result<Option<Vec<u8>>, Self::Error> {
+        self.require::<ContractsRawCode, _>(key)?;
+        Ok(self.cache.read_alloc(key).expect("Infallible"))
+    }
+}
+
+impl<Type: AccessedTable, S> MerkleRootStorage<ContractId, Type> for AsyncStorage<S>
+where
+    S: AsyncInterpreterStorage,
+    MemoryStorage: StorageMutate<Type> + StorageInspect<Type, Error = Infallible>,
+{
+    fn root(&self, _key: &ContractId) -> Result<MerkleRoot, Self::Error> {
+        Err(AsyncStorageError::RootUnavailable)
+    }
+}
+
+impl<S> ContractsAssetsStorage for AsyncStorage<S> where S: AsyncInterpreterStorage {}
+
+impl<S> InterpreterStorage for AsyncStorage<S>
+where
+    S: AsyncInterpreterStorage,
+{
+    type DataError = AsyncStorageError<S::DataError>;
+
+    fn block_height(&self) -> Result<BlockHeight, Self::DataError> {
+        Ok(self.block_height)
+    }
+
+    fn timestamp(&self, height: BlockHeight) -> Result<Word, Self::DataError> {
+        self.block(height).map(|(timestamp, _)| timestamp)
+    }
+
+    fn block_hash(&self, block_height: BlockHeight) -> Result<Bytes32, Self::DataError> {
+        self.block(block_height).map(|(_, hash)| hash)
+    }
+
+    fn coinbase(&self) -> Result<ContractId, Self::DataError> {
+        Ok(self.coinbase)
+    }
+
+    fn merkle_contract_state_range(
+        &self,
+        id: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> Result<Vec<Option<Cow<'_, Bytes32>>>, Self::DataError> {
+        self.require_range(id, start_key, range)?;
+        Ok(self
+            .cache
+            .merkle_contract_state_range(id, start_key, range)
+            .expect("Infallible"))
+    }
+
+    fn merkle_contract_state_insert_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        values: &[Bytes32],
+    ) -> Result<Option<()>, Self::DataError> {
+        self.require_range(contract, start_key, values.len() as Word)?;
+        Ok(self
+            .cache
+            .merkle_contract_state_insert_range(contract, start_key, values)
+            .expect("Infallible"))
+    }
+
+    fn merkle_contract_state_remove_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> Result<Option<()>, Self::DataError> {
+        self.require_range(contract, start_key, range)?;
+        Ok(self
+            .cache
+            .merkle_contract_state_remove_range(contract, start_key, range)
+            .expect("Infallible"))
+    }
+
+    fn checkpoint(&mut self) -> Result<StorageCheckpoint, Self::DataError> {
+        Ok(self.cache.checkpoint().expect("Infallible"))
+    }
+
+    fn revert_to(
+        &mut self,
+        checkpoint: StorageCheckpoint,
+    ) -> Result<(), Self::DataError> {
+        self.cache.revert_to(checkpoint).expect("Infallible");
+        Ok(())
+    }
+
+    fn release(&mut self, checkpoint: StorageCheckpoint) -> Result<(), Self::DataError> {
+        self.cache.release(checkpoint).expect("Infallible");
+        Ok(())
+    }
+}
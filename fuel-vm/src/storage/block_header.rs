@@ -0,0 +1,166 @@
+//! Headers of the blocks of the chain, read by the `BHSH`, `TIME` and `CB`
+//! instructions.
+
+use alloc::collections::BTreeMap;
+use core::convert::Infallible;
+
+use fuel_crypto::Hasher;
+use fuel_types::{
+    BlockHeight,
+    Bytes32,
+    ContractId,
+    Word,
+};
+use tai64::Tai64;
+
+/// Header data of a block used by the VM.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockHeader {
+    /// Height of the block.
+    pub height: BlockHeight,
+    /// Hash of the block.
+    pub hash: Bytes32,
+    /// Time of the block, as a TAI64 timestamp.
+    pub timestamp: Word,
+    /// Coinbase contract of the block, receiving its fees.
+    pub coinbase: ContractId,
+    /// Height of the data availability layer when the block was produced.
+    pub da_height: Word,
+}
+
+/// Provider of the headers of the blocks of the chain.
+pub trait BlockHeaderProvider {
+    /// Error of the provider.
+    type Error;
+
+    /// Header of the block at `height`.
+    fn block_header(&self, height: BlockHeight) -> Result<BlockHeader, Self::Error>;
+}
+
+impl<P> BlockHeaderProvider for &P
+where
+    P: BlockHeaderProvider + ?Sized,
+{
+    type Error = P::Error;
+
+    fn block_header(&self, height: BlockHeight) -> Result<BlockHeader, Self::Error> {
+        P::block_header(self, height)
+    }
+}
+
+/// In-memory block headers.
+///
+/// The headers of the heights that aren't set are generated: their hash is the hash
+/// of the height, their timestamp is `block_time` seconds per height after the
+/// genesis timestamp, their coinbase is the default one and their DA height is zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryBlockHeaders {
+    headers: BTreeMap<BlockHeight, BlockHeader>,
+    genesis_timestamp: Word,
+    block_time: Word,
+    coinbase: ContractId,
+}
+
+impl MemoryBlockHeaders {
+    /// Time between the generated blocks, in seconds.
+    pub const DEFAULT_BLOCK_TIME: Word = 10;
+
+    /// Create the headers, generating the ones that aren't set from the genesis
+    /// timestamp, the time between the blocks and the default coinbase.
+    pub fn new(genesis_timestamp: Tai64, block_time: Word, coinbase: ContractId) -> Self {
+        Self {
+            headers: BTreeMap::new(),
+            genesis_timestamp: genesis_timestamp.0,
+            block_time,
+            coinbase,
+        }
+    }
+
+    /// Coinbase of the generated headers.
+    pub const fn coinbase(&self) -> &ContractId {
+        &self.coinbase
+    }
+
+    /// Set the header of the block at its height, returning the header set before.
+    pub fn insert(&mut self, header: BlockHeader) -> Option<BlockHeader> {
+        self.headers.insert(header.height, header)
+    }
+
+    /// Remove the header set at `height`, which is generated again.
+    pub fn remove(&mut self, height: BlockHeight) -> Option<BlockHeader> {
+        self.headers.remove(&height)
+    }
+
+    /// Header of the block at `height`.
+    pub fn get(&self, height: BlockHeight) -> BlockHeader {
+        self.headers.get(&height).copied().unwrap_or_else(|| {
+            let timestamp = Tai64(self.genesis_timestamp)
+                + (*height as Word).saturating_mul(self.block_time);
+
+            BlockHeader {
+                height,
+                hash: Hasher::hash(height.to_be_bytes()),
+                timestamp: timestamp.0,
+                coinbase: self.coinbase,
+                da_height: 0,
+            }
+        })
+    }
+}
+
+impl Default for MemoryBlockHeaders {
+    fn default() -> Self {
+        Self::new(
+            Tai64::UNIX_EPOCH,
+            Self::DEFAULT_BLOCK_TIME,
+            ContractId::default(),
+        )
+    }
+}
+
+impl BlockHeaderProvider for MemoryBlockHeaders {
+    type Error = Infallible;
+
+    fn block_header(&self, height: BlockHeight) -> Result<BlockHeader, Infallible> {
+        Ok(self.get(height))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unset_headers_are_generated() {
+        let coinbase = ContractId::new([1; 32]);
+        let headers = MemoryBlockHeaders::new(Tai64::UNIX_EPOCH + 100, 5, coinbase);
+
+        let header = headers.get(4.into());
+
+        assert_eq!(header.height, 4.into());
+        assert_eq!(header.hash, Hasher::hash(4u32.to_be_bytes()));
+        assert_eq!(header.timestamp, (Tai64::UNIX_EPOCH + 120).0);
+        assert_eq!(header.coinbase, coinbase);
+        assert_eq!(header.da_height, 0);
+    }
+
+    #[test]
+    fn set_headers_replace_the_generated_ones() {
+        let mut headers = MemoryBlockHeaders::default();
+        let generated = headers.get(7.into());
+        let header = BlockHeader {
+            height: 7.into(),
+            hash: Bytes32::new([2; 32]),
+            timestamp: 1_700_000_000,
+            coinbase: ContractId::new([3; 32]),
+            da_height: 42,
+        };
+
+        assert_eq!(headers.insert(header), None);
+        assert_eq!(headers.get(7.into()), header);
+        assert_eq!(headers.remove(7.into()), Some(header));
+        assert_eq!(headers.get(7.into()), generated);
+    }
+}
@@ -0,0 +1,316 @@
+//! Storage wrapper recording the [`StateChanges`] made through it.
+
+use alloc::{
+    borrow::Cow,
+    vec::Vec,
+};
+
+use fuel_storage::{
+    Mappable,
+    MerkleRoot,
+    MerkleRootStorage,
+    StorageInspect,
+    StorageMutate,
+    StorageRead,
+    StorageSize,
+};
+use fuel_types::{
+    BlockHeight,
+    Bytes32,
+    ContractId,
+    Word,
+};
+
+use crate::state::StateChanges;
+
+use super::{
+    memory::add_one,
+    AccessList,
+    ContractsAssets,
+    ContractsAssetsStorage,
+    ContractsInfo,
+    ContractsRawCode,
+    ContractsState,
+    InterpreterStorage,
+    StorageCheckpoint,
+};
+
+/// Borrows a storage, recording the writes made to it into `changes`.
+pub(crate) struct ChangesRecorder<'a, S> {
+    pub storage: &'a mut S,
+    pub changes: &'a mut StateChanges,
+}
+
+/// A table whose writes are part of the [`StateChanges`].
+pub(crate) trait RecordedTable: Mappable {
+    /// Record the replacement of the `old` value of `key` by `new`.
+    fn record(
+        changes: &mut StateChanges,
+        key: &Self::Key,
+        old: Option<Self::OwnedValue>,
+        new: Option<&Self::Value>,
+    );
+}
+
+impl RecordedTable for ContractsState {
+    fn record(
+        changes: &mut StateChanges,
+        key: &Self::Key,
+        old: Option<Bytes32>,
+        new: Option<&Bytes32>,
+    ) {
+        changes.record_state(key.contract_id(), key.state_key(), old, new.copied());
+    }
+}
+
+impl RecordedTable for ContractsAssets {
+    fn record(
+        changes: &mut StateChanges,
+        key: &Self::Key,
+        old: Option<Word>,
+        new: Option<&Word>,
+    ) {
+        changes.record_balance(key.contract_id(), key.asset_id(), old, new.copied());
+    }
+}
+
+impl RecordedTable for ContractsRawCode {
+    fn record(
+        changes: &mut StateChanges,
+        key: &ContractId,
+        _: Option<Self::OwnedValue>,
+        new: Option<&[u8]>,
+    ) {
+        if let Some(bytecode) = new {
+            changes.record_bytecode(key, bytecode.into());
+        }
+    }
+}
+
+impl RecordedTable for ContractsInfo {
+    fn record(
+        changes: &mut StateChanges,
+        key: &ContractId,
+        _: Option<Self::OwnedValue>,
+        new: Option<&Self::Value>,
+    ) {
+        if let Some((salt, root)) = new {
+            changes.record_contract_info(key, salt, root);
+        }
+    }
+}
+
+impl<Type: Mappable, S> StorageInspect<Type> for ChangesRecorder<'_, S>
+where
+    S: StorageInspect<Type>,
+{
+    type Error = <S as StorageInspect<Type>>::Error;
+
+    fn get(
+        &self,
+        key: &<Type as Mappable>::Key,
+    ) -> Result<Option<Cow<'_, <Type as Mappable>::OwnedValue>>, Self::Error> {
+        <S as StorageInspect<Type>>::get(self.storage, key)
+    }
+
+    fn contains_key(&self, key: &<Type as Mappable>::Key) -> Result<bool, Self::Error> {
+        <S as StorageInspect<Type>>::contains_key(self.storage, key)
+    }
+}
+
+impl<Type: Mappable, S> StorageSize<Type> for ChangesRecorder<'_, S>
+where
+    S: StorageSize<Type>,
+{
+    fn size_of_value(
+        &self,
+        key: &<Type as Mappable>::Key,
+    ) -> Result<Option<usize>, Self::Error> {
+        <S as StorageSize<Type>>::size_of_value(self.storage, key)
+    }
+}
+
+impl<Type: Mappable, S> StorageRead<Type> for ChangesRecorder<'_, S>
+where
+    S: StorageRead<Type>,
+{
+    fn read(
+        &self,
+        key: &<Type as Mappable>::Key,
+        buf: &mut [u8],
+    ) -> Result<Option<usize>, Self::Error> {
+        <S as StorageRead<Type>>::read(self.storage, key, buf)
+    }
+
+    fn read_alloc(
+        &self,
+        key: &<Type as Mappable>::Key,
+    ) -> Result<Option<Vec<u8>>, Self::Error> {
+        <S as StorageRead<Type>>::read_alloc(self.storage, key)
+    }
+}
+
+impl<Type: RecordedTable, S> StorageMutate<Type> for ChangesRecorder<'_, S>
+where
+    S: StorageMutate<Type>,
+    Type::OwnedValue: Clone,
+{
+    fn insert(
+        &mut self,
+        key: &<Type as Mappable>::Key,
+        value: &<Type as Mappable>::Value,
+    ) -> Result<Option<<Type as Mappable>::OwnedValue>, Self::Error> {
+        let existing = <S as StorageMutate<Type>>::insert(self.storage, key, value)?;
+        Type::record(self.changes, key, existing.clone(), Some(value));
+        Ok(existing)
+    }
+
+    fn remove(
+        &mut self,
+        key: &<Type as Mappable>::Key,
+    ) -> Result<Option<<Type as Mappable>::OwnedValue>, Self::Error> {
+        let existing = <S as StorageMutate<Type>>::remove(self.storage, key)?;
+        if existing.is_some() {
+            Type::record(self.changes, key, existing.clone(), None);
+        }
+        Ok(existing)
+    }
+}
+
+impl<Key, Type: RecordedTable, S> MerkleRootStorage<Key, Type> for ChangesRecorder<'_, S>
+where
+    S: MerkleRootStorage<Key, Type>,
+    Type::OwnedValue: Clone,
+{
+    fn root(&self, key: &Key) -> Result<MerkleRoot, Self::Error> {
+        <S as MerkleRootStorage<Key, Type>>::root(self.storage, key)
+    }
+}
+
+impl<S: ContractsAssetsStorage> ContractsAssetsStorage for ChangesRecorder<'_, S> {}
+
+impl<S> InterpreterStorage for ChangesRecorder<'_, S>
+where
+    S: InterpreterStorage,
+{
+    type DataError = <S as InterpreterStorage>::DataError;
+
+    fn block_height(&self) -> Result<BlockHeight, Self::DataError> {
+        self.storage.block_height()
+    }
+
+    fn timestamp(&self, height: BlockHeight) -> Result<Word, Self::DataError> {
+        self.storage.timestamp(height)
+    }
+
+    fn block_hash(&self, block_height: BlockHeight) -> Result<Bytes32, Self::DataError> {
+        self.storage.block_hash(block_height)
+    }
+
+    fn coinbase(&self) -> Result<ContractId, Self::DataError> {
+        self.storage.coinbase()
+    }
+
+    fn storage_contract_size(
+        &self,
+        id: &ContractId,
+    ) -> Result<Option<usize>, Self::DataError> {
+        self.storage.storage_contract_size(id)
+    }
+
+    fn read_contract(
+        &self,
+        id: &ContractId,
+        writer: &mut [u8],
+    ) -> Result<Option<Word>, Self::DataError> {
+        self.storage.read_contract(id, writer)
+    }
+
+    fn merkle_contract_state_range(
+        &self,
+        id: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> Result<Vec<Option<Cow<'_, Bytes32>>>, Self::DataError> {
+        self.storage
+            .merkle_contract_state_range(id, start_key, range)
+    }
+
+    fn merkle_contract_state_insert_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        values: &[Bytes32],
+    ) -> Result<Option<()>, Self::DataError> {
+        let old = self.old_state_range(contract, start_key, values.len() as Word)?;
+        let result = self
+            .storage
+            .merkle_contract_state_insert_range(contract, start_key, values)?;
+        for ((key, old), new) in old.into_iter().zip(values) {
+            self.changes.record_state(contract, &key, old, Some(*new));
+        }
+        Ok(result)
+    }
+
+    fn merkle_contract_state_remove_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> Result<Option<()>, Self::DataError> {
+        let old = self.old_state_range(contract, start_key, range)?;
+        let result = self
+            .storage
+            .merkle_contract_state_remove_range(contract, start_key, range)?;
+        for (key, old) in old {
+            if old.is_some() {
+                self.changes.record_state(contract, &key, old, None);
+            }
+        }
+        Ok(result)
+    }
+
+    fn checkpoint(&mut self) -> Result<StorageCheckpoint, Self::DataError> {
+        self.storage.checkpoint()
+    }
+
+    fn revert_to(
+        &mut self,
+        checkpoint: StorageCheckpoint,
+    ) -> Result<(), Self::DataError> {
+        self.storage.revert_to(checkpoint)
+    }
+
+    fn release(&mut self, checkpoint: StorageCheckpoint) -> Result<(), Self::DataError> {
+        self.storage.release(checkpoint)
+    }
+
+    fn prefetch(&mut self, access_list: &AccessList) -> Result<(), Self::DataError> {
+        self.storage.prefetch(access_list)
+    }
+}
+
+impl<S> ChangesRecorder<'_, S>
+where
+    S: InterpreterStorage,
+{
+    /// Values of `range` consecutive slots, paired with their keys.
+    fn old_state_range(
+        &self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> Result<Vec<(Bytes32, Option<Bytes32>)>, S::DataError> {
+        let values = self
+            .storage
+            .merkle_contract_state_range(contract, start_key, range)?;
+        let keys = core::iter::successors(Some(**start_key), |key| {
+            let mut key = *key;
+            (!add_one(&mut key)).then_some(key)
+        });
+        Ok(keys
+            .map(Bytes32::from)
+            .zip(values.into_iter().map(|value| value.map(Cow::into_owned)))
+            .collect())
+    }
+}
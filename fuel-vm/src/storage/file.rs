@@ -0,0 +1,848 @@
+//! File-backed storage, keeping the state of the interpreter across restarts.
+
+use alloc::{
+    borrow::Cow,
+    vec::Vec,
+};
+use core::convert::Infallible;
+use std::{
+    fs::{
+        self,
+        File,
+        OpenOptions,
+    },
+    io::{
+        self,
+        Read,
+        Seek,
+        SeekFrom,
+        Write,
+    },
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+
+use fuel_crypto::Hasher;
+use fuel_storage::{
+    Mappable,
+    MerkleRoot,
+    MerkleRootStorage,
+    StorageInspect,
+    StorageMutate,
+    StorageRead,
+    StorageSize,
+    StorageWrite,
+};
+use fuel_tx::Contract;
+use fuel_types::{
+    BlockHeight,
+    Bytes32,
+    ContractId,
+    Word,
+};
+
+use super::{
+    interpreter::ContractsAssetsStorage,
+    memory::StorageEntry,
+    AccessList,
+    BlockHeaderProvider,
+    ContractsAssetKey,
+    ContractsRawCode,
+    ContractsStateKey,
+    InterpreterStorage,
+    MemoryBlockHeaders,
+    MemoryStorage,
+    StorageCheckpoint,
+};
+
+const SNAPSHOT: &str = "snapshot";
+const SNAPSHOT_TMP: &str = "snapshot.tmp";
+const LOG: &str = "log";
+
+/// Length of the header of a frame: the length of its payload and its hash.
+const FRAME_HEADER: usize = 8 + 32;
+
+/// Storage of the interpreter kept in a directory.
+///
+/// The state is held in a [`MemoryStorage`], and the changes are written to the
+/// directory when they are committed. The directory contains a snapshot of the state
+/// and a log of the batches of changes committed after it. Every batch is appended
+/// to the log with its hash and synced before [`Self::commit`] returns, so a batch is
+/// either fully applied or ignored when the storage is opened again after a crash.
+/// [`Self::compact`] replaces the snapshot atomically and truncates the log.
+///
+/// The block height and the block headers aren't persisted.
+#[derive(Debug)]
+pub struct FileStorage {
+    dir: PathBuf,
+    log: File,
+    /// Length of the complete frames of the log.
+    log_len: u64,
+    block_height: BlockHeight,
+    memory: MemoryStorage,
+}
+
+impl FileStorage {
+    /// Open the storage kept in `dir`, creating the directory if it doesn't exist.
+    ///
+    /// A batch of changes partially written to the log by a crash is discarded.
+    pub fn open<P: AsRef<Path>>(
+        dir: P,
+        block_height: BlockHeight,
+        coinbase: ContractId,
+    ) -> io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+
+        let mut memory = MemoryStorage::new(block_height, coinbase);
+        match fs::read(dir.join(SNAPSHOT)) {
+            Ok(snapshot) => {
+                let (frames, len) = read_frames(&snapshot)?;
+                if frames.len() != 1 || len != snapshot.len() {
+                    return Err(invalid_data("The snapshot is corrupted"))
+                }
+                frames
+                    .into_iter()
+                    .flatten()
+                    .for_each(|entry| memory.set_entry(entry));
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+
+        let mut log = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(dir.join(LOG))?;
+        let mut bytes = Vec::new();
+        log.read_to_end(&mut bytes)?;
+        let (batches, len) = read_frames(&bytes)?;
+        batches
+            .into_iter()
+            .flatten()
+            .for_each(|entry| memory.set_entry(entry));
+        let log_len = len as u64;
+        if len != bytes.len() {
+            log.set_len(log_len)?;
+            log.sync_all()?;
+        }
+        log.seek(SeekFrom::Start(log_len))?;
+
+        memory.commit();
+        memory.persist();
+
+        Ok(Self {
+            dir,
+            log,
+            log_len,
+            block_height,
+            memory,
+        })
+    }
+
+    /// Directory of the storage.
+    pub fn path(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Write the changes made since the last commit to the log, as a single batch.
+    ///
+    /// If the batch can't be written, the changes stay pending and the part of the
+    /// batch already written is removed from the log.
+    pub fn commit(&mut self) -> io::Result<()> {
+        let entries = self.memory.uncommitted_entries();
+        if !entries.is_empty() {
+            let frame = frame(&entries);
+            if let Err(e) = self.append(&frame) {
+                // The torn frame is truncated again before the next batch is
+                // written, if it can't be truncated here.
+                let _ = self.log.set_len(self.log_len);
+                return Err(e)
+            }
+        }
+
+        self.memory.commit();
+        self.memory.persist();
+        Ok(())
+    }
+
+    /// Write a frame after the complete frames of the log, and sync it.
+    fn append(&mut self, frame: &[u8]) -> io::Result<()> {
+        self.log.set_len(self.log_len)?;
+        self.log.seek(SeekFrom::Start(self.log_len))?;
+        self.log.write_all(frame)?;
+        self.log.sync_data()?;
+        self.log_len += frame.len() as u64;
+        Ok(())
+    }
+
+    /// Discard the changes made since the last commit.
+    pub fn revert(&mut self) {
+        self.memory.revert();
+    }
+
+    /// Commit the pending changes, then replace the snapshot by the state and
+    /// truncate the log.
+    pub fn compact(&mut self) -> io::Result<()> {
+        self.commit()?;
+
+        let entries: Vec<_> = self.memory.entries().collect();
+        let tmp = self.dir.join(SNAPSHOT_TMP);
+        let mut snapshot = File::create(&tmp)?;
+        snapshot.write_all(&frame(&entries))?;
+        snapshot.sync_all()?;
+        fs::rename(&tmp, self.dir.join(SNAPSHOT))?;
+        sync_dir(&self.dir)?;
+
+        self.log.set_len(0)?;
+        self.log_len = 0;
+        self.log.seek(SeekFrom::Start(0))?;
+        self.log.sync_all()
+    }
+
+    /// Set the block height of the chain.
+    pub fn set_block_height(&mut self, block_height: BlockHeight) {
+        self.block_height = block_height;
+    }
+
+    /// Mutable headers of the blocks of the chain.
+    pub fn block_headers_mut(&mut self) -> &mut MemoryBlockHeaders {
+        self.memory.block_headers_mut()
+    }
+}
+
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> io::Result<()> {
+    File::open(dir)?.sync_all()
+}
+
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+fn invalid_data(error: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+/// Encode the entries in a frame: the length of the payload, its hash, then the
+/// payload.
+fn frame(entries: &[StorageEntry]) -> Vec<u8> {
+    let mut payload = Vec::new();
+    for entry in entries {
+        encode(entry, &mut payload);
+    }
+
+    let mut frame = Vec::with_capacity(FRAME_HEADER + payload.len());
+    frame.extend_from_slice(&(payload.len() as u64).to_be_bytes());
+    frame.extend_from_slice(Hasher::hash(&payload).as_ref());
+    frame.extend_from_slice(&payload);
+    frame
+}
+
+/// Decode the entries of the complete frames of `bytes`, returning them with the
+/// length of those frames.
+///
+/// Only the last frame may be torn by an interrupted write: a truncated frame, or a
+/// frame whose hash doesn't match and which ends the bytes, is left out. A frame
+/// whose hash doesn't match followed by more bytes is a corruption, and an error.
+fn read_frames(mut bytes: &[u8]) -> io::Result<(Vec<Vec<StorageEntry>>, usize)> {
+    let mut frames = Vec::new();
+    let mut len = 0;
+
+    while bytes.len() >= FRAME_HEADER {
+        let (header, rest) = bytes.split_at(FRAME_HEADER);
+        let payload_len = u64::from_be_bytes(header[..8].try_into().expect("8 bytes"));
+        let Some(payload) = usize::try_from(payload_len)
+            .ok()
+            .and_then(|payload_len| rest.get(..payload_len))
+        else {
+            break
+        };
+        if Hasher::hash(payload).as_ref() != &header[8..] {
+            if rest.len() > payload.len() {
+                return Err(invalid_data("Corrupted frame in the storage"))
+            }
+            break
+        }
+
+        let mut payload_bytes = payload;
+        let mut entries = Vec::new();
+        while !payload_bytes.is_empty() {
+            let entry = decode(&mut payload_bytes)
+                .ok_or_else(|| invalid_data("Invalid entry in the storage"))?;
+            entries.push(entry);
+        }
+        frames.push(entries);
+
+        len += FRAME_HEADER + payload.len();
+        bytes = &rest[payload.len()..];
+    }
+
+    Ok((frames, len))
+}
+
+/// Encode an entry: its table, its key, whether it's set, then its value.
+fn encode(entry: &StorageEntry, buf: &mut Vec<u8>) {
+    match entry {
+        StorageEntry::Contract(key, value) => {
+            buf.push(0);
+            buf.extend_from_slice(key.as_ref());
+            buf.push(value.is_some() as u8);
+            if let Some(code) = value {
+                buf.extend_from_slice(&(code.as_ref().len() as u64).to_be_bytes());
+                buf.extend_from_slice(code.as_ref());
+            }
+        }
+        StorageEntry::ContractInfo(key, value) => {
+            buf.push(1);
+            buf.extend_from_slice(key.as_ref());
+            buf.push(value.is_some() as u8);
+            if let Some((salt, root)) = value {
+                buf.extend_from_slice(salt.as_ref());
+                buf.extend_from_slice(root.as_ref());
+            }
+        }
+        StorageEntry::Balance(key, value) => {
+            buf.push(2);
+            buf.extend_from_slice(key.as_ref());
+            buf.push(value.is_some() as u8);
+            if let Some(balance) = value {
+                buf.extend_from_slice(&balance.to_be_bytes());
+            }
+        }
+        StorageEntry::State(key, value) => {
+            buf.push(3);
+            buf.extend_from_slice(key.as_ref());
+            buf.push(value.is_some() as u8);
+            if let Some(value) = value {
+                buf.extend_from_slice(value.as_ref());
+            }
+        }
+    }
+}
+
+/// Decode an entry encoded by [`encode`], advancing `buf` past it.
+fn decode(buf: &mut &[u8]) -> Option<StorageEntry> {
+    fn take<'a>(buf: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+        if buf.len() < len {
+            return None
+        }
+        let (bytes, rest) = buf.split_at(len);
+        *buf = rest;
+        Some(bytes)
+    }
+    fn array<const N: usize>(buf: &mut &[u8]) -> Option<[u8; N]> {
+        take(buf, N)?.try_into().ok()
+    }
+
+    let [table] = array::<1>(buf)?;
+    let entry = match table {
+        0 => {
+            let key = ContractId::new(array(buf)?);
+            let value = match array::<1>(buf)? {
+                [0] => None,
+                _ => {
+                    let len = usize::try_from(Word::from_be_bytes(array(buf)?)).ok()?;
+                    Some(Contract::from(take(buf, len)?))
+                }
+            };
+            StorageEntry::Contract(key, value)
+        }
+        1 => {
+            let key = ContractId::new(array(buf)?);
+            let value = match array::<1>(buf)? {
+                [0] => None,
+                _ => Some((array::<32>(buf)?.into(), array::<32>(buf)?.into())),
+            };
+            StorageEntry::ContractInfo(key, value)
+        }
+        2 => {
+            let key = ContractsAssetKey::from_array(array(buf)?);
+            let value = match array::<1>(buf)? {
+                [0] => None,
+                _ => Some(Word::from_be_bytes(array(buf)?)),
+            };
+            StorageEntry::Balance(key, value)
+        }
+        3 => {
+            let key = ContractsStateKey::from_array(array(buf)?);
+            let value = match array::<1>(buf)? {
+                [0] => None,
+                _ => Some(array::<32>(buf)?.into()),
+            };
+            StorageEntry::State(key, value)
+        }
+        _ => return None,
+    };
+
+    Some(entry)
+}
+
+impl<Type: Mappable> StorageInspect<Type> for FileStorage
+where
+    MemoryStorage: StorageInspect<Type>,
+{
+    type Error = <MemoryStorage as StorageInspect<Type>>::Error;
+
+    fn get(
+        &self,
+        key: &Type::Key,
+    ) -> Result<Option<Cow<'_, Type::OwnedValue>>, Self::Error> {
+        <MemoryStorage as StorageInspect<Type>>::get(&self.memory, key)
+    }
+
+    fn contains_key(&self, key: &Type::Key) -> Result<bool, Self::Error> {
+        <MemoryStorage as StorageInspect<Type>>::contains_key(&self.memory, key)
+    }
+}
+
+impl<Type: Mappable> StorageMutate<Type> for FileStorage
+where
+    MemoryStorage: StorageMutate<Type>,
+{
+    fn insert(
+        &mut self,
+        key: &Type::Key,
+        value: &Type::Value,
+    ) -> Result<Option<Type::OwnedValue>, Self::Error> {
+        <MemoryStorage as StorageMutate<Type>>::insert(&mut self.memory, key, value)
+    }
+
+    fn remove(
+        &mut self,
+        key: &Type::Key,
+    ) -> Result<Option<Type::OwnedValue>, Self::Error> {
+        <MemoryStorage as StorageMutate<Type>>::remove(&mut self.memory, key)
+    }
+}
+
+impl StorageSize<ContractsRawCode> for FileStorage {
+    fn size_of_value(&self, key: &ContractId) -> Result<Option<usize>, Infallible> {
+        self.memory.size_of_value(key)
+    }
+}
+
+impl StorageRead<ContractsRawCode> for FileStorage {
+    fn read(
+        &self,
+        key: &ContractId,
+        buf: &mut [u8],
+    ) -> Result<Option<usize>, Infallible> {
+        self.memory.read(key, buf)
+    }
+
+    fn read_alloc(&self, key: &ContractId) -> Result<Option<Vec<u8>>, Infallible> {
+        self.memory.read_alloc(key)
+    }
+}
+
+impl StorageWrite<ContractsRawCode> for FileStorage {
+    fn write(&mut self, key: &ContractId, buf: Vec<u8>) -> Result<usize, Infallible> {
+        self.memory.write(key, buf)
+    }
+
+    fn replace(
+        &mut self,
+        key: &ContractId,
+        buf: Vec<u8>,
+    ) -> Result<(usize, Option<Vec<u8>>), Infallible> {
+        self.memory.replace(key, buf)
+    }
+
+    fn take(&mut self, key: &ContractId) -> Result<Option<Vec<u8>>, Infallible> {
+        self.memory.take(key)
+    }
+}
+
+impl<Key, Type: Mappable> MerkleRootStorage<Key, Type> for FileStorage
+where
+    MemoryStorage: MerkleRootStorage<Key, Type>,
+{
+    fn root(&self, key: &Key) -> Result<MerkleRoot, Self::Error> {
+        <MemoryStorage as MerkleRootStorage<Key, Type>>::root(&self.memory, key)
+    }
+}
+
+impl ContractsAssetsStorage for FileStorage {}
+
+impl InterpreterStorage for FileStorage {
+    type DataError = Infallible;
+
+    fn block_height(&self) -> Result<BlockHeight, Infallible> {
+        Ok(self.block_height)
+    }
+
+    fn timestamp(&self, height: BlockHeight) -> Result<Word, Infallible> {
+        self.memory.timestamp(height)
+    }
+
+    fn block_hash(&self, block_height: BlockHeight) -> Result<Bytes32, Infallible> {
+        self.memory.block_hash(block_height)
+    }
+
+    fn coinbase(&self) -> Result<ContractId, Infallible> {
+        Ok(self.memory.block_header(self.block_height)?.coinbase)
+    }
+
+    fn merkle_contract_state_range(
+        &self,
+        id: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> Result<Vec<Option<Cow<'_, Bytes32>>>, Infallible> {
+        self.memory
+            .merkle_contract_state_range(id, start_key, range)
+    }
+
+    fn merkle_contract_state_insert_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        values: &[Bytes32],
+    ) -> Result<Option<()>, Infallible> {
+        self.memory
+            .merkle_contract_state_insert_range(contract, start_key, values)
+    }
+
+    fn merkle_contract_state_remove_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> Result<Option<()>, Infallible> {
+        self.memory
+            .merkle_contract_state_remove_range(contract, start_key, range)
+    }
+
+    fn checkpoint(&mut self) -> Result<StorageCheckpoint, Infallible> {
+        self.memory.checkpoint()
+    }
+
+    fn revert_to(&mut self, checkpoint: StorageCheckpoint) -> Result<(), Infallible> {
+        self.memory.revert_to(checkpoint)
+    }
+
+    fn release(&mut self, checkpoint: StorageCheckpoint) -> Result<(), Infallible> {
+        self.memory.release(checkpoint)
+    }
+
+    fn prefetch(&mut self, access_list: &AccessList) -> Result<(), Infallible> {
+        self.memory.prefetch(access_list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{
+        ContractsAssets,
+        ContractsInfo,
+        ContractsState,
+    };
+    use fuel_storage::{
+        StorageAsMut,
+        StorageAsRef,
+    };
+    use fuel_types::{
+        AssetId,
+        Salt,
+    };
+    use std::sync::atomic::{
+        AtomicUsize,
+        Ordering,
+    };
+
+    /// Empty directory, removed when dropped.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            static COUNTER: AtomicUsize = AtomicUsize::new(0);
+            let dir = std::env::temp_dir().join(format!(
+                "fuel-vm-file-storage-{}-{}",
+                std::process::id(),
+                COUNTER.fetch_add(1, Ordering::Relaxed)
+            ));
+            let _ = fs::remove_dir_all(&dir);
+            Self(dir)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn open(dir: &TempDir) -> FileStorage {
+        FileStorage::open(&dir.0, Default::default(), Default::default())
+            .expect("Failed to open the storage")
+    }
+
+    const CONTRACT: ContractId = ContractId::new([1; 32]);
+
+    fn state_key(k: u8) -> ContractsStateKey {
+        ContractsStateKey::new(&CONTRACT, &[k; 32].into())
+    }
+
+    fn asset_key(k: u8) -> ContractsAssetKey {
+        ContractsAssetKey::new(&CONTRACT, &AssetId::new([k; 32]))
+    }
+
+    /// Write a contract with its code, info, a balance and two state slots.
+    fn write_contract(storage: &mut FileStorage) {
+        let code = vec![7u8; 100];
+        storage
+            .storage_contract_insert(&CONTRACT, &code.into())
+            .unwrap();
+        storage
+            .storage_as_mut::<ContractsInfo>()
+            .insert(&CONTRACT, &(Salt::new([2; 32]), Bytes32::new([3; 32])))
+            .unwrap();
+        storage
+            .storage_as_mut::<ContractsAssets>()
+            .insert(&asset_key(4), &50)
+            .unwrap();
+        storage
+            .storage_as_mut::<ContractsState>()
+            .insert(&state_key(5), &Bytes32::new([6; 32]))
+            .unwrap();
+        storage
+            .storage_as_mut::<ContractsState>()
+            .insert(&state_key(8), &Bytes32::new([9; 32]))
+            .unwrap();
+    }
+
+    fn assert_same_state(a: &FileStorage, b: &FileStorage) {
+        assert_eq!(
+            a.memory.entries().collect::<Vec<_>>(),
+            b.memory.entries().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            MerkleRootStorage::<ContractId, ContractsState>::root(a, &CONTRACT),
+            MerkleRootStorage::<ContractId, ContractsState>::root(b, &CONTRACT)
+        );
+        assert_eq!(
+            MerkleRootStorage::<ContractId, ContractsAssets>::root(a, &CONTRACT),
+            MerkleRootStorage::<ContractId, ContractsAssets>::root(b, &CONTRACT)
+        );
+    }
+
+    #[test]
+    fn committed_changes_are_kept_on_reopen() {
+        let dir = TempDir::new();
+        let mut storage = open(&dir);
+        write_contract(&mut storage);
+        storage.commit().unwrap();
+        storage
+            .storage_as_mut::<ContractsState>()
+            .remove(&state_key(8))
+            .unwrap();
+        storage.commit().unwrap();
+
+        let reopened = open(&dir);
+        assert_same_state(&storage, &reopened);
+        assert_eq!(
+            reopened.read_alloc(&CONTRACT).unwrap(),
+            Some(vec![7u8; 100])
+        );
+        assert!(!reopened
+            .storage_as_ref::<ContractsState>()
+            .contains_key(&state_key(8))
+            .unwrap());
+    }
+
+    #[test]
+    fn uncommitted_changes_are_lost_on_reopen() {
+        let dir = TempDir::new();
+        let mut storage = open(&dir);
+        write_contract(&mut storage);
+        storage.commit().unwrap();
+        let committed = open(&dir);
+
+        storage
+            .storage_as_mut::<ContractsAssets>()
+            .insert(&asset_key(4), &10)
+            .unwrap();
+        storage
+            .storage_as_mut::<ContractsState>()
+            .insert(&state_key(10), &Bytes32::new([11; 32]))
+            .unwrap();
+        drop(storage);
+
+        assert_same_state(&committed, &open(&dir));
+    }
+
+    #[test]
+    fn reverted_changes_are_not_committed() {
+        let dir = TempDir::new();
+        let mut storage = open(&dir);
+        write_contract(&mut storage);
+        storage.commit().unwrap();
+        let committed = open(&dir);
+
+        storage
+            .storage_as_mut::<ContractsAssets>()
+            .insert(&asset_key(4), &10)
+            .unwrap();
+        storage.revert();
+        storage.commit().unwrap();
+
+        assert_same_state(&committed, &storage);
+        assert_same_state(&committed, &open(&dir));
+    }
+
+    #[test]
+    fn torn_batch_is_discarded() {
+        let dir = TempDir::new();
+        let mut storage = open(&dir);
+        write_contract(&mut storage);
+        storage.commit().unwrap();
+        let committed = open(&dir);
+        let len = fs::metadata(dir.0.join(LOG)).unwrap().len();
+
+        storage
+            .storage_as_mut::<ContractsAssets>()
+            .insert(&asset_key(4), &10)
+            .unwrap();
+        storage.commit().unwrap();
+        drop(storage);
+
+        // Simulate a crash in the middle of the write of the second batch.
+        let log = OpenOptions::new()
+            .write(true)
+            .open(dir.0.join(LOG))
+            .unwrap();
+        log.set_len(len + FRAME_HEADER as u64 + 3).unwrap();
+        drop(log);
+
+        let mut reopened = open(&dir);
+        assert_same_state(&committed, &reopened);
+        assert_eq!(fs::metadata(dir.0.join(LOG)).unwrap().len(), len);
+
+        // New batches are appended after the last complete one.
+        reopened
+            .storage_as_mut::<ContractsAssets>()
+            .insert(&asset_key(4), &20)
+            .unwrap();
+        reopened.commit().unwrap();
+        assert_same_state(&reopened, &open(&dir));
+    }
+
+    #[test]
+    fn batches_are_written_over_a_torn_batch() {
+        let dir = TempDir::new();
+        let mut storage = open(&dir);
+        write_contract(&mut storage);
+        storage.commit().unwrap();
+
+        // Simulate a write of the second batch failing after a part of it.
+        storage.log.write_all(&[0xff; FRAME_HEADER + 3]).unwrap();
+
+        storage
+            .storage_as_mut::<ContractsAssets>()
+            .insert(&asset_key(4), &10)
+            .unwrap();
+        storage.commit().unwrap();
+        storage
+            .storage_as_mut::<ContractsState>()
+            .remove(&state_key(8))
+            .unwrap();
+        storage.commit().unwrap();
+
+        assert_same_state(&storage, &open(&dir));
+    }
+
+    #[test]
+    fn corrupted_last_batch_is_discarded() {
+        let dir = TempDir::new();
+        let mut storage = open(&dir);
+        write_contract(&mut storage);
+        storage.commit().unwrap();
+        let committed = open(&dir);
+
+        storage
+            .storage_as_mut::<ContractsAssets>()
+            .insert(&asset_key(4), &10)
+            .unwrap();
+        storage.commit().unwrap();
+        drop(storage);
+
+        let mut log = fs::read(dir.0.join(LOG)).unwrap();
+        *log.last_mut().unwrap() ^= 1;
+        fs::write(dir.0.join(LOG), log).unwrap();
+
+        assert_same_state(&committed, &open(&dir));
+    }
+
+    #[test]
+    fn corrupted_batch_followed_by_batches_is_an_error() {
+        let dir = TempDir::new();
+        let mut storage = open(&dir);
+        write_contract(&mut storage);
+        storage.commit().unwrap();
+        let len = fs::metadata(dir.0.join(LOG)).unwrap().len() as usize;
+
+        storage
+            .storage_as_mut::<ContractsAssets>()
+            .insert(&asset_key(4), &10)
+            .unwrap();
+        storage.commit().unwrap();
+        drop(storage);
+
+        let mut log = fs::read(dir.0.join(LOG)).unwrap();
+        log[len - 1] ^= 1;
+        fs::write(dir.0.join(LOG), &log).unwrap();
+
+        let error = FileStorage::open(&dir.0, Default::default(), Default::default())
+            .expect_err("The corrupted log was opened");
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(fs::read(dir.0.join(LOG)).unwrap(), log);
+    }
+
+    #[test]
+    fn compacted_storage_is_reopened() {
+        let dir = TempDir::new();
+        let mut storage = open(&dir);
+        write_contract(&mut storage);
+        storage.commit().unwrap();
+        storage
+            .storage_as_mut::<ContractsState>()
+            .remove(&state_key(5))
+            .unwrap();
+        storage.compact().unwrap();
+
+        assert_eq!(fs::metadata(dir.0.join(LOG)).unwrap().len(), 0);
+        assert!(!dir.0.join(SNAPSHOT_TMP).exists());
+        assert_same_state(&storage, &open(&dir));
+
+        storage
+            .storage_as_mut::<ContractsAssets>()
+            .insert(&asset_key(12), &1)
+            .unwrap();
+        storage.commit().unwrap();
+        assert_same_state(&storage, &open(&dir));
+    }
+
+    #[test]
+    fn corrupted_snapshot_is_an_error() {
+        let dir = TempDir::new();
+        let mut storage = open(&dir);
+        write_contract(&mut storage);
+        storage.compact().unwrap();
+        drop(storage);
+
+        let mut snapshot = fs::read(dir.0.join(SNAPSHOT)).unwrap();
+        *snapshot.last_mut().unwrap() ^= 1;
+        fs::write(dir.0.join(SNAPSHOT), snapshot).unwrap();
+
+        let error = FileStorage::open(&dir.0, Default::default(), Default::default())
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+}
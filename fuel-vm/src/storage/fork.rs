@@ -0,0 +1,500 @@
+//! Storage forking the state of a chain served by an upstream provider.
+
+use alloc::{
+    borrow::Cow,
+    collections::BTreeSet,
+    vec::Vec,
+};
+use core::{
+    cell::RefCell,
+    convert::Infallible,
+    fmt::Debug,
+};
+
+use fuel_storage::{
+    Mappable,
+    MerkleRoot,
+    MerkleRootStorage,
+    StorageInspect,
+    StorageMutate,
+    StorageRead,
+    StorageSize,
+};
+use fuel_tx::Contract;
+use fuel_types::{
+    AssetId,
+    BlockHeight,
+    Bytes32,
+    ContractId,
+    Salt,
+    Word,
+};
+
+use crate::prelude::{
+    InterpreterError,
+    RuntimeError,
+};
+
+use super::{
+    access_list::storage_range,
+    memory::StorageEntry,
+    AccessList,
+    BlockHeaderProvider,
+    ContractsAssetKey,
+    ContractsAssets,
+    ContractsAssetsStorage,
+    ContractsInfo,
+    ContractsRawCode,
+    ContractsState,
+    ContractsStateKey,
+    InterpreterStorage,
+    MemoryStorage,
+    StorageCheckpoint,
+};
+
+/// Upstream provider of the state of a chain, read by a [`ForkedStorage`].
+///
+/// It is implemented for the types reading the contract tables and the block
+/// headers with the same error, such as [`MemoryStorage`].
+pub trait ForkProvider:
+    StorageInspect<ContractsRawCode, Error = Self::DataError>
+    + StorageInspect<ContractsInfo, Error = Self::DataError>
+    + StorageInspect<ContractsState, Error = Self::DataError>
+    + StorageInspect<ContractsAssets, Error = Self::DataError>
+    + BlockHeaderProvider<Error = Self::DataError>
+{
+    /// Error of the provider.
+    type DataError: Debug;
+}
+
+impl<P, E> ForkProvider for P
+where
+    P: StorageInspect<ContractsRawCode, Error = E>
+        + StorageInspect<ContractsInfo, Error = E>
+        + StorageInspect<ContractsState, Error = E>
+        + StorageInspect<ContractsAssets, Error = E>
+        + BlockHeaderProvider<Error = E>,
+    E: Debug,
+{
+    type DataError = E;
+}
+
+/// Error of a [`ForkedStorage`].
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+#[display(bound = "ProviderError: Debug")]
+pub enum ForkedStorageError<ProviderError> {
+    /// The Merkle roots of the contracts can't be computed from the fetched entries.
+    #[display(fmt = "The Merkle roots are unavailable")]
+    RootUnavailable,
+    /// The upstream provider failed to fetch an entry.
+    #[display(fmt = "Failed to fetch the storage entry: {_0:?}")]
+    Provider(ProviderError),
+}
+
+impl<E> From<ForkedStorageError<E>> for InterpreterError<ForkedStorageError<E>> {
+    fn from(error: ForkedStorageError<E>) -> Self {
+        InterpreterError::Storage(error)
+    }
+}
+
+impl<E> From<ForkedStorageError<E>> for RuntimeError<ForkedStorageError<E>> {
+    fn from(error: ForkedStorageError<E>) -> Self {
+        RuntimeError::Storage(error)
+    }
+}
+
+/// Key of an entry fetched from the provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum FetchedKey {
+    Contract(ContractId),
+    ContractInfo(ContractId),
+    State(ContractsStateKey),
+    Balance(ContractsAssetKey),
+}
+
+/// The tables read from the provider.
+pub(crate) trait ForkedTable: Mappable {
+    /// Key of the entry in the fetched entries.
+    fn fetched_key(key: &Self::Key) -> FetchedKey;
+
+    /// Entry of the storage with the fetched value.
+    fn entry(key: &Self::Key, value: Option<Self::OwnedValue>) -> StorageEntry;
+}
+
+impl ForkedTable for ContractsRawCode {
+    fn fetched_key(key: &ContractId) -> FetchedKey {
+        FetchedKey::Contract(*key)
+    }
+
+    fn entry(key: &ContractId, value: Option<Contract>) -> StorageEntry {
+        StorageEntry::Contract(*key, value)
+    }
+}
+
+impl ForkedTable for ContractsInfo {
+    fn fetched_key(key: &ContractId) -> FetchedKey {
+        FetchedKey::ContractInfo(*key)
+    }
+
+    fn entry(key: &ContractId, value: Option<(Salt, Bytes32)>) -> StorageEntry {
+        StorageEntry::ContractInfo(*key, value)
+    }
+}
+
+impl ForkedTable for ContractsState {
+    fn fetched_key(key: &ContractsStateKey) -> FetchedKey {
+        FetchedKey::State(*key)
+    }
+
+    fn entry(key: &ContractsStateKey, value: Option<Bytes32>) -> StorageEntry {
+        StorageEntry::State(*key, value)
+    }
+}
+
+impl ForkedTable for ContractsAssets {
+    fn fetched_key(key: &ContractsAssetKey) -> FetchedKey {
+        FetchedKey::Balance(*key)
+    }
+
+    fn entry(key: &ContractsAssetKey, value: Option<Word>) -> StorageEntry {
+        StorageEntry::Balance(*key, value)
+    }
+}
+
+/// Storage forking the state of a chain at a block height.
+///
+/// The entries of the contract tables are fetched lazily from the upstream
+/// [`ForkProvider`] on their first access, and cached. The writes stay local: the
+/// provider is never written to. The block headers are read from the provider.
+///
+/// The state of the fork can be overridden with [`Self::set_contract_code`],
+/// [`Self::set_storage_slot`] and [`Self::set_balance`], to replay transactions
+/// against patched contracts. The overrides are local writes, like the changes of
+/// the transactions.
+///
+/// The Merkle roots of the contracts aren't available, as only the accessed entries
+/// are fetched.
+#[derive(Debug)]
+pub struct ForkedStorage<P> {
+    provider: P,
+    block_height: BlockHeight,
+    /// The fetched entries, with the local writes on top of them.
+    local: RefCell<MemoryStorage>,
+    fetched: RefCell<BTreeSet<FetchedKey>>,
+}
+
+impl<P> ForkedStorage<P> {
+    /// Fork the state of the chain served by `provider` at `block_height`.
+    pub fn new(provider: P, block_height: BlockHeight) -> Self {
+        Self {
+            provider,
+            block_height,
+            local: RefCell::new(MemoryStorage::new(block_height, ContractId::default())),
+            fetched: RefCell::default(),
+        }
+    }
+
+    /// The upstream provider of the fork.
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    /// Remove the fork, returning the provider.
+    pub fn into_inner(self) -> P {
+        self.provider
+    }
+}
+
+impl<P> ForkedStorage<P>
+where
+    P: ForkProvider,
+{
+    /// Fetch the entry of `key` from the provider if it wasn't fetched yet.
+    fn load<Type: ForkedTable>(
+        &self,
+        key: &Type::Key,
+    ) -> Result<(), ForkedStorageError<P::DataError>>
+    where
+        P: StorageInspect<Type, Error = P::DataError>,
+    {
+        let fetched_key = Type::fetched_key(key);
+        if self.fetched.borrow().contains(&fetched_key) {
+            return Ok(())
+        }
+
+        let value = StorageInspect::<Type>::get(&self.provider, key)
+            .map_err(ForkedStorageError::Provider)?
+            .map(Cow::into_owned);
+        // The entry wasn't changed locally, so it is set as persisted and is kept
+        // when the local changes are reverted.
+        self.local
+            .borrow_mut()
+            .set_persisted_entry(Type::entry(key, value));
+        self.fetched.borrow_mut().insert(fetched_key);
+
+        Ok(())
+    }
+
+    /// Fetch `range` consecutive storage slots of the contract.
+    fn load_range(
+        &self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> Result<(), ForkedStorageError<P::DataError>> {
+        let range = usize::try_from(range).unwrap_or(usize::MAX);
+        storage_range(start_key, range).try_for_each(|key| {
+            self.load::<ContractsState>(&ContractsStateKey::new(contract, &key))
+        })
+    }
+
+    /// Replace the code of the contract, keeping its salt.
+    pub fn set_contract_code(
+        &mut self,
+        contract: &ContractId,
+        code: Contract,
+    ) -> Result<(), ForkedStorageError<P::DataError>> {
+        let salt = StorageInspect::<ContractsInfo>::get(self, contract)?
+            .map(|info| info.0)
+            .unwrap_or_default();
+        let root = code.root();
+        StorageMutate::<ContractsRawCode>::insert(self, contract, code.as_ref())?;
+        StorageMutate::<ContractsInfo>::insert(self, contract, &(salt, root))?;
+        Ok(())
+    }
+
+    /// Set a storage slot of the contract.
+    pub fn set_storage_slot(
+        &mut self,
+        contract: &ContractId,
+        key: &Bytes32,
+        value: &Bytes32,
+    ) -> Result<(), ForkedStorageError<P::DataError>> {
+        let key = ContractsStateKey::new(contract, key);
+        StorageMutate::<ContractsState>::insert(self, &key, value)?;
+        Ok(())
+    }
+
+    /// Set the balance of an asset of the contract.
+    pub fn set_balance(
+        &mut self,
+        contract: &ContractId,
+        asset_id: &AssetId,
+        amount: Word,
+    ) -> Result<(), ForkedStorageError<P::DataError>> {
+        let key = ContractsAssetKey::new(contract, asset_id);
+        StorageMutate::<ContractsAssets>::insert(self, &key, &amount)?;
+        Ok(())
+    }
+}
+
+impl<Type: ForkedTable, P> StorageInspect<Type> for ForkedStorage<P>
+where
+    P: ForkProvider + StorageInspect<Type, Error = <P as ForkProvider>::DataError>,
+    MemoryStorage: StorageInspect<Type, Error = Infallible>,
+{
+    type Error = ForkedStorageError<<P as ForkProvider>::DataError>;
+
+    fn get(
+        &self,
+        key: &Type::Key,
+    ) -> Result<Option<Cow<'_, Type::OwnedValue>>, Self::Error> {
+        self.load::<Type>(key)?;
+        let local = self.local.borrow();
+        let value = StorageInspect::<Type>::get(&*local, key).expect("Infallible");
+        Ok(value.map(|value| Cow::Owned(value.into_owned())))
+    }
+
+    fn contains_key(&self, key: &Type::Key) -> Result<bool, Self::Error> {
+        self.load::<Type>(key)?;
+        let local = self.local.borrow();
+        Ok(StorageInspect::<Type>::contains_key(&*local, key).expect("Infallible"))
+    }
+}
+
+impl<Type: ForkedTable, P> StorageMutate<Type> for ForkedStorage<P>
+where
+    P: ForkProvider + StorageInspect<Type, Error = <P as ForkProvider>::DataError>,
+    MemoryStorage: StorageMutate<Type> + StorageInspect<Type, Error = Infallible>,
+{
+    fn insert(
+        &mut self,
+        key: &Type::Key,
+        value: &Type::Value,
+    ) -> Result<Option<Type::OwnedValue>, Self::Error> {
+        self.load::<Type>(key)?;
+        Ok(
+            StorageMutate::<Type>::insert(self.local.get_mut(), key, value)
+                .expect("Infallible"),
+        )
+    }
+
+    fn remove(
+        &mut self,
+        key: &Type::Key,
+    ) -> Result<Option<Type::OwnedValue>, Self::Error> {
+        self.load::<Type>(key)?;
+        Ok(StorageMutate::<Type>::remove(self.local.get_mut(), key).expect("Infallible"))
+    }
+}
+
+impl<P> StorageSize<ContractsRawCode> for ForkedStorage<P>
+where
+    P: ForkProvider,
+{
+    fn size_of_value(&self, key: &ContractId) -> Result<Option<usize>, Self::Error> {
+        self.load::<ContractsRawCode>(key)?;
+        Ok(self.local.borrow().size_of_value(key).expect("Infallible"))
+    }
+}
+
+impl<P> StorageRead<ContractsRawCode> for ForkedStorage<P>
+where
+    P: ForkProvider,
+{
+    fn read(
+        &self,
+        key: &ContractId,
+        buf: &mut [u8],
+    ) -> Result<Option<usize>, Self::Error> {
+        self.load::<ContractsRawCode>(key)?;
+        Ok(self.local.borrow().read(key, buf).expect("Infallible"))
+    }
+
+    fn read_alloc(&self, key: &ContractId) -> Result<Option<Vec<u8>>, Self::Error> {
+        self.load::<ContractsRawCode>(key)?;
+        Ok(self.local.borrow().read_alloc(key).expect("Infallible"))
+    }
+}
+
+impl<Type: ForkedTable, P> MerkleRootStorage<ContractId, Type> for ForkedStorage<P>
+where
+    P: ForkProvider + StorageInspect<Type, Error = <P as ForkProvider>::DataError>,
+    MemoryStorage: StorageMutate<Type> + StorageInspect<Type, Error = Infallible>,
+{
+    fn root(&self, _key: &ContractId) -> Result<MerkleRoot, Self::Error> {
+        Err(ForkedStorageError::RootUnavailable)
+    }
+}
+
+impl<P> ContractsAssetsStorage for ForkedStorage<P> where P: ForkProvider {}
+
+impl<P> InterpreterStorage for ForkedStorage<P>
+where
+    P: ForkProvider,
+{
+    type DataError = ForkedStorageError<P::DataError>;
+
+    fn block_height(&self) -> Result<BlockHeight, Self::DataError> {
+        Ok(self.block_height)
+    }
+
+    fn timestamp(&self, height: BlockHeight) -> Result<Word, Self::DataError> {
+        self.provider
+            .block_header(height)
+            .map(|header| header.timestamp)
+            .map_err(ForkedStorageError::Provider)
+    }
+
+    fn block_hash(&self, block_height: BlockHeight) -> Result<Bytes32, Self::DataError> {
+        self.provider
+            .block_header(block_height)
+            .map(|header| header.hash)
+            .map_err(ForkedStorageError::Provider)
+    }
+
+    fn coinbase(&self) -> Result<ContractId, Self::DataError> {
+        self.provider
+            .block_header(self.block_height)
+            .map(|header| header.coinbase)
+            .map_err(ForkedStorageError::Provider)
+    }
+
+    fn merkle_contract_state_range(
+        &self,
+        id: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> Result<Vec<Option<Cow<'_, Bytes32>>>, Self::DataError> {
+        self.load_range(id, start_key, range)?;
+        let local = self.local.borrow();
+        let values = local
+            .merkle_contract_state_range(id, start_key, range)
+            .expect("Infallible");
+        Ok(values
+            .into_iter()
+            .map(|value| value.map(|value| Cow::Owned(value.into_owned())))
+            .collect())
+    }
+
+    fn merkle_contract_state_insert_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        values: &[Bytes32],
+    ) -> Result<Option<()>, Self::DataError> {
+        self.load_range(contract, start_key, values.len() as Word)?;
+        Ok(self
+            .local
+            .get_mut()
+            .merkle_contract_state_insert_range(contract, start_key, values)
+            .expect("Infallible"))
+    }
+
+    fn merkle_contract_state_remove_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> Result<Option<()>, Self::DataError> {
+        self.load_range(contract, start_key, range)?;
+        Ok(self
+            .local
+            .get_mut()
+            .merkle_contract_state_remove_range(contract, start_key, range)
+            .expect("Infallible"))
+    }
+
+    fn checkpoint(&mut self) -> Result<StorageCheckpoint, Self::DataError> {
+        Ok(self.local.get_mut().checkpoint().expect("Infallible"))
+    }
+
+    fn revert_to(
+        &mut self,
+        checkpoint: StorageCheckpoint,
+    ) -> Result<(), Self::DataError> {
+        self.local
+            .get_mut()
+            .revert_to(checkpoint)
+            .expect("Infallible");
+        Ok(())
+    }
+
+    fn release(&mut self, checkpoint: StorageCheckpoint) -> Result<(), Self::DataError> {
+        self.local
+            .get_mut()
+            .release(checkpoint)
+            .expect("Infallible");
+        Ok(())
+    }
+
+    fn prefetch(&mut self, access_list: &AccessList) -> Result<(), Self::DataError> {
+        for contract in &access_list.contracts {
+            self.load::<ContractsRawCode>(contract)?;
+            self.load::<ContractsInfo>(contract)?;
+        }
+        for (contract, keys) in &access_list.storage_slots {
+            for key in keys {
+                self.load::<ContractsState>(&ContractsStateKey::new(contract, key))?;
+            }
+        }
+        for (contract, assets) in &access_list.assets {
+            for asset_id in assets {
+                self.load::<ContractsAssets>(&ContractsAssetKey::new(
+                    contract, asset_id,
+                ))?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -27,6 +27,7 @@ use crate::{
         RuntimeError,
     },
     storage::{
+        AccessList,
         ContractsAssets,
         ContractsInfo,
         ContractsRawCode,
@@ -42,6 +43,23 @@ use core::ops::{
     DerefMut,
 };
 
+/// Checkpoint of the changes made to an [`InterpreterStorage`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StorageCheckpoint(usize);
+
+impl StorageCheckpoint {
+    /// Create a checkpoint from its depth, the number of checkpoints taken before it
+    /// and not released yet.
+    pub const fn new(depth: usize) -> Self {
+        Self(depth)
+    }
+
+    /// Number of checkpoints taken before this one and not released yet.
+    pub const fn depth(&self) -> usize {
+        self.0
+    }
+}
+
 /// When this trait is implemented, the underlying interpreter is guaranteed to
 /// have full functionality
 pub trait InterpreterStorage:
@@ -211,6 +229,31 @@ pub trait InterpreterStorage:
         start_key: &Bytes32,
         range: Word,
     ) -> Result<Option<()>, Self::DataError>;
+
+    /// Start recording the changes made to the storage, to undo them with
+    /// [`Self::revert_to`] or keep them with [`Self::release`].
+    ///
+    /// Checkpoints are nested: reverting to or releasing a checkpoint does the same
+    /// with the checkpoints taken after it. The interpreter takes a checkpoint before
+    /// running a script, and reverts to it if the script reverts or panics, so every
+    /// storage must be able to undo its changes.
+    fn checkpoint(&mut self) -> Result<StorageCheckpoint, Self::DataError>;
+
+    /// Undo the changes made since `checkpoint`, and release it.
+    fn revert_to(&mut self, checkpoint: StorageCheckpoint)
+        -> Result<(), Self::DataError>;
+
+    /// Keep the changes made since `checkpoint`, and release it.
+    fn release(&mut self, checkpoint: StorageCheckpoint) -> Result<(), Self::DataError>;
+
+    /// Load the entries of the access list ahead of the execution of a transaction.
+    ///
+    /// Backends with slow random reads can fetch the entries in a single batch and
+    /// cache them. The default implementation does nothing.
+    fn prefetch(&mut self, access_list: &AccessList) -> Result<(), Self::DataError> {
+        let _ = access_list;
+        Ok(())
+    }
 }
 
 /// Storage operations for contract assets.
@@ -324,4 +367,23 @@ where
             range,
         )
     }
+
+    fn checkpoint(&mut self) -> Result<StorageCheckpoint, Self::DataError> {
+        <S as InterpreterStorage>::checkpoint(self.deref_mut())
+    }
+
+    fn revert_to(
+        &mut self,
+        checkpoint: StorageCheckpoint,
+    ) -> Result<(), Self::DataError> {
+        <S as InterpreterStorage>::revert_to(self.deref_mut(), checkpoint)
+    }
+
+    fn release(&mut self, checkpoint: StorageCheckpoint) -> Result<(), Self::DataError> {
+        <S as InterpreterStorage>::release(self.deref_mut(), checkpoint)
+    }
+
+    fn prefetch(&mut self, access_list: &AccessList) -> Result<(), Self::DataError> {
+        <S as InterpreterStorage>::prefetch(self.deref_mut(), access_list)
+    }
 }
@@ -1,17 +1,31 @@
-use crate::{
-    crypto,
-    storage::{
-        ContractsAssetKey,
-        ContractsAssets,
-        ContractsInfo,
-        ContractsRawCode,
-        ContractsState,
-        ContractsStateKey,
-        InterpreterStorage,
-    },
+use crate::storage::{
+    import_state,
+    BlockHeader,
+    BlockHeaderProvider,
+    ContractBalance,
+    ContractSnapshot,
+    ContractsAssetKey,
+    ContractsAssets,
+    ContractsInfo,
+    ContractsRawCode,
+    ContractsState,
+    ContractsStateKey,
+    InterpreterStorage,
+    MemoryBlockHeaders,
+    SnapshotError,
+    StorageCheckpoint,
+    StorageSnapshot,
 };
 
 use fuel_crypto::Hasher;
+use fuel_merkle::{
+    common::StorageMap,
+    sparse::{
+        self,
+        MerkleTreeKey,
+        Primitive,
+    },
+};
 use fuel_storage::{
     Mappable,
     MerkleRoot,
@@ -23,15 +37,18 @@ use fuel_storage::{
     StorageSize,
     StorageWrite,
 };
-use fuel_tx::Contract;
+use fuel_tx::{
+    Contract,
+    StorageSlot,
+};
 use fuel_types::{
+    AssetId,
     BlockHeight,
     Bytes32,
     ContractId,
     Salt,
     Word,
 };
-use itertools::Itertools;
 use tai64::Tai64;
 
 use alloc::{
@@ -49,6 +66,225 @@ struct MemoryStorageInner {
     balances: BTreeMap<ContractsAssetKey, Word>,
     contract_state: BTreeMap<ContractsStateKey, Bytes32>,
     contract_code_root: BTreeMap<ContractId, (Salt, Bytes32)>,
+    balance_trees: BTreeMap<ContractId, ContractTree>,
+    state_trees: BTreeMap<ContractId, ContractTree>,
+}
+
+impl MemoryStorageInner {
+    fn undo(&mut self, entry: StorageEntry) {
+        match entry {
+            StorageEntry::Contract(key, value) => {
+                set(&mut self.contracts, key, value);
+            }
+            StorageEntry::ContractInfo(key, value) => {
+                set(&mut self.contract_code_root, key, value);
+            }
+            StorageEntry::Balance(key, value) => {
+                self.set_balance(key, value);
+            }
+            StorageEntry::State(key, value) => {
+                self.set_state(key, value);
+            }
+        }
+    }
+
+    /// Set a balance, updating the balance tree of the contract.
+    fn set_balance(
+        &mut self,
+        key: ContractsAssetKey,
+        value: Option<Word>,
+    ) -> Option<Word> {
+        let previous = set(&mut self.balances, key, value);
+        let contract = key.contract_id();
+        let balances = self.balances.range(
+            ContractsAssetKey::new(contract, &AssetId::zeroed())
+                ..=ContractsAssetKey::new(contract, &AssetId::new([u8::MAX; 32])),
+        );
+        update_tree(
+            &mut self.balance_trees,
+            contract,
+            MerkleTreeKey::new(key.asset_id()),
+            value
+                .map(Word::to_be_bytes)
+                .as_ref()
+                .map(|value| &value[..]),
+            || {
+                balances.map(|(key, value)| {
+                    (MerkleTreeKey::new(key.asset_id()), value.to_be_bytes())
+                })
+            },
+        );
+        previous
+    }
+
+    /// Set a storage slot, updating the state tree of the contract.
+    fn set_state(
+        &mut self,
+        key: ContractsStateKey,
+        value: Option<Bytes32>,
+    ) -> Option<Bytes32> {
+        let previous = set(&mut self.contract_state, key, value);
+        let contract = key.contract_id();
+        let slots = self.contract_state.range(
+            ContractsStateKey::new(contract, &Bytes32::zeroed())
+                ..=ContractsStateKey::new(contract, &Bytes32::new([u8::MAX; 32])),
+        );
+        update_tree(
+            &mut self.state_trees,
+            contract,
+            MerkleTreeKey::new(key.state_key()),
+            value.as_ref().map(AsRef::as_ref),
+            || slots.map(|(key, value)| (MerkleTreeKey::new(key.state_key()), *value)),
+        );
+        previous
+    }
+}
+
+/// Sparse Merkle tree of the balances or the storage slots of a contract, as computed
+/// by the nodes: the leaves are keyed by the hash of the asset id or the slot key.
+///
+/// The nodes replaced by an update are kept in the storage of the tree, so the tree
+/// is rebuilt from its leaves once the nodes are twice as many as after the last
+/// rebuild.
+#[derive(Debug, Clone)]
+struct ContractTree {
+    root: MerkleRoot,
+    nodes: StorageMap<NodesTable>,
+    /// Number of nodes of the tree after its last rebuild.
+    rebuilt_nodes: usize,
+}
+
+impl Default for ContractTree {
+    fn default() -> Self {
+        Self {
+            root: *sparse::empty_sum(),
+            nodes: StorageMap::new(),
+            rebuilt_nodes: 0,
+        }
+    }
+}
+
+/// Minimum number of nodes of a [`ContractTree`] before it is rebuilt, so small
+/// trees aren't rebuilt on most updates.
+const MIN_REBUILT_NODES: usize = 64;
+
+/// Nodes of a [`ContractTree`], by hash.
+#[derive(Debug, Clone)]
+struct NodesTable;
+
+impl Mappable for NodesTable {
+    type Key = Self::OwnedKey;
+    type OwnedKey = MerkleRoot;
+    type OwnedValue = Primitive;
+    type Value = Self::OwnedValue;
+}
+
+impl PartialEq for ContractTree {
+    fn eq(&self, other: &Self) -> bool {
+        self.root == other.root
+    }
+}
+
+impl Eq for ContractTree {}
+
+/// Set the leaf of `key` in the tree of `contract` to `data`, or remove it if `data`
+/// is `None`. Empty trees are dropped, and trees with too many replaced nodes are
+/// rebuilt from their `leaves`, which include the update.
+fn update_tree<I, D>(
+    trees: &mut BTreeMap<ContractId, ContractTree>,
+    contract: &ContractId,
+    key: MerkleTreeKey,
+    data: Option<&[u8]>,
+    leaves: impl FnOnce() -> I,
+) where
+    I: Iterator<Item = (MerkleTreeKey, D)>,
+    D: AsRef<[u8]>,
+{
+    let tree = trees.entry(*contract).or_default();
+    let mut merkle_tree =
+        sparse::MerkleTree::<NodesTable, _>::load(&mut tree.nodes, &tree.root)
+            .expect("The root of the tree is in its storage");
+    match data {
+        Some(data) => merkle_tree.update(key, data),
+        None => merkle_tree.delete(key),
+    }
+    .expect("`StorageMap` can't return error");
+    tree.root = merkle_tree.root();
+
+    if tree.root == *sparse::empty_sum() {
+        trees.remove(contract);
+    } else if tree.nodes.len() > 2 * tree.rebuilt_nodes.max(MIN_REBUILT_NODES) {
+        let merkle_tree =
+            sparse::MerkleTree::<NodesTable, _>::from_set(StorageMap::new(), leaves())
+                .expect("`StorageMap` can't return error");
+        debug_assert_eq!(merkle_tree.root(), tree.root);
+        tree.nodes = merkle_tree.into_storage();
+        tree.rebuilt_nodes = tree.nodes.len();
+    }
+}
+
+/// Entry of a table of the storage, with `None` for an unset entry.
+///
+/// The journal holds the entries replaced by the changes to the memory state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum StorageEntry {
+    Contract(ContractId, Option<Contract>),
+    ContractInfo(ContractId, Option<(Salt, Bytes32)>),
+    Balance(ContractsAssetKey, Option<Word>),
+    State(ContractsStateKey, Option<Bytes32>),
+}
+
+/// Values of the entries of the memory state at the last persist, for the entries
+/// changed since then. Only the first change of an entry is recorded.
+#[derive(Debug, Default, Clone)]
+struct PersistedValues {
+    contracts: BTreeMap<ContractId, Option<Contract>>,
+    balances: BTreeMap<ContractsAssetKey, Option<Word>>,
+    contract_state: BTreeMap<ContractsStateKey, Option<Bytes32>>,
+    contract_code_root: BTreeMap<ContractId, Option<(Salt, Bytes32)>>,
+}
+
+impl PersistedValues {
+    fn record(&mut self, entry: StorageEntry) {
+        match entry {
+            StorageEntry::Contract(key, value) => {
+                self.contracts.entry(key).or_insert(value);
+            }
+            StorageEntry::ContractInfo(key, value) => {
+                self.contract_code_root.entry(key).or_insert(value);
+            }
+            StorageEntry::Balance(key, value) => {
+                self.balances.entry(key).or_insert(value);
+            }
+            StorageEntry::State(key, value) => {
+                self.contract_state.entry(key).or_insert(value);
+            }
+        }
+    }
+
+    fn restore(self, memory: &mut MemoryStorageInner) {
+        for (key, value) in self.contracts {
+            set(&mut memory.contracts, key, value);
+        }
+        for (key, value) in self.balances {
+            memory.set_balance(key, value);
+        }
+        for (key, value) in self.contract_state {
+            memory.set_state(key, value);
+        }
+        for (key, value) in self.contract_code_root {
+            set(&mut memory.contract_code_root, key, value);
+        }
+    }
+}
+
+/// Insert `value` at `key` of the map, or remove the entry if `value` is `None`,
+/// returning the previous value.
+fn set<K: Ord, V>(map: &mut BTreeMap<K, V>, key: K, value: Option<V>) -> Option<V> {
+    match value {
+        Some(value) => map.insert(key, value),
+        None => map.remove(&key),
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -59,12 +295,22 @@ struct MemoryStorageInner {
 /// - memory: the transactions will be applied to this state.
 /// - transacted: will receive the committed `memory` state.
 /// - persisted: will receive the persisted `transacted` state.
+///
+/// Only the `memory` state is stored. The changes made since the last commit are
+/// journaled with the values they replaced, and the persisted values of the entries
+/// changed since the last persist are kept aside, so `commit`, `revert`, `rollback`
+/// and `persist` cost as much as the changes they apply or undo, not the whole state.
+///
+/// Storage checkpoints are positions in the journal, and can't revert the changes
+/// committed after them.
 pub struct MemoryStorage {
     block_height: BlockHeight,
-    coinbase: ContractId,
+    block_headers: MemoryBlockHeaders,
     memory: MemoryStorageInner,
-    transacted: MemoryStorageInner,
-    persisted: MemoryStorageInner,
+    journal: Vec<StorageEntry>,
+    persisted: PersistedValues,
+    /// Length of the journal at each checkpoint that isn't released.
+    checkpoints: Vec<usize>,
 }
 
 impl MemoryStorage {
@@ -72,10 +318,15 @@ impl MemoryStorage {
     pub fn new(block_height: BlockHeight, coinbase: ContractId) -> Self {
         Self {
             block_height,
-            coinbase,
+            block_headers: MemoryBlockHeaders::new(
+                Tai64::UNIX_EPOCH,
+                MemoryBlockHeaders::DEFAULT_BLOCK_TIME,
+                coinbase,
+            ),
             memory: Default::default(),
-            transacted: Default::default(),
+            journal: Default::default(),
             persisted: Default::default(),
+            checkpoints: Default::default(),
         }
     }
 
@@ -100,26 +351,235 @@ impl MemoryStorage {
             .unwrap_or(Cow::Borrowed(&DEFAULT_STATE))
     }
 
+    /// Snapshot of the memory state, with the block height and the coinbase.
+    pub fn export_snapshot(&self) -> StorageSnapshot {
+        let memory = &self.memory;
+        let mut contracts = BTreeMap::<ContractId, ContractSnapshot>::new();
+        fn contract<'a>(
+            contracts: &'a mut BTreeMap<ContractId, ContractSnapshot>,
+            contract_id: &ContractId,
+        ) -> &'a mut ContractSnapshot {
+            contracts
+                .entry(*contract_id)
+                .or_insert_with(|| ContractSnapshot {
+                    contract_id: *contract_id,
+                    ..Default::default()
+                })
+        }
+
+        for (id, bytecode) in &memory.contracts {
+            contract(&mut contracts, id).bytecode = bytecode.clone();
+        }
+        for (id, (salt, root)) in &memory.contract_code_root {
+            let contract = contract(&mut contracts, id);
+            contract.salt = *salt;
+            contract.root = *root;
+        }
+        for (key, value) in &memory.contract_state {
+            contract(&mut contracts, key.contract_id())
+                .state
+                .push(StorageSlot::new(*key.state_key(), *value));
+        }
+        for (key, amount) in &memory.balances {
+            contract(&mut contracts, key.contract_id())
+                .balances
+                .push(ContractBalance {
+                    asset_id: *key.asset_id(),
+                    amount: *amount,
+                });
+        }
+
+        StorageSnapshot::new(
+            self.block_height,
+            self.block_headers.get(self.block_height).coinbase,
+            contracts.into_values().collect(),
+        )
+    }
+
+    /// Create a memory storage with the state of the snapshot as persisted state.
+    pub fn from_snapshot(
+        snapshot: &StorageSnapshot,
+    ) -> Result<Self, SnapshotError<Infallible>> {
+        let mut storage = Self::new(snapshot.block_height, snapshot.coinbase);
+        import_state(&mut storage, snapshot)?;
+        storage.commit();
+        storage.persist();
+        Ok(storage)
+    }
+
+    /// The entries changed since the last commit, with their value in the memory state.
+    #[cfg(feature = "file-storage")]
+    pub(crate) fn uncommitted_entries(&self) -> Vec<StorageEntry> {
+        let mut changed = PersistedValues::default();
+        for entry in &self.journal {
+            changed.record(entry.clone());
+        }
+
+        let memory = &self.memory;
+        let contracts = changed
+            .contracts
+            .into_keys()
+            .map(|key| StorageEntry::Contract(key, memory.contracts.get(&key).cloned()));
+        let infos = changed.contract_code_root.into_keys().map(|key| {
+            StorageEntry::ContractInfo(key, memory.contract_code_root.get(&key).copied())
+        });
+        let balances = changed
+            .balances
+            .into_keys()
+            .map(|key| StorageEntry::Balance(key, memory.balances.get(&key).copied()));
+        let state = changed.contract_state.into_keys().map(|key| {
+            StorageEntry::State(key, memory.contract_state.get(&key).copied())
+        });
+        contracts
+            .chain(infos)
+            .chain(balances)
+            .chain(state)
+            .collect()
+    }
+
+    /// All the entries of the memory state.
+    #[cfg(feature = "file-storage")]
+    pub(crate) fn entries(&self) -> impl Iterator<Item = StorageEntry> + '_ {
+        let memory = &self.memory;
+        let contracts = memory
+            .contracts
+            .iter()
+            .map(|(key, value)| StorageEntry::Contract(*key, Some(value.clone())));
+        let infos = memory
+            .contract_code_root
+            .iter()
+            .map(|(key, value)| StorageEntry::ContractInfo(*key, Some(*value)));
+        let balances = memory
+            .balances
+            .iter()
+            .map(|(key, value)| StorageEntry::Balance(*key, Some(*value)));
+        let state = memory
+            .contract_state
+            .iter()
+            .map(|(key, value)| StorageEntry::State(*key, Some(*value)));
+        contracts.chain(infos).chain(balances).chain(state)
+    }
+
+    /// Set an entry of the memory state.
+    #[cfg(feature = "file-storage")]
+    pub(crate) fn set_entry(&mut self, entry: StorageEntry) {
+        match entry {
+            StorageEntry::Contract(key, value) => {
+                self.set_contract(&key, value);
+            }
+            StorageEntry::ContractInfo(key, value) => {
+                self.set_contract_info(&key, value);
+            }
+            StorageEntry::Balance(key, value) => {
+                self.set_balance(&key, value);
+            }
+            StorageEntry::State(key, value) => {
+                self.set_state(&key, value);
+            }
+        }
+    }
+
+    /// Set an entry of the memory state as if it was persisted, without journaling
+    /// it. The entry must be unchanged since the last persist.
+    pub(crate) fn set_persisted_entry(&mut self, entry: StorageEntry) {
+        match entry {
+            StorageEntry::Contract(key, value) => {
+                set(&mut self.memory.contracts, key, value);
+            }
+            StorageEntry::ContractInfo(key, value) => {
+                set(&mut self.memory.contract_code_root, key, value);
+            }
+            StorageEntry::Balance(key, value) => {
+                self.memory.set_balance(key, value);
+            }
+            StorageEntry::State(key, value) => {
+                self.memory.set_state(key, value);
+            }
+        }
+    }
+
     /// Set the transacted state to the memory state.
     pub fn commit(&mut self) {
-        self.transacted = self.memory.clone();
+        for entry in self.journal.drain(..) {
+            self.persisted.record(entry);
+        }
+        self.checkpoints
+            .iter_mut()
+            .for_each(|checkpoint| *checkpoint = 0);
     }
 
     /// Revert the memory state to the transacted state.
     pub fn revert(&mut self) {
-        self.memory = self.transacted.clone();
+        self.undo_to(0);
     }
 
     /// Revert the memory and transacted changes to the persisted state.
     pub fn rollback(&mut self) {
-        self.memory = self.persisted.clone();
-        self.transacted = self.persisted.clone();
+        self.undo_to(0);
+        core::mem::take(&mut self.persisted).restore(&mut self.memory);
     }
 
     /// Persist the changes from transacted to memory+persisted state.
     pub fn persist(&mut self) {
-        self.memory = self.transacted.clone();
-        self.persisted = self.transacted.clone();
+        self.undo_to(0);
+        self.persisted = Default::default();
+    }
+
+    /// Undo the journaled changes until the journal has `len` entries.
+    ///
+    /// The checkpoints taken after the undone changes are moved back to the
+    /// resulting state.
+    fn undo_to(&mut self, len: usize) {
+        while self.journal.len() > len {
+            if let Some(entry) = self.journal.pop() {
+                self.memory.undo(entry);
+            }
+        }
+        for checkpoint in &mut self.checkpoints {
+            *checkpoint = (*checkpoint).min(len);
+        }
+    }
+
+    fn set_contract(
+        &mut self,
+        key: &ContractId,
+        value: Option<Contract>,
+    ) -> Option<Contract> {
+        let previous = set(&mut self.memory.contracts, *key, value);
+        self.journal
+            .push(StorageEntry::Contract(*key, previous.clone()));
+        previous
+    }
+
+    fn set_contract_info(
+        &mut self,
+        key: &ContractId,
+        value: Option<(Salt, Bytes32)>,
+    ) -> Option<(Salt, Bytes32)> {
+        let previous = set(&mut self.memory.contract_code_root, *key, value);
+        self.journal
+            .push(StorageEntry::ContractInfo(*key, previous));
+        previous
+    }
+
+    fn set_balance(
+        &mut self,
+        key: &ContractsAssetKey,
+        value: Option<Word>,
+    ) -> Option<Word> {
+        let previous = self.memory.set_balance(*key, value);
+        self.journal.push(StorageEntry::Balance(*key, previous));
+        previous
+    }
+
+    fn set_state(
+        &mut self,
+        key: &ContractsStateKey,
+        value: Option<Bytes32>,
+    ) -> Option<Bytes32> {
+        let previous = self.memory.set_state(*key, value);
+        self.journal.push(StorageEntry::State(*key, previous));
+        previous
     }
 
     #[cfg(feature = "test-helpers")]
@@ -127,6 +587,28 @@ impl MemoryStorage {
     pub fn set_block_height(&mut self, block_height: BlockHeight) {
         self.block_height = block_height;
     }
+
+    /// Move to the next block of the chain, returning its height.
+    ///
+    /// The headers of the blocks are read from [`Self::block_headers`].
+    pub fn advance_block(&mut self) -> BlockHeight {
+        self.block_height = self
+            .block_height
+            .succ()
+            .expect("The block height overflowed");
+        self.block_height
+    }
+
+    /// Headers of the blocks of the chain.
+    pub fn block_headers(&self) -> &MemoryBlockHeaders {
+        &self.block_headers
+    }
+
+    /// Mutable headers of the blocks of the chain, to set the hashes, timestamps,
+    /// coinbases and DA heights of the blocks.
+    pub fn block_headers_mut(&mut self) -> &mut MemoryBlockHeaders {
+        &mut self.block_headers
+    }
 }
 
 impl Default for MemoryStorage {
@@ -156,18 +638,18 @@ impl StorageMutate<ContractsRawCode> for MemoryStorage {
         key: &ContractId,
         value: &[u8],
     ) -> Result<Option<Contract>, Infallible> {
-        Ok(self.memory.contracts.insert(*key, value.into()))
+        Ok(self.set_contract(key, Some(value.into())))
     }
 
     fn remove(&mut self, key: &ContractId) -> Result<Option<Contract>, Infallible> {
-        Ok(self.memory.contracts.remove(key))
+        Ok(self.set_contract(key, None))
     }
 }
 
 impl StorageWrite<ContractsRawCode> for MemoryStorage {
     fn write(&mut self, key: &ContractId, buf: Vec<u8>) -> Result<usize, Infallible> {
         let size = buf.len();
-        self.memory.contracts.insert(*key, Contract::from(buf));
+        self.set_contract(key, Some(Contract::from(buf)));
         Ok(size)
     }
 
@@ -180,7 +662,7 @@ impl StorageWrite<ContractsRawCode> for MemoryStorage {
         Self: StorageSize<ContractsRawCode>,
     {
         let size = buf.len();
-        let last = self.memory.contracts.insert(*key, Contract::from(buf));
+        let last = self.set_contract(key, Some(Contract::from(buf)));
         Ok((size, last.map(Vec::from)))
     }
 
@@ -188,7 +670,7 @@ impl StorageWrite<ContractsRawCode> for MemoryStorage {
         &mut self,
         key: &<ContractsRawCode as Mappable>::Key,
     ) -> Result<Option<Vec<u8>>, Self::Error> {
-        Ok(self.memory.contracts.remove(key).map(Vec::from))
+        Ok(self.set_contract(key, None).map(Vec::from))
     }
 }
 
@@ -237,14 +719,14 @@ impl StorageMutate<ContractsInfo> for MemoryStorage {
         key: &ContractId,
         value: &(Salt, Bytes32),
     ) -> Result<Option<(Salt, Bytes32)>, Infallible> {
-        Ok(self.memory.contract_code_root.insert(*key, *value))
+        Ok(self.set_contract_info(key, Some(*value)))
     }
 
     fn remove(
         &mut self,
         key: &ContractId,
     ) -> Result<Option<(Salt, Bytes32)>, Infallible> {
-        Ok(self.memory.contract_code_root.remove(key))
+        Ok(self.set_contract_info(key, None))
     }
 }
 
@@ -272,31 +754,20 @@ impl StorageMutate<ContractsAssets> for MemoryStorage {
         key: &<ContractsAssets as Mappable>::Key,
         value: &Word,
     ) -> Result<Option<Word>, Infallible> {
-        Ok(self.memory.balances.insert(*key, *value))
+        Ok(self.set_balance(key, Some(*value)))
     }
 
     fn remove(
         &mut self,
         key: &<ContractsAssets as Mappable>::Key,
     ) -> Result<Option<Word>, Infallible> {
-        Ok(self.memory.balances.remove(key))
+        Ok(self.set_balance(key, None))
     }
 }
 
 impl MerkleRootStorage<ContractId, ContractsAssets> for MemoryStorage {
     fn root(&self, parent: &ContractId) -> Result<MerkleRoot, Infallible> {
-        let root = self
-            .memory
-            .balances
-            .iter()
-            .filter_map(|(key, balance)| {
-                (key.contract_id() == parent).then_some((key.asset_id(), balance))
-            })
-            .sorted_by_key(|t| t.0)
-            .map(|(_, &balance)| balance)
-            .map(Word::to_be_bytes);
-
-        Ok(crypto::ephemeral_merkle_root(root).into())
+        Ok(contract_root(&self.memory.balance_trees, parent))
     }
 }
 
@@ -324,35 +795,33 @@ impl StorageMutate<ContractsState> for MemoryStorage {
         key: &<ContractsState as Mappable>::Key,
         value: &Bytes32,
     ) -> Result<Option<Bytes32>, Infallible> {
-        Ok(self.memory.contract_state.insert(*key, *value))
+        Ok(self.set_state(key, Some(*value)))
     }
 
     fn remove(
         &mut self,
         key: &<ContractsState as Mappable>::Key,
     ) -> Result<Option<Bytes32>, Infallible> {
-        Ok(self.memory.contract_state.remove(key))
+        Ok(self.set_state(key, None))
     }
 }
 
 impl MerkleRootStorage<ContractId, ContractsState> for MemoryStorage {
     fn root(&self, parent: &ContractId) -> Result<MerkleRoot, Infallible> {
-        let root = self
-            .memory
-            .contract_state
-            .iter()
-            .filter_map(|(key, value)| {
-                (key.contract_id() == parent).then_some((key.state_key(), value))
-            })
-            .sorted_by_key(|t| t.0)
-            .map(|(_, value)| value);
-
-        Ok(crypto::ephemeral_merkle_root(root).into())
+        Ok(contract_root(&self.memory.state_trees, parent))
     }
 }
 
 impl ContractsAssetsStorage for MemoryStorage {}
 
+impl BlockHeaderProvider for MemoryStorage {
+    type Error = Infallible;
+
+    fn block_header(&self, height: BlockHeight) -> Result<BlockHeader, Infallible> {
+        Ok(self.block_headers.get(height))
+    }
+}
+
 impl InterpreterStorage for MemoryStorage {
     type DataError = Infallible;
 
@@ -361,18 +830,34 @@ impl InterpreterStorage for MemoryStorage {
     }
 
     fn timestamp(&self, height: BlockHeight) -> Result<Word, Self::DataError> {
-        const GENESIS: Tai64 = Tai64::UNIX_EPOCH;
-        const INTERVAL: Word = 10;
-
-        Ok((GENESIS + (*height as Word * INTERVAL)).0)
+        Ok(self.block_headers.get(height).timestamp)
     }
 
     fn block_hash(&self, block_height: BlockHeight) -> Result<Bytes32, Infallible> {
-        Ok(Hasher::hash(block_height.to_be_bytes()))
+        Ok(self.block_headers.get(block_height).hash)
     }
 
     fn coinbase(&self) -> Result<ContractId, Infallible> {
-        Ok(self.coinbase)
+        Ok(self.block_headers.get(self.block_height).coinbase)
+    }
+
+    fn checkpoint(&mut self) -> Result<StorageCheckpoint, Infallible> {
+        let checkpoint = StorageCheckpoint::new(self.checkpoints.len());
+        self.checkpoints.push(self.journal.len());
+        Ok(checkpoint)
+    }
+
+    fn revert_to(&mut self, checkpoint: StorageCheckpoint) -> Result<(), Infallible> {
+        if let Some(&len) = self.checkpoints.get(checkpoint.depth()) {
+            self.undo_to(len);
+        }
+        self.checkpoints.truncate(checkpoint.depth());
+        Ok(())
+    }
+
+    fn release(&mut self, checkpoint: StorageCheckpoint) -> Result<(), Infallible> {
+        self.checkpoints.truncate(checkpoint.depth());
+        Ok(())
     }
 
     fn merkle_contract_state_range(
@@ -419,22 +904,18 @@ impl InterpreterStorage for MemoryStorage {
         values: &[Bytes32],
     ) -> Result<Option<()>, Self::DataError> {
         let mut any_unset_key = false;
-        let values: Vec<_> = core::iter::successors(Some(**start_key), |n| {
+        let keys = core::iter::successors(Some(**start_key), |n| {
             let mut n = *n;
             if add_one(&mut n) {
                 None
             } else {
                 Some(n)
             }
-        })
-        .zip(values)
-        .map(|(key, value)| {
+        });
+        for (key, value) in keys.zip(values) {
             let key = (contract, &Bytes32::from(key)).into();
-            any_unset_key |= !self.memory.contract_state.contains_key(&key);
-            (key, *value)
-        })
-        .collect();
-        self.memory.contract_state.extend(values);
+            any_unset_key |= self.set_state(&key, Some(*value)).is_none();
+        }
         Ok((!any_unset_key).then_some(()))
     }
 
@@ -445,29 +926,34 @@ impl InterpreterStorage for MemoryStorage {
         range: Word,
     ) -> Result<Option<()>, Self::DataError> {
         let mut all_set_key = true;
-        let mut values: hashbrown::HashSet<_> =
-            core::iter::successors(Some(**start_key), |n| {
-                let mut n = *n;
-                if add_one(&mut n) {
-                    None
-                } else {
-                    Some(n)
-                }
-            })
-            .take(range as usize)
-            .collect();
-        self.memory.contract_state.retain(|key, _| {
-            let c = key.contract_id();
-            let k = key.state_key();
-            let r = values.remove(&**k);
-            all_set_key &= c == contract && r;
-            c != contract || !r
-        });
-        Ok((all_set_key && values.is_empty()).then_some(()))
+        let keys = core::iter::successors(Some(**start_key), |n| {
+            let mut n = *n;
+            if add_one(&mut n) {
+                None
+            } else {
+                Some(n)
+            }
+        })
+        .take(range as usize);
+        for key in keys {
+            let key = (contract, &Bytes32::from(key)).into();
+            all_set_key &= self.set_state(&key, None).is_some();
+        }
+        Ok(all_set_key.then_some(()))
     }
 }
 
-fn add_one(a: &mut [u8; 32]) -> bool {
+/// Root of the tree of `contract`, or the empty root if it has no leaf.
+fn contract_root(
+    trees: &BTreeMap<ContractId, ContractTree>,
+    contract: &ContractId,
+) -> MerkleRoot {
+    trees
+        .get(contract)
+        .map_or(*sparse::empty_sum(), |tree| tree.root)
+}
+
+pub(crate) fn add_one(a: &mut [u8; 32]) -> bool {
     let right = u128::from_be_bytes(a[16..].try_into().unwrap());
     let (right, of) = right.overflowing_add(1);
     a[16..].copy_from_slice(&right.to_be_bytes()[..]);
@@ -484,6 +970,11 @@ fn add_one(a: &mut [u8; 32]) -> bool {
 mod tests {
     use super::*;
     use alloc::vec;
+    use rand::{
+        rngs::StdRng,
+        Rng,
+        SeedableRng,
+    };
     use test_case::test_case;
 
     const fn key(k: u8) -> [u8; 32] {
@@ -518,4 +1009,259 @@ mod tests {
             .map(|v| v.map(|v| v.into_owned()))
             .collect()
     }
+
+    fn state(mem: &MemoryStorage, k: u8) -> Option<Bytes32> {
+        mem.merkle_contract_state(&ContractId::default(), &key(k).into())
+            .unwrap()
+            .map(Cow::into_owned)
+    }
+
+    fn set_state(mem: &mut MemoryStorage, k: u8, value: u8) {
+        mem.merkle_contract_state_insert(
+            &ContractId::default(),
+            &key(k).into(),
+            &[value; 32].into(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn nested_checkpoints_are_reverted_and_released() {
+        let mut mem = MemoryStorage::default();
+        set_state(&mut mem, 0, 1);
+
+        let outer = mem.checkpoint().unwrap();
+        set_state(&mut mem, 0, 2);
+        let inner = mem.checkpoint().unwrap();
+        set_state(&mut mem, 1, 3);
+        mem.release(inner).unwrap();
+        let inner = mem.checkpoint().unwrap();
+        set_state(&mut mem, 2, 4);
+        mem.revert_to(inner).unwrap();
+
+        assert_eq!(state(&mem, 0), Some([2; 32].into()));
+        assert_eq!(state(&mem, 1), Some([3; 32].into()));
+        assert_eq!(state(&mem, 2), None);
+
+        mem.revert_to(outer).unwrap();
+
+        assert_eq!(state(&mem, 0), Some([1; 32].into()));
+        assert_eq!(state(&mem, 1), None);
+    }
+
+    #[test]
+    fn reverting_to_a_checkpoint_reverts_the_ones_taken_after_it() {
+        let mut mem = MemoryStorage::default();
+
+        let outer = mem.checkpoint().unwrap();
+        set_state(&mut mem, 0, 1);
+        let _inner = mem.checkpoint().unwrap();
+        set_state(&mut mem, 1, 2);
+        mem.revert_to(outer).unwrap();
+
+        assert_eq!(state(&mem, 0), None);
+        assert_eq!(state(&mem, 1), None);
+        assert_eq!(mem.checkpoint().unwrap(), outer);
+    }
+
+    #[test]
+    fn journal_tracks_the_transacted_and_persisted_states() {
+        let mut mem = MemoryStorage::default();
+        set_state(&mut mem, 0, 1);
+        mem.commit();
+        mem.persist();
+
+        set_state(&mut mem, 0, 2);
+        mem.commit();
+        set_state(&mut mem, 1, 3);
+        mem.revert();
+        assert_eq!(state(&mem, 0), Some([2; 32].into()));
+        assert_eq!(state(&mem, 1), None);
+
+        mem.rollback();
+        assert_eq!(state(&mem, 0), Some([1; 32].into()));
+
+        set_state(&mut mem, 0, 4);
+        mem.commit();
+        set_state(&mut mem, 1, 5);
+        mem.persist();
+        mem.rollback();
+        assert_eq!(state(&mem, 0), Some([4; 32].into()));
+        assert_eq!(state(&mem, 1), None);
+    }
+
+    #[test]
+    fn journal_matches_copies_of_the_states() {
+        let rng = &mut StdRng::seed_from_u64(2322u64);
+        let contract = ContractId::default();
+        let mut mem = MemoryStorage::default();
+        let mut memory = MemoryStorageInner::default();
+        let mut transacted = MemoryStorageInner::default();
+        let mut persisted = MemoryStorageInner::default();
+
+        for _ in 0..1000 {
+            match rng.gen_range(0..8) {
+                0 => {
+                    mem.commit();
+                    transacted = memory.clone();
+                }
+                1 => {
+                    mem.revert();
+                    memory = transacted.clone();
+                }
+                2 => {
+                    mem.rollback();
+                    memory = persisted.clone();
+                    transacted = persisted.clone();
+                }
+                3 => {
+                    mem.persist();
+                    memory = transacted.clone();
+                    persisted = transacted.clone();
+                }
+                4 => {
+                    let k = rng.gen_range(0..8);
+                    mem.merkle_contract_state_remove(&contract, &key(k).into())
+                        .unwrap();
+                    memory.set_state((&contract, &key(k).into()).into(), None);
+                }
+                5 => {
+                    let balance = rng.gen();
+                    mem.merkle_contract_asset_id_balance_insert(
+                        &contract,
+                        &Default::default(),
+                        balance,
+                    )
+                    .unwrap();
+                    memory.set_balance(
+                        (&contract, &Default::default()).into(),
+                        Some(balance),
+                    );
+                }
+                _ => {
+                    let (k, value) = (rng.gen_range(0..8), rng.gen());
+                    set_state(&mut mem, k, value);
+                    memory.set_state(
+                        (&contract, &key(k).into()).into(),
+                        Some([value; 32].into()),
+                    );
+                }
+            }
+
+            assert_eq!(mem.memory, memory);
+        }
+    }
+
+    #[test]
+    fn roots_are_those_of_the_sparse_merkle_trees() {
+        use fuel_merkle::sparse::in_memory::MerkleTree;
+
+        let contract = ContractId::from([1; 32]);
+        let other = ContractId::from([2; 32]);
+        let mut mem = MemoryStorage::default();
+        for k in 0..4 {
+            for id in [&contract, &other] {
+                mem.merkle_contract_state_insert(id, &key(k).into(), &[k; 32].into())
+                    .unwrap();
+                mem.merkle_contract_asset_id_balance_insert(
+                    id,
+                    &[k; 32].into(),
+                    k as Word,
+                )
+                .unwrap();
+            }
+        }
+        mem.merkle_contract_state_remove(&contract, &key(1).into())
+            .unwrap();
+        mem.merkle_contract_asset_id_balance_insert(&contract, &[2; 32].into(), 7)
+            .unwrap();
+
+        let state = MerkleTree::root_from_set(
+            [0, 2, 3]
+                .into_iter()
+                .map(|k| (MerkleTreeKey::new(key(k)), [k; 32])),
+        );
+        let balances =
+            MerkleTree::root_from_set([(0, 0), (1, 1), (2, 7), (3, 3)].into_iter().map(
+                |(k, balance): (u8, Word)| {
+                    (MerkleTreeKey::new([k; 32]), balance.to_be_bytes())
+                },
+            ));
+
+        assert_eq!(
+            MerkleRootStorage::<ContractId, ContractsState>::root(&mem, &contract),
+            Ok(state)
+        );
+        assert_eq!(
+            MerkleRootStorage::<ContractId, ContractsAssets>::root(&mem, &contract),
+            Ok(balances)
+        );
+    }
+
+    #[test]
+    fn tree_nodes_stay_bounded_after_overwrites_and_reverts() {
+        use fuel_merkle::sparse::in_memory::MerkleTree;
+
+        let contract = ContractId::from([1; 32]);
+        let mut mem = MemoryStorage::default();
+        for k in 0..16 {
+            mem.merkle_contract_state_insert(&contract, &key(k).into(), &[k; 32].into())
+                .unwrap();
+            mem.merkle_contract_asset_id_balance_insert(&contract, &[k; 32].into(), 1)
+                .unwrap();
+        }
+        mem.commit();
+
+        for i in 0..1000u32 {
+            let k = (i % 16) as u8;
+            let value = Hasher::hash(i.to_be_bytes());
+            mem.merkle_contract_state_insert(&contract, &key(k).into(), &value)
+                .unwrap();
+            mem.merkle_contract_asset_id_balance_insert(
+                &contract,
+                &[k; 32].into(),
+                i as Word,
+            )
+            .unwrap();
+            if i % 3 == 0 {
+                mem.revert();
+            } else {
+                mem.commit();
+            }
+
+            for trees in [&mem.memory.state_trees, &mem.memory.balance_trees] {
+                assert!(trees[&contract].nodes.len() <= 4 * MIN_REBUILT_NODES);
+            }
+        }
+
+        let state = MerkleTree::root_from_set(
+            mem.memory
+                .contract_state
+                .iter()
+                .map(|(key, value)| (MerkleTreeKey::new(key.state_key()), *value)),
+        );
+        assert_eq!(
+            MerkleRootStorage::<ContractId, ContractsState>::root(&mem, &contract),
+            Ok(state)
+        );
+    }
+
+    #[test]
+    fn roots_of_contracts_without_entries_are_empty() {
+        let contract = ContractId::from([1; 32]);
+        let mut mem = MemoryStorage::default();
+        mem.merkle_contract_state_insert(&contract, &key(0).into(), &[1; 32].into())
+            .unwrap();
+        mem.merkle_contract_state_remove(&contract, &key(0).into())
+            .unwrap();
+
+        assert_eq!(
+            MerkleRootStorage::<ContractId, ContractsState>::root(&mem, &contract),
+            Ok(*sparse::empty_sum())
+        );
+        assert_eq!(
+            MerkleRootStorage::<ContractId, ContractsAssets>::root(&mem, &contract),
+            Ok(*sparse::empty_sum())
+        );
+    }
 }
@@ -8,7 +8,10 @@ use crate::{
         InterpreterError,
         RuntimeError,
     },
-    storage::InterpreterStorage,
+    storage::{
+        InterpreterStorage,
+        StorageCheckpoint,
+    },
 };
 
 use fuel_asm::Word;
@@ -172,4 +175,22 @@ impl InterpreterStorage for PredicateStorage {
     ) -> Result<Option<()>, StorageUnavailable> {
         Err(StorageUnavailable)
     }
+
+    fn checkpoint(&mut self) -> Result<StorageCheckpoint, StorageUnavailable> {
+        Err(StorageUnavailable)
+    }
+
+    fn revert_to(
+        &mut self,
+        _checkpoint: StorageCheckpoint,
+    ) -> Result<(), StorageUnavailable> {
+        Err(StorageUnavailable)
+    }
+
+    fn release(
+        &mut self,
+        _checkpoint: StorageCheckpoint,
+    ) -> Result<(), StorageUnavailable> {
+        Err(StorageUnavailable)
+    }
 }
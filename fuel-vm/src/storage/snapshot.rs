@@ -0,0 +1,164 @@
+//! Snapshots of the state of a storage, used to seed storages with a known state.
+
+use alloc::vec::Vec;
+
+use fuel_tx::{
+    Contract,
+    StorageSlot,
+};
+use fuel_types::{
+    canonical::{
+        Deserialize,
+        Serialize,
+    },
+    AssetId,
+    BlockHeight,
+    Bytes32,
+    ContractId,
+    Salt,
+    Word,
+};
+
+use super::InterpreterStorage;
+
+/// Snapshot of the contracts of a storage, with the block height and the coinbase
+/// of the chain.
+///
+/// The snapshot is encoded with the canonical serialization of `fuel-types`, and
+/// with `serde` behind the `serde` feature. Its version is encoded first, so a
+/// snapshot of another version of the format is rejected by [`import_state`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Deserialize, Serialize)]
+pub struct StorageSnapshot {
+    version: u32,
+    /// Block height of the chain.
+    pub block_height: BlockHeight,
+    /// Coinbase contract of the chain.
+    pub coinbase: ContractId,
+    /// Contracts of the storage, ordered by id.
+    pub contracts: Vec<ContractSnapshot>,
+}
+
+/// Code, storage slots and balances of a contract.
+///
+/// The balances and storage slots of a contract without code are exported with an
+/// empty code and zeroed salt and root, which [`import_state`] doesn't write.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Deserialize, Serialize)]
+pub struct ContractSnapshot {
+    /// Id of the contract.
+    pub contract_id: ContractId,
+    /// Bytecode of the contract.
+    pub bytecode: Contract,
+    /// Salt used to compute the contract id.
+    pub salt: Salt,
+    /// Root of the bytecode.
+    pub root: Bytes32,
+    /// Storage slots of the contract, ordered by key.
+    pub state: Vec<StorageSlot>,
+    /// Balances of the contract, ordered by asset.
+    pub balances: Vec<ContractBalance>,
+}
+
+impl ContractSnapshot {
+    /// Returns `true` if the snapshot holds the code of the contract, and not only
+    /// its storage slots and balances.
+    pub fn is_deployed(&self) -> bool {
+        !self.bytecode.as_ref().is_empty()
+            || self.salt != Salt::zeroed()
+            || self.root != Bytes32::zeroed()
+    }
+}
+
+/// Balance of an asset held by a contract.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Deserialize, Serialize)]
+pub struct ContractBalance {
+    /// Asset of the balance.
+    pub asset_id: AssetId,
+    /// Amount of the asset.
+    pub amount: Word,
+}
+
+/// Failure to import a [`StorageSnapshot`].
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+#[display(bound = "StorageError: core::fmt::Display")]
+pub enum SnapshotError<StorageError> {
+    /// The snapshot was made with an unsupported version of the format.
+    #[display(fmt = "Unsupported snapshot version {_0}")]
+    UnsupportedVersion(u32),
+    /// The snapshot couldn't be written to the storage.
+    #[display(fmt = "Failed to write the snapshot: {_0}")]
+    Storage(StorageError),
+}
+
+impl StorageSnapshot {
+    /// Version of the format of the snapshots.
+    pub const VERSION: u32 = 1;
+
+    /// Create a snapshot of the current version of the format.
+    pub const fn new(
+        block_height: BlockHeight,
+        coinbase: ContractId,
+        contracts: Vec<ContractSnapshot>,
+    ) -> Self {
+        Self {
+            version: Self::VERSION,
+            block_height,
+            coinbase,
+            contracts,
+        }
+    }
+
+    /// Version of the format of the snapshot.
+    pub const fn version(&self) -> u32 {
+        self.version
+    }
+}
+
+/// Write the contracts of the snapshot to `storage`.
+///
+/// The block height and the coinbase of the snapshot aren't part of the
+/// [`InterpreterStorage`] tables, and are left to the caller.
+pub fn import_state<S>(
+    storage: &mut S,
+    snapshot: &StorageSnapshot,
+) -> Result<(), SnapshotError<S::DataError>>
+where
+    S: InterpreterStorage,
+{
+    if snapshot.version != StorageSnapshot::VERSION {
+        return Err(SnapshotError::UnsupportedVersion(snapshot.version))
+    }
+
+    for contract in &snapshot.contracts {
+        let id = &contract.contract_id;
+        if contract.is_deployed() {
+            storage
+                .storage_contract_insert(id, &contract.bytecode)
+                .map_err(SnapshotError::Storage)?;
+            storage
+                .storage_contract_root_insert(id, &contract.salt, &contract.root)
+                .map_err(SnapshotError::Storage)?;
+        }
+        for slot in &contract.state {
+            storage
+                .merkle_contract_state_insert(id, slot.key(), slot.value())
+                .map_err(SnapshotError::Storage)?;
+        }
+        for balance in &contract.balances {
+            storage
+                .merkle_contract_asset_id_balance_insert(
+                    id,
+                    &balance.asset_id,
+                    balance.amount,
+                )
+                .map_err(SnapshotError::Storage)?;
+        }
+    }
+
+    Ok(())
+}
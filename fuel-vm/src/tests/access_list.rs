@@ -0,0 +1,119 @@
+use alloc::vec;
+
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::ConsensusParameters;
+use fuel_types::canonical::Serialize;
+use fuel_vm::{
+    checked_transaction::Checked,
+    interpreter::InterpreterParams,
+    prelude::*,
+    storage::{
+        AccessList,
+        AccessRecorder,
+    },
+};
+
+fn key(index: u8) -> Bytes32 {
+    let mut key = Bytes32::zeroed();
+    key[31] = index;
+    key
+}
+
+/// Deploys a contract reading slot 0, writing the slots 1 and 2 and reading the
+/// balance of the zero asset, and builds a script calling it.
+fn setup(test_context: &mut TestBuilder) -> (ContractId, Checked<Script>) {
+    let contract = vec![
+        op::movi(0x10, 96),
+        op::aloc(0x10),
+        op::srw(0x11, 0x12, RegId::HP),
+        op::sw(RegId::HP, RegId::ONE, 3),
+        op::addi(0x13, RegId::HP, 32),
+        op::movi(0x14, 2),
+        op::swwq(RegId::HP, 0x12, 0x13, 0x14),
+        op::bal(0x15, 0x13, RegId::FP),
+        op::ret(RegId::ONE),
+    ];
+    let contract_id = test_context
+        .setup_contract(contract, None, None)
+        .contract_id;
+
+    let script = vec![
+        op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+        op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+        op::ret(RegId::ONE),
+    ];
+    let tx = test_context
+        .start_script(script, Call::new(contract_id, 0, 0).to_bytes())
+        .gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+
+    (contract_id, tx)
+}
+
+fn recording_vm(
+    test_context: &TestBuilder,
+) -> Interpreter<AccessRecorder<MemoryStorage>, Script> {
+    Interpreter::with_storage(
+        test_context.get_storage().clone(),
+        InterpreterParams::from(&ConsensusParameters::standard()),
+    )
+    .add_access_recording()
+}
+
+#[test]
+fn accessed_entries_are_recorded() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let (contract_id, tx) = setup(&mut test_context);
+
+    let mut vm = recording_vm(&test_context);
+    vm.transact(tx).expect("Failed to execute");
+    let access_list = vm.take_access_list();
+
+    assert_eq!(access_list.contracts, [contract_id].into_iter().collect());
+    assert_eq!(
+        access_list.storage_slots[&contract_id],
+        [key(0), key(1), key(2)].into_iter().collect()
+    );
+    assert!(access_list.assets[&contract_id].contains(&AssetId::zeroed()));
+    assert!(vm.access_list().is_empty());
+
+    let json = serde_json::to_vec(&access_list).expect("Serialization failed");
+    let decoded: AccessList =
+        serde_json::from_slice(&json).expect("Deserialization failed");
+    assert_eq!(decoded, access_list);
+}
+
+#[test]
+fn prefetching_the_access_list_does_not_change_the_execution() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let (_, tx) = setup(&mut test_context);
+
+    let mut vm = recording_vm(&test_context);
+    let receipts = vm
+        .transact(tx.clone())
+        .expect("Failed to execute")
+        .receipts()
+        .to_vec();
+    let access_list = vm.take_access_list();
+
+    let mut vm = recording_vm(&test_context);
+    vm.prefetch(&access_list).expect("Failed to prefetch");
+    assert!(vm.access_list().is_empty());
+    let prefetched_receipts = vm
+        .transact(tx)
+        .expect("Failed to execute")
+        .receipts()
+        .to_vec();
+
+    assert_eq!(prefetched_receipts, receipts);
+    assert_eq!(vm.access_list(), access_list);
+
+    let vm = vm.remove_access_recording();
+    assert_eq!(vm.receipts(), receipts.as_slice());
+}
@@ -0,0 +1,344 @@
+use alloc::{
+    boxed::Box,
+    vec,
+};
+use core::{
+    convert::Infallible,
+    sync::atomic::{
+        AtomicUsize,
+        Ordering,
+    },
+};
+
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_storage::{
+    Mappable,
+    StorageInspect,
+};
+use fuel_tx::ConsensusParameters;
+use fuel_types::{
+    canonical::Serialize,
+    BlockHeight,
+};
+use fuel_vm::{
+    checked_transaction::Checked,
+    hook::{
+        ExecutionHook,
+        InstructionStep,
+    },
+    interpreter::InterpreterParams,
+    prelude::*,
+    storage::{
+        AsyncInterpreterStorage,
+        AsyncStorage,
+        AsyncStorageInspect,
+        ContractsState,
+        ContractsStateKey,
+        InterpreterStorage,
+    },
+};
+
+/// Backend behind async I/O, counting the entries fetched from it.
+#[derive(Debug)]
+struct RemoteStorage {
+    storage: MemoryStorage,
+    fetches: AtomicUsize,
+}
+
+impl RemoteStorage {
+    fn new(storage: MemoryStorage) -> Self {
+        Self {
+            storage,
+            fetches: AtomicUsize::new(0),
+        }
+    }
+
+    fn fetches(&self) -> usize {
+        self.fetches.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait::async_trait]
+impl<Type> AsyncStorageInspect<Type> for RemoteStorage
+where
+    Type: Mappable + Send + Sync,
+    Type::Key: Sync,
+    Type::OwnedValue: Send,
+    MemoryStorage: StorageInspect<Type, Error = Infallible>,
+{
+    type Error = Infallible;
+
+    async fn get(&self, key: &Type::Key) -> Result<Option<Type::OwnedValue>, Infallible> {
+        tokio::task::yield_now().await;
+        self.fetches.fetch_add(1, Ordering::SeqCst);
+        Ok(StorageInspect::<Type>::get(&self.storage, key)?
+            .map(|value| value.into_owned()))
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncInterpreterStorage for RemoteStorage {
+    type DataError = Infallible;
+
+    async fn block_height(&self) -> Result<BlockHeight, Infallible> {
+        self.storage.block_height()
+    }
+
+    async fn timestamp(&self, height: BlockHeight) -> Result<Word, Infallible> {
+        tokio::task::yield_now().await;
+        self.fetches.fetch_add(1, Ordering::SeqCst);
+        self.storage.timestamp(height)
+    }
+
+    async fn block_hash(&self, height: BlockHeight) -> Result<Bytes32, Infallible> {
+        tokio::task::yield_now().await;
+        self.fetches.fetch_add(1, Ordering::SeqCst);
+        self.storage.block_hash(height)
+    }
+
+    async fn coinbase(&self) -> Result<ContractId, Infallible> {
+        self.storage.coinbase()
+    }
+}
+
+/// Deploys a contract reading slot 0, writing the slots 1 and 2 and reading the
+/// balance of the zero asset, and builds a script logging the time of the current
+/// block and calling the contract.
+fn setup(test_context: &mut TestBuilder) -> (ContractId, Checked<Script>) {
+    let contract = vec![
+        op::movi(0x10, 96),
+        op::aloc(0x10),
+        op::srw(0x11, 0x12, RegId::HP),
+        op::sw(RegId::HP, RegId::ONE, 3),
+        op::addi(0x13, RegId::HP, 32),
+        op::movi(0x14, 2),
+        op::swwq(RegId::HP, 0x12, 0x13, 0x14),
+        op::bal(0x15, 0x13, RegId::FP),
+        op::ret(RegId::ONE),
+    ];
+    let contract_id = test_context
+        .setup_contract(contract, None, None)
+        .contract_id;
+
+    let script = vec![
+        op::bhei(0x20),
+        op::time(0x21, 0x20),
+        op::log(0x21, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+        op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+        op::ret(RegId::ONE),
+    ];
+    let tx = test_context
+        .start_script(script, Call::new(contract_id, 0, 0).to_bytes())
+        .gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+
+    (contract_id, tx)
+}
+
+async fn async_vm(
+    test_context: &TestBuilder,
+) -> Interpreter<AsyncStorage<RemoteStorage>, Script> {
+    let storage =
+        AsyncStorage::new(RemoteStorage::new(test_context.get_storage().clone()))
+            .await
+            .expect("Infallible");
+
+    Interpreter::with_storage(
+        storage,
+        InterpreterParams::from(&ConsensusParameters::standard()),
+    )
+}
+
+#[tokio::test]
+async fn async_execution_matches_the_sync_execution() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let (contract_id, tx) = setup(&mut test_context);
+
+    let mut vm = Interpreter::<_, Script>::with_storage(
+        test_context.get_storage().clone(),
+        InterpreterParams::from(&ConsensusParameters::standard()),
+    );
+    let expected = vm.transact(tx.clone()).expect("Failed to execute");
+    let receipts = expected.receipts().to_vec();
+    let state_changes = expected.state_changes().clone();
+
+    let mut vm = async_vm(&test_context).await;
+    let result = vm.transact_async(tx).await.expect("Failed to execute");
+
+    assert!(!result.should_revert());
+    assert_eq!(result.receipts(), receipts.as_slice());
+    assert_eq!(result.state_changes(), &state_changes);
+    assert!(vm.as_ref().fetched().contains_contract(&contract_id));
+    assert!(vm.as_ref().storage().fetches() > 0);
+}
+
+#[tokio::test]
+async fn fetching_the_access_list_avoids_fetches_during_the_execution() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let (_, tx) = setup(&mut test_context);
+
+    let mut vm = Interpreter::<_, Script>::with_storage(
+        test_context.get_storage().clone(),
+        InterpreterParams::from(&ConsensusParameters::standard()),
+    )
+    .add_access_recording();
+    vm.transact(tx.clone()).expect("Failed to execute");
+    let access_list = vm.take_access_list();
+
+    let mut vm = async_vm(&test_context).await;
+    vm.as_mut()
+        .fetch(&access_list)
+        .await
+        .expect("Failed to fetch");
+    let block_height = vm.as_ref().block_height().expect("Infallible");
+    vm.as_mut()
+        .fetch_block(block_height)
+        .await
+        .expect("Failed to fetch");
+    let fetches = vm.as_ref().storage().fetches();
+    vm.transact_async(tx).await.expect("Failed to execute");
+
+    assert_eq!(vm.as_ref().storage().fetches(), fetches);
+}
+
+#[derive(Debug, Default, Clone)]
+struct InstructionCounter(usize);
+
+impl ExecutionHook for InstructionCounter {
+    fn before_instruction(&mut self, _step: &InstructionStep<'_>) {
+        self.0 += 1;
+    }
+}
+
+#[tokio::test]
+async fn execution_hook_only_observes_the_complete_execution() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let (_, tx) = setup(&mut test_context);
+
+    let mut vm = Interpreter::<_, Script>::with_storage(
+        test_context.get_storage().clone(),
+        InterpreterParams::from(&ConsensusParameters::standard()),
+    );
+    vm.with_execution_hook(InstructionCounter::default());
+    vm.transact(tx.clone()).expect("Failed to execute");
+    let expected = vm.execution_hook::<InstructionCounter>().unwrap().0;
+
+    let mut vm = async_vm(&test_context).await;
+    vm.with_execution_hook(InstructionCounter::default());
+    vm.transact_async(tx).await.expect("Failed to execute");
+
+    assert!(vm.as_ref().storage().fetches() > 0);
+    assert_eq!(
+        vm.execution_hook::<InstructionCounter>().unwrap().0,
+        expected
+    );
+}
+
+/// Deploys a contract reading a chain of three storage slots, each holding the key
+/// of the next one, then transferring a coin to the contract id read from the last
+/// slot, and builds a script calling it.
+fn setup_dependent_reads(test_context: &mut TestBuilder) -> Checked<Script> {
+    let recipient = test_context
+        .setup_contract(vec![op::ret(RegId::ONE)], None, None)
+        .contract_id;
+
+    let contract = vec![
+        op::movi(0x10, 64),
+        op::aloc(0x10),
+        op::srwq(RegId::HP, 0x11, RegId::HP, RegId::ONE),
+        op::srwq(RegId::HP, 0x11, RegId::HP, RegId::ONE),
+        op::srwq(RegId::HP, 0x11, RegId::HP, RegId::ONE),
+        op::addi(0x12, RegId::HP, 32),
+        op::tr(RegId::HP, RegId::ONE, 0x12),
+        op::ret(RegId::ONE),
+    ];
+    let slots = vec![
+        StorageSlot::new(Bytes32::zeroed(), Bytes32::new([1; 32])),
+        StorageSlot::new(Bytes32::new([1; 32]), Bytes32::new([2; 32])),
+        StorageSlot::new(Bytes32::new([2; 32]), Bytes32::new(*recipient)),
+    ];
+    let contract_id = test_context
+        .setup_contract(contract, Some((AssetId::zeroed(), 10)), Some(slots))
+        .contract_id;
+
+    let script = vec![
+        op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+        op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+        op::ret(RegId::ONE),
+    ];
+    test_context
+        .start_script(script, Call::new(contract_id, 0, 0).to_bytes())
+        .gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .contract_input(recipient)
+        .fee_input()
+        .contract_output(&contract_id)
+        .contract_output(&recipient)
+        .build()
+}
+
+#[tokio::test]
+async fn suspended_instructions_resume_with_the_fetched_entries() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let tx = setup_dependent_reads(&mut test_context);
+
+    let mut vm = Interpreter::<_, Script>::with_storage(
+        test_context.get_storage().clone(),
+        InterpreterParams::from(&ConsensusParameters::standard()),
+    );
+    let expected = vm.transact(tx.clone()).expect("Failed to execute");
+    assert!(!expected.should_revert());
+    let receipts = expected.receipts().to_vec();
+    let state_changes = expected.state_changes().clone();
+
+    let mut vm = async_vm(&test_context).await;
+    let result = vm.transact_async(tx).await.expect("Failed to execute");
+
+    assert_eq!(result.receipts(), receipts.as_slice());
+    assert_eq!(result.state_changes(), &state_changes);
+}
+
+#[tokio::test]
+async fn changes_of_a_reverted_script_are_undone_after_a_fetch() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract = vec![
+        op::movi(0x10, 32),
+        op::aloc(0x10),
+        op::sww(RegId::HP, 0x11, RegId::ONE),
+        op::srw(0x12, 0x11, RegId::HP),
+        op::rvrt(RegId::ONE),
+    ];
+    let contract_id = test_context
+        .setup_contract(contract, None, None)
+        .contract_id;
+    let script = vec![
+        op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+        op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+        op::ret(RegId::ONE),
+    ];
+    let tx = test_context
+        .start_script(script, Call::new(contract_id, 0, 0).to_bytes())
+        .gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+
+    let mut vm = async_vm(&test_context).await;
+    let result = vm.transact_async(tx).await.expect("Failed to execute");
+
+    assert!(result.should_revert());
+    assert!(result.state_changes().is_empty());
+    let key = ContractsStateKey::new(&contract_id, &Bytes32::zeroed());
+    assert_eq!(
+        StorageInspect::<ContractsState>::get(vm.as_ref(), &key).expect("Fetched"),
+        None
+    );
+}
@@ -1,4 +1,7 @@
-use alloc::vec;
+use alloc::{
+    format,
+    vec,
+};
 
 use crate::{
     consts::*,
@@ -89,4 +92,15 @@ fn backtrace() {
         .expect("Caller expected")
         .to();
     assert_eq!(id, &contract_call);
+
+    let debug = format!("{backtrace:?}");
+    let memory = debug
+        .split("memory: ")
+        .nth(1)
+        .expect("Memory is part of the debug output");
+    assert_eq!(
+        memory.find("..."),
+        Some(26),
+        "Memory is shown as truncated hex"
+    );
 }
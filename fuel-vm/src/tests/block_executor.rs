@@ -0,0 +1,618 @@
+use alloc::{
+    borrow::Cow,
+    vec,
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use core::num::NonZeroUsize;
+#[cfg(feature = "std")]
+use fuel_vm::storage::ContractsState;
+
+use fuel_asm::{
+    op,
+    Instruction,
+    RegId,
+};
+use fuel_tx::{
+    field::{
+        InputContract,
+        MintAmount,
+        OutputContract,
+        Outputs,
+        TxPointer as TxPointerField,
+    },
+    input,
+    output,
+    Chargeable,
+    ConsensusParameters,
+    TransactionBuilder,
+};
+use fuel_types::canonical::Serialize;
+use fuel_vm::{
+    block_executor::{
+        BlockExecutionError,
+        BlockExecutor,
+    },
+    checked_transaction::{
+        CheckPredicateParams,
+        CheckedTransaction,
+        EstimatePredicates,
+        IntoChecked,
+    },
+    crypto::ephemeral_merkle_root,
+    interpreter::InterpreterParams,
+    prelude::*,
+    state::ValueChange,
+    storage::{
+        ContractsAssets,
+        ContractsAssetsStorage,
+    },
+};
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+const COIN_AMOUNT: Word = 10_000_000;
+
+/// Builds a script paying its fee with a coin of the base asset.
+fn paying_script(
+    test_context: &mut TestBuilder,
+    script: Vec<Instruction>,
+) -> CheckedTransaction {
+    let base_asset_id = *test_context.get_base_asset_id();
+    test_context
+        .start_script(script, vec![])
+        .gas_price(1)
+        .gas_limit(1_000_000)
+        .coin_input(base_asset_id, COIN_AMOUNT)
+        .change_output(base_asset_id)
+        .build()
+        .into()
+}
+
+fn change(tx: &Transaction) -> Word {
+    let outputs = match tx {
+        Transaction::Script(script) => script.outputs(),
+        _ => unreachable!(),
+    };
+    outputs
+        .iter()
+        .find_map(|output| match output {
+            Output::Change { amount, .. } => Some(*amount),
+            _ => None,
+        })
+        .expect("No change output")
+}
+
+#[test]
+fn fees_are_minted_to_the_coinbase_contract() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let txs = vec![
+        paying_script(&mut test_context, vec![op::ret(RegId::ONE)]),
+        paying_script(&mut test_context, vec![op::noop(), op::ret(RegId::ONE)]),
+    ];
+
+    let storage = test_context.get_storage().clone();
+    let coinbase = storage.coinbase().unwrap();
+    let base_asset_id = *test_context.get_base_asset_id();
+    let mut executor = BlockExecutor::new(storage, ConsensusParameters::standard());
+    let block = executor
+        .execute_block(txs)
+        .expect("Failed to execute the block");
+
+    let mut total_fee = 0;
+    for tx in &block.transactions {
+        assert!(tx.fee > 0);
+        assert!(!tx.reverted);
+        assert_eq!(COIN_AMOUNT - change(&tx.transaction), tx.fee);
+        total_fee += tx.fee;
+    }
+    assert_eq!(
+        block.fees,
+        [(base_asset_id, total_fee)].into_iter().collect()
+    );
+    assert_eq!(*block.mint.mint_amount(), total_fee);
+    assert_eq!(block.mint.input_contract().contract_id, coinbase);
+    assert_eq!(
+        block.mint_receipts,
+        vec![Receipt::transfer(
+            ContractId::zeroed(),
+            coinbase,
+            total_fee,
+            base_asset_id,
+            0,
+            0
+        )]
+    );
+    assert_eq!(
+        block.gas_used(),
+        block.transactions[0].gas_used + block.transactions[1].gas_used
+    );
+
+    let storage = executor.as_ref();
+    assert_eq!(
+        storage.merkle_contract_asset_id_balance(&coinbase, &base_asset_id),
+        Ok(Some(total_fee))
+    );
+    assert_eq!(
+        block.mint.output_contract().balance_root,
+        MerkleRootStorage::<ContractId, ContractsAssets>::root(storage, &coinbase)
+            .unwrap()
+            .into()
+    );
+}
+
+#[test]
+fn block_roots_cover_all_the_transactions_and_receipts() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let txs = vec![
+        paying_script(
+            &mut test_context,
+            vec![op::log(0, 0, 0, 0), op::ret(RegId::ONE)],
+        ),
+        paying_script(&mut test_context, vec![op::ret(RegId::ONE)]),
+    ];
+
+    let mut executor = BlockExecutor::new(
+        test_context.get_storage().clone(),
+        ConsensusParameters::standard(),
+    );
+    let block = executor
+        .execute_block(txs)
+        .expect("Failed to execute the block");
+
+    let mint_id = block.mint.id(&ConsensusParameters::standard().chain_id);
+    let ids: Vec<_> = block
+        .transactions
+        .iter()
+        .map(|tx| tx.id)
+        .chain([mint_id])
+        .collect();
+    assert_eq!(block.transactions_root, ephemeral_merkle_root(ids.iter()));
+
+    let receipts: Vec<_> = block
+        .transactions
+        .iter()
+        .flat_map(|tx| &tx.receipts)
+        .chain(&block.mint_receipts)
+        .map(Receipt::to_bytes)
+        .collect();
+    assert_eq!(receipts.len(), 6);
+    assert_eq!(block.receipts_root, ephemeral_merkle_root(receipts.iter()));
+}
+
+#[test]
+fn mint_spends_the_last_coinbase_output_of_the_block() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let coinbase = test_context
+        .setup_contract(vec![op::ret(RegId::ONE)], None, None)
+        .contract_id;
+    let mut snapshot = test_context.get_storage().export_snapshot();
+    snapshot.coinbase = coinbase;
+    let storage = MemoryStorage::from_snapshot(&snapshot).expect("Valid snapshot");
+    let block_height = storage.block_height().unwrap();
+    let base_asset_id = *test_context.get_base_asset_id();
+
+    let mut touching_coinbase = || {
+        test_context
+            .start_script(vec![op::ret(RegId::ONE)], vec![])
+            .gas_price(1)
+            .gas_limit(1_000_000)
+            .coin_input(base_asset_id, COIN_AMOUNT)
+            .contract_input(coinbase)
+            .change_output(base_asset_id)
+            .contract_output(&coinbase)
+            .build()
+            .into()
+    };
+    let txs: Vec<CheckedTransaction> = vec![
+        touching_coinbase(),
+        touching_coinbase(),
+        paying_script(&mut test_context, vec![op::ret(RegId::ONE)]),
+    ];
+
+    let mut executor = BlockExecutor::new(storage, ConsensusParameters::standard());
+    let initial_root = MerkleRootStorage::<ContractId, ContractsAssets>::root(
+        executor.as_ref(),
+        &coinbase,
+    )
+    .unwrap();
+    let block = executor
+        .execute_block(txs)
+        .expect("Failed to execute the block");
+
+    assert_eq!(*block.mint.tx_pointer(), TxPointer::new(block_height, 3));
+    let input = block.mint.input_contract();
+    assert_eq!(input.tx_pointer, TxPointer::new(block_height, 1));
+    assert_eq!(input.utxo_id, UtxoId::new(block.transactions[1].id, 1));
+    assert_eq!(input.balance_root, initial_root.into());
+    assert_ne!(
+        block.mint.output_contract().balance_root,
+        input.balance_root
+    );
+}
+
+#[test]
+fn executing_a_mint_records_its_state_changes() {
+    let consensus_params = ConsensusParameters::standard();
+    let storage = MemoryStorage::default();
+    let coinbase = storage.coinbase().unwrap();
+    let block_height = storage.block_height().unwrap();
+    let asset_id = consensus_params.base_asset_id;
+
+    let mint = Transaction::mint(
+        TxPointer::new(block_height, 0),
+        input::contract::Contract {
+            contract_id: coinbase,
+            ..Default::default()
+        },
+        output::contract::Contract::default(),
+        100,
+        asset_id,
+    )
+    .into_checked_basic(block_height, &consensus_params)
+    .expect("Valid mint");
+
+    let mut interpreter = Interpreter::<_, Script>::with_storage(
+        storage,
+        InterpreterParams::from(&consensus_params),
+    );
+    let state = interpreter.execute_mint(mint).expect("Failed to mint");
+
+    assert!(!state.should_revert());
+    let changes = state.state_changes();
+    assert_eq!(changes.minted, [(asset_id, 100)].into_iter().collect());
+    assert_eq!(
+        changes.balances[&coinbase][&asset_id],
+        ValueChange {
+            old: None,
+            new: Some(100)
+        }
+    );
+    assert_eq!(
+        interpreter
+            .as_ref()
+            .merkle_contract_asset_id_balance(&coinbase, &asset_id),
+        Ok(Some(100))
+    );
+}
+
+#[test]
+fn changes_of_reverted_scripts_are_undone() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract = vec![
+        op::movi(0x10, 32),
+        op::aloc(0x10),
+        op::movi(0x11, 5),
+        op::sww(RegId::HP, 0x12, 0x11),
+        op::ret(RegId::ONE),
+    ];
+    let contract_id = test_context
+        .setup_contract(contract, None, None)
+        .contract_id;
+
+    let base_asset_id = *test_context.get_base_asset_id();
+    let script = vec![
+        op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+        op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+        op::rvrt(RegId::ONE),
+    ];
+    let tx = test_context
+        .start_script(script, Call::new(contract_id, 0, 0).to_bytes())
+        .gas_price(1)
+        .gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .coin_input(base_asset_id, COIN_AMOUNT)
+        .contract_output(&contract_id)
+        .change_output(base_asset_id)
+        .build();
+
+    let mut executor = BlockExecutor::new(
+        test_context.get_storage().clone(),
+        ConsensusParameters::standard(),
+    );
+    let block = executor
+        .execute_block([tx.into()])
+        .expect("Failed to execute the block");
+
+    let tx = &block.transactions[0];
+    assert!(tx.reverted);
+    assert!(tx.fee > 0);
+    assert_eq!(block.fees[&base_asset_id], tx.fee);
+    assert_eq!(
+        executor
+            .as_ref()
+            .merkle_contract_state(&contract_id, &Bytes32::zeroed()),
+        Ok(None)
+    );
+}
+
+#[test]
+fn scripts_with_predicates_are_charged_like_the_transactor() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let test_context = TestBuilder::new(2322u64);
+    let consensus_params = ConsensusParameters::standard();
+    let base_asset_id = *test_context.get_base_asset_id();
+
+    let predicate: Vec<u8> = vec![
+        op::addi(0x20, 0x20, 1),
+        op::addi(0x20, 0x20, 1),
+        op::ret(RegId::ONE),
+    ]
+    .into_iter()
+    .collect();
+    let owner = Input::predicate_owner(&predicate);
+    let mut tx = TransactionBuilder::script(
+        vec![op::noop(), op::ret(RegId::ONE)].into_iter().collect(),
+        vec![],
+    )
+    .gas_price(1)
+    .gas_limit(1_000_000)
+    .add_input(Input::coin_predicate(
+        rng.gen(),
+        owner,
+        COIN_AMOUNT,
+        base_asset_id,
+        rng.gen(),
+        Default::default(),
+        0,
+        predicate,
+        vec![],
+    ))
+    .add_output(Output::change(rng.gen(), 0, base_asset_id))
+    .finalize();
+    tx.estimate_predicates(&CheckPredicateParams::from(&consensus_params))
+        .expect("Should estimate predicates");
+    assert!(tx.gas_used_by_predicates() > 0);
+    let tx = tx
+        .into_checked(test_context.get_block_height(), &consensus_params)
+        .expect("Should check the transaction");
+
+    let mut transactor = Transactor::<_, Script>::new(
+        test_context.get_storage().clone(),
+        InterpreterParams::from(&consensus_params),
+    );
+    transactor.transact(tx.clone());
+    let transition = transactor
+        .to_owned_state_transition()
+        .expect("Transaction is executed");
+    let expected_fee = COIN_AMOUNT - change(&transition.tx().clone().into());
+
+    let mut executor =
+        BlockExecutor::new(test_context.get_storage().clone(), consensus_params);
+    let block = executor
+        .execute_block([tx.into()])
+        .expect("Failed to execute the block");
+
+    let tx = &block.transactions[0];
+    assert_eq!(tx.fee, expected_fee);
+    assert_eq!(block.fees[&base_asset_id], expected_fee);
+    assert_eq!(
+        Some(tx.gas_used),
+        tx.receipts.iter().find_map(Receipt::gas_used)
+    );
+}
+
+#[test]
+fn created_contracts_are_deployed() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let params = ConsensusParameters::standard();
+
+    let bytecode: Vec<u8> = vec![op::ret(RegId::ONE)].into_iter().collect();
+    let salt: Salt = rng.gen();
+    let contract = Contract::from(bytecode.clone());
+    let state_root = Contract::default_state_root();
+    let contract_id = contract.id(&salt, &contract.root(), &state_root);
+
+    let tx = TransactionBuilder::create(bytecode.into(), salt, vec![])
+        .add_random_fee_input()
+        .add_output(Output::contract_created(contract_id, state_root))
+        .finalize_checked(Default::default());
+
+    let mut executor = BlockExecutor::new(MemoryStorage::default(), params);
+    let block = executor
+        .execute_block([tx.into()])
+        .expect("Failed to execute the block");
+
+    assert_eq!(block.transactions[0].gas_used, 0);
+    assert!(block.transactions[0].receipts.is_empty());
+    assert_eq!(*block.mint.mint_amount(), 0);
+    let deployed = executor
+        .as_ref()
+        .storage_contract(&contract_id)
+        .expect("Failed to read the contract")
+        .map(Cow::into_owned);
+    assert_eq!(deployed, Some(contract));
+}
+
+#[test]
+fn mint_transactions_are_rejected() {
+    let test_context = TestBuilder::new(2322u64);
+    let params = ConsensusParameters::standard();
+    let storage = test_context.get_storage().clone();
+    let block_height = storage.block_height().unwrap();
+
+    let mint = Transaction::mint(
+        TxPointer::new(block_height, 0),
+        Default::default(),
+        Default::default(),
+        0,
+        params.base_asset_id,
+    )
+    .into_checked_basic(block_height, &params)
+    .expect("Failed to check the mint transaction");
+
+    let mut executor = BlockExecutor::new(storage, params);
+    let result = executor.execute_block([mint.into()]);
+
+    assert!(matches!(
+        result,
+        Err(BlockExecutionError::UnexpectedMint { index: 0 })
+    ));
+}
+
+/// Reads the slot 0, logs it and increments it.
+#[cfg(feature = "std")]
+fn counter() -> Vec<Instruction> {
+    vec![
+        op::movi(0x10, 32),
+        op::aloc(0x10),
+        op::srw(0x11, 0x12, RegId::HP),
+        op::log(0x11, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::addi(0x11, 0x11, 1),
+        op::sww(RegId::HP, 0x12, 0x11),
+        op::ret(RegId::ONE),
+    ]
+}
+
+/// Builds a script calling `contract_id`, ending with `last`.
+#[cfg(feature = "std")]
+fn calling_script(
+    test_context: &mut TestBuilder,
+    contract_id: ContractId,
+    last: Instruction,
+) -> CheckedTransaction {
+    let base_asset_id = *test_context.get_base_asset_id();
+    let script = vec![
+        op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+        op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+        last,
+    ];
+    test_context
+        .start_script(script, Call::new(contract_id, 0, 0).to_bytes())
+        .gas_price(1)
+        .gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .coin_input(base_asset_id, COIN_AMOUNT)
+        .contract_output(&contract_id)
+        .change_output(base_asset_id)
+        .build()
+        .into()
+}
+
+#[cfg(feature = "std")]
+fn create(salt: Salt) -> CheckedTransaction {
+    let bytecode: Vec<u8> = counter().into_iter().collect();
+    let contract = Contract::from(bytecode.clone());
+    let state_root = Contract::default_state_root();
+    let contract_id = contract.id(&salt, &contract.root(), &state_root);
+
+    TransactionBuilder::create(bytecode.into(), salt, vec![])
+        .add_random_fee_input()
+        .add_output(Output::contract_created(contract_id, state_root))
+        .finalize_checked(Default::default())
+        .into()
+}
+
+#[cfg(feature = "std")]
+fn assert_same_storage(
+    storage: &MemoryStorage,
+    expected: &MemoryStorage,
+    contracts: &[ContractId],
+) {
+    assert!(storage
+        .all_contract_state()
+        .eq(expected.all_contract_state()));
+    for contract in contracts {
+        assert_eq!(
+            MerkleRootStorage::<ContractId, ContractsState>::root(storage, contract),
+            MerkleRootStorage::<ContractId, ContractsState>::root(expected, contract),
+        );
+        assert_eq!(
+            MerkleRootStorage::<ContractId, ContractsAssets>::root(storage, contract),
+            MerkleRootStorage::<ContractId, ContractsAssets>::root(expected, contract),
+        );
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn parallel_execution_matches_the_sequential_one() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let shared = test_context
+        .setup_contract(counter(), None, None)
+        .contract_id;
+    let independent = test_context
+        .setup_contract(counter(), None, None)
+        .contract_id;
+    let rng = &mut StdRng::seed_from_u64(1234u64);
+
+    let txs = vec![
+        calling_script(&mut test_context, shared, op::ret(RegId::ONE)),
+        calling_script(&mut test_context, independent, op::ret(RegId::ONE)),
+        calling_script(&mut test_context, shared, op::rvrt(RegId::ONE)),
+        calling_script(&mut test_context, shared, op::ret(RegId::ONE)),
+        create(rng.gen()),
+        calling_script(&mut test_context, shared, op::ret(RegId::ONE)),
+        calling_script(&mut test_context, independent, op::ret(RegId::ONE)),
+        calling_script(&mut test_context, shared, op::ret(RegId::ONE)),
+    ];
+    let storage = test_context.get_storage().clone();
+    let coinbase = storage.coinbase().unwrap();
+
+    let mut sequential =
+        BlockExecutor::new(storage.clone(), ConsensusParameters::standard());
+    let expected = sequential
+        .execute_block(txs.clone())
+        .expect("Failed to execute the block");
+    // The reverted call doesn't increment the counter
+    let mut count = Bytes32::zeroed();
+    count[..8].copy_from_slice(&4u64.to_be_bytes());
+    assert_eq!(
+        *sequential
+            .as_ref()
+            .contract_state(&shared, &Bytes32::zeroed()),
+        count
+    );
+
+    for threads in [1, 2, 4] {
+        let mut parallel =
+            BlockExecutor::new(storage.clone(), ConsensusParameters::standard());
+        let block = parallel
+            .execute_block_parallel(txs.clone(), NonZeroUsize::new(threads).unwrap())
+            .expect("Failed to execute the block");
+
+        assert_eq!(block, expected);
+        assert_same_storage(
+            parallel.as_ref(),
+            sequential.as_ref(),
+            &[shared, independent, coinbase],
+        );
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn parallel_execution_reports_the_failing_transaction() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let shared = test_context
+        .setup_contract(counter(), None, None)
+        .contract_id;
+    let missing = ContractId::from([1; 32]);
+
+    let txs = vec![
+        calling_script(&mut test_context, shared, op::ret(RegId::ONE)),
+        calling_script(&mut test_context, shared, op::ret(RegId::ONE)),
+        calling_script(&mut test_context, missing, op::ret(RegId::ONE)),
+        calling_script(&mut test_context, shared, op::ret(RegId::ONE)),
+    ];
+    let storage = test_context.get_storage().clone();
+
+    let mut sequential =
+        BlockExecutor::new(storage.clone(), ConsensusParameters::standard());
+    let result = sequential.execute_block(txs.clone());
+    assert!(matches!(
+        result,
+        Err(BlockExecutionError::Transaction { index: 2, .. })
+    ));
+
+    let mut parallel = BlockExecutor::new(storage, ConsensusParameters::standard());
+    let result = parallel.execute_block_parallel(txs, NonZeroUsize::new(4).unwrap());
+    assert!(matches!(
+        result,
+        Err(BlockExecutionError::Transaction { index: 2, .. })
+    ));
+    assert_same_storage(parallel.as_ref(), sequential.as_ref(), &[shared]);
+}
@@ -35,7 +35,10 @@ use crate::{
     prelude::*,
 };
 
-use crate::interpreter::InterpreterParams;
+use crate::{
+    interpreter::InterpreterParams,
+    storage::BlockHeader,
+};
 
 use crate::script_with_data_offset;
 use fuel_asm::{
@@ -1751,3 +1754,59 @@ fn coinbase_works() {
 
     assert_eq!(data.as_ref().unwrap(), &*expected);
 }
+
+#[test]
+fn block_headers_of_the_storage_are_used() {
+    let mut client = MemoryClient::default();
+    let previous = BlockHeader {
+        height: 3.into(),
+        hash: Bytes32::new([1; 32]),
+        timestamp: 1_700_000_000,
+        coinbase: ContractId::new([2; 32]),
+        da_height: 100,
+    };
+    let current = BlockHeader {
+        height: 4.into(),
+        hash: Bytes32::new([3; 32]),
+        timestamp: 1_700_000_060,
+        coinbase: ContractId::new([4; 32]),
+        da_height: 101,
+    };
+    let storage = client.as_mut();
+    storage.block_headers_mut().insert(previous);
+    storage.block_headers_mut().insert(current);
+    storage.set_block_height(previous.height);
+    assert_eq!(storage.advance_block(), current.height);
+
+    #[rustfmt::skip]
+    let script = vec![
+        op::movi(0x10, 32),                 // allocation size
+        op::aloc(0x10),                     // allocate memory
+        op::movi(0x11, 3),                  // previous block height
+        op::bhsh(RegId::HP, 0x11),          // previous block hash
+        op::logd(0, 0, RegId::HP, 0x10),    // log output
+        op::time(0x12, 0x11),               // previous block time
+        op::bhei(0x13),                     // current block height
+        op::time(0x14, 0x13),               // current block time
+        op::log(0x12, 0x13, 0x14, 0),       // log output
+        op::cb(RegId::HP),                  // current coinbase
+        op::logd(0, 0, RegId::HP, 0x10),    // log output
+        op::ret(RegId::ONE)
+    ];
+
+    let tx = TransactionBuilder::script(script.into_iter().collect(), vec![])
+        .gas_limit(1_000_000)
+        .add_random_fee_input()
+        .finalize_checked(current.height);
+
+    let receipts = client.transact(tx);
+    let [Receipt::LogData { data: hash, .. }, Receipt::Log { ra, rb, rc, .. }, Receipt::LogData { data: coinbase, .. }, ..] =
+        receipts
+    else {
+        panic!("expected log receipts");
+    };
+
+    assert_eq!(hash.as_deref(), Some(previous.hash.as_ref()));
+    assert_eq!((*ra, *rb, *rc), (previous.timestamp, 4, current.timestamp));
+    assert_eq!(coinbase.as_deref(), Some(current.coinbase.as_ref()));
+}
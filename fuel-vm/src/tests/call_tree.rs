@@ -0,0 +1,171 @@
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use fuel_asm::{
+    op,
+    GTFArgs,
+    Instruction,
+    PanicReason,
+    RegId,
+};
+use fuel_tx::ConsensusParameters;
+use fuel_types::canonical::Serialize;
+use fuel_vm::{
+    checked_transaction::Checked,
+    hook::{
+        CallOutcome,
+        CallTree,
+    },
+    interpreter::InterpreterParams,
+    prelude::*,
+};
+
+/// Deploys a contract calling `callee`, then builds a script calling that contract.
+fn setup(
+    callee: Vec<Instruction>,
+) -> (TestBuilder, ContractId, ContractId, Checked<Script>) {
+    let mut test_context = TestBuilder::new(2322u64);
+
+    let callee_id = test_context.setup_contract(callee, None, None).contract_id;
+
+    // Calls the contract described by the second `Call` of the script data
+    let caller = vec![
+        op::movi(0x11, 0x2a),
+        op::log(0x11, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+        op::addi(0x10, 0x10, Call::LEN as Immediate12),
+        op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+        op::ret(RegId::ONE),
+    ];
+    let caller_id = test_context.setup_contract(caller, None, None).contract_id;
+
+    // The asset id of the call points to the caller id, no coins are forwarded
+    let script = vec![
+        op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+        op::call(0x10, RegId::ZERO, 0x10, RegId::CGAS),
+        op::ret(RegId::ONE),
+    ];
+    let script_data: Vec<u8> = [
+        Call::new(caller_id, 1, 2).to_bytes(),
+        Call::new(callee_id, 3, 4).to_bytes(),
+    ]
+    .concat();
+
+    let tx = test_context
+        .start_script(script, script_data)
+        .gas_limit(1_000_000)
+        .contract_input(caller_id)
+        .contract_input(callee_id)
+        .fee_input()
+        .contract_output(&caller_id)
+        .contract_output(&callee_id)
+        .build();
+
+    (test_context, caller_id, callee_id, tx)
+}
+
+fn transact(test_context: &TestBuilder, tx: Checked<Script>) -> CallTree {
+    let params = InterpreterParams::from(&ConsensusParameters::standard());
+    let mut transactor = Transactor::new(test_context.get_storage().clone(), params);
+    transactor.with_call_tracer().transact(tx);
+
+    transactor
+        .call_tree()
+        .expect("Call tracing is enabled")
+        .clone()
+}
+
+#[test]
+fn call_tree_nests_successful_calls() {
+    let (test_context, caller_id, callee_id, tx) = setup(vec![
+        op::movi(0x11, 0x07),
+        op::log(0x11, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::ret(0x11),
+    ]);
+
+    let tree = transact(&test_context, tx);
+
+    assert_eq!(tree.outcome, Some(CallOutcome::Return(1)));
+    assert!(tree.logs.is_empty());
+    assert_eq!(tree.calls.len(), 1);
+
+    let caller = &tree.calls[0];
+    assert_eq!(caller.contract_id, caller_id);
+    assert_eq!((caller.param1, caller.param2), (1, 2));
+    assert_eq!(
+        (caller.amount, caller.asset_id),
+        (0, AssetId::new(*caller_id))
+    );
+    assert_eq!(caller.outcome, Some(CallOutcome::Return(1)));
+    assert!(matches!(caller.logs[..], [Receipt::Log { ra: 0x2a, .. }]));
+    assert_eq!(caller.calls.len(), 1);
+
+    let callee = &caller.calls[0];
+    assert_eq!(callee.contract_id, callee_id);
+    assert_eq!((callee.param1, callee.param2), (3, 4));
+    assert_eq!(callee.outcome, Some(CallOutcome::Return(7)));
+    assert!(matches!(callee.logs[..], [Receipt::Log { ra: 0x07, .. }]));
+    assert!(callee.calls.is_empty());
+
+    let caller_exit = caller.gas_at_exit.expect("Caller returned");
+    let callee_exit = callee.gas_at_exit.expect("Callee returned");
+    assert!(caller.gas_at_entry > callee.gas_at_entry);
+    assert!(callee.gas_at_entry > callee_exit);
+    assert!(caller_exit < caller.gas_at_entry - (callee.gas_at_entry - callee_exit));
+
+    let mut visited = vec![];
+    tree.walk(|depth, node| visited.push((depth, node.contract_id)));
+    assert_eq!(visited, vec![(1, caller_id), (2, callee_id)]);
+}
+
+#[test]
+fn call_tree_records_nested_revert() {
+    let (test_context, _, _, tx) = setup(vec![op::rvrt(RegId::ONE)]);
+
+    let tree = transact(&test_context, tx);
+
+    let caller = &tree.calls[0];
+    let callee = &caller.calls[0];
+    assert_eq!(callee.outcome, Some(CallOutcome::Revert(1)));
+    assert!(callee.gas_at_exit.is_some());
+    assert_eq!(caller.outcome, Some(CallOutcome::Revert(1)));
+    assert_eq!(caller.gas_at_exit, None);
+    assert_eq!(tree.outcome, Some(CallOutcome::Revert(1)));
+}
+
+#[test]
+fn call_tree_records_nested_panic() {
+    let (test_context, _, _, tx) = setup(vec![
+        op::div(0x10, RegId::ONE, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ]);
+
+    let tree = transact(&test_context, tx);
+
+    let panic = Some(CallOutcome::Panic(PanicReason::ArithmeticError));
+    let callee = &tree.calls[0].calls[0];
+    assert_eq!(callee.outcome, panic);
+    assert_eq!(tree.calls[0].outcome, panic);
+    assert_eq!(tree.calls[0].gas_at_exit, None);
+    assert_eq!(tree.outcome, panic);
+}
+
+#[test]
+fn memory_client_exposes_call_tree_of_last_transaction() {
+    let (test_context, caller_id, _, tx) = setup(vec![op::ret(RegId::ONE)]);
+    let params = InterpreterParams::from(&ConsensusParameters::standard());
+
+    let mut client = MemoryClient::new(test_context.get_storage().clone(), params);
+    assert!(client.call_tree().is_none());
+
+    client.with_call_tracer();
+    client.transact(tx.clone());
+    let first = client.call_tree().expect("Call tracing is enabled").clone();
+    assert_eq!(first.calls.len(), 1);
+    assert_eq!(first.calls[0].contract_id, caller_id);
+
+    client.transact(tx);
+    assert_eq!(client.call_tree(), Some(&first));
+}
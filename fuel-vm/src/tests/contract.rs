@@ -1,6 +1,7 @@
 use crate::{
     prelude::*,
     script_with_data_offset,
+    storage::ContractsState,
     util::test_helpers::TestBuilder,
 };
 use alloc::{
@@ -11,8 +12,10 @@ use fuel_asm::{
     op,
     RegId,
 };
+use fuel_storage::MerkleRootStorage;
 use fuel_tx::{
     ConsensusParameters,
+    StorageSlot,
     Witness,
 };
 use fuel_types::canonical::Serialize;
@@ -585,3 +588,25 @@ fn internal_transfer_cant_exceed_more_than_source_contract_balance() {
         test_context.get_contract_balance(&sender_contract_id, &asset_id);
     assert_eq!(source_balance, initial_internal_balance);
 }
+
+#[test]
+fn state_root_of_deployed_contract_matches_the_create_transaction() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let storage_slots: Vec<StorageSlot> = (0..4u8)
+        .map(|k| StorageSlot::new([k; 32].into(), [k + 1; 32].into()))
+        .collect();
+    let contract_id = test_context
+        .setup_contract(vec![op::ret(RegId::ONE)], None, Some(storage_slots.clone()))
+        .contract_id;
+
+    let root = MerkleRootStorage::<ContractId, ContractsState>::root(
+        test_context.get_storage(),
+        &contract_id,
+    )
+    .expect("Failed to compute the state root");
+
+    assert_eq!(
+        Bytes32::from(root),
+        Contract::initial_state_root(storage_slots.iter())
+    );
+}
@@ -0,0 +1,307 @@
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use fuel_asm::{
+    op,
+    GTFArgs,
+    Instruction,
+    RegId,
+};
+use fuel_tx::ConsensusParameters;
+use fuel_types::canonical::Serialize;
+use fuel_vm::{
+    checked_transaction::Checked,
+    consts::VM_MAX_RAM,
+    interpreter::InterpreterParams,
+    prelude::*,
+    state::{
+        BreakCondition,
+        Comparison,
+        Watchpoint,
+    },
+};
+
+fn script(program: Vec<Instruction>) -> Checked<Script> {
+    TransactionBuilder::script(program.into_iter().collect(), vec![])
+        .gas_limit(1_000_000)
+        .add_random_fee_input()
+        .finalize()
+        .into_checked(Default::default(), &ConsensusParameters::standard())
+        .expect("failed to generate checked tx")
+}
+
+fn stopped_at(state: &ProgramState) -> Breakpoint {
+    *state
+        .debug_ref()
+        .and_then(DebugEval::breakpoint)
+        .expect("Expected a debug event")
+}
+
+/// Deploys `contract` and builds a script that calls it, then logs `0x11`.
+fn call_setup(
+    contract: Vec<Instruction>,
+) -> (
+    Interpreter<MemoryStorage, Script>,
+    ContractId,
+    Checked<Script>,
+) {
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract_id = test_context
+        .setup_contract(contract, None, None)
+        .contract_id;
+
+    let script = vec![
+        op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+        op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+        op::log(0x11, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ];
+    let tx = test_context
+        .start_script(script, Call::new(contract_id, 0, 0).to_bytes())
+        .gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+
+    let vm = Interpreter::with_storage(
+        test_context.get_storage().clone(),
+        InterpreterParams::from(&ConsensusParameters::standard()),
+    );
+
+    (vm, contract_id, tx)
+}
+
+#[test]
+fn conditional_breakpoint_on_register() {
+    let mut vm = Interpreter::with_memory_storage();
+
+    // Counts 0x11 up to 5
+    let tx = script(vec![
+        op::movi(0x10, 5),
+        op::addi(0x11, 0x11, 1),
+        op::jnei(0x10, 0x11, 1),
+        op::ret(0x11),
+    ]);
+
+    vm.set_conditional_breakpoint(
+        Breakpoint::script(2),
+        BreakCondition::Register {
+            register: 0x11,
+            comparison: Comparison::Eq,
+            value: 3,
+        },
+    );
+
+    let state = vm.transact(tx).map(ProgramState::from).unwrap();
+    assert_eq!(stopped_at(&state), Breakpoint::script(2));
+    assert_eq!(vm.registers()[0x11], 3);
+
+    let state = vm.resume().expect("Failed to resume");
+    assert_eq!(state, ProgramState::Return(5));
+}
+
+#[test]
+fn conditional_breakpoint_on_memory_and_gas() {
+    let mut vm = Interpreter::with_memory_storage();
+
+    let tx = script(vec![
+        op::movi(0x10, 8),
+        op::aloc(0x10),
+        op::movi(0x11, 0x2a),
+        op::sw(RegId::HP, 0x11, 0),
+        op::noop(),
+        op::noop(),
+        op::ret(RegId::ONE),
+    ]);
+
+    let mut expected = [0u8; 8];
+    expected[7] = 0x2a;
+    let memory = BreakCondition::Memory {
+        address: VM_MAX_RAM - 8,
+        bytes: expected.to_vec(),
+    };
+
+    // The memory condition only holds once the word is stored
+    vm.set_conditional_breakpoint(Breakpoint::script(3), memory.clone());
+    vm.set_conditional_breakpoint(Breakpoint::script(4), memory);
+    vm.set_conditional_breakpoint(Breakpoint::script(5), BreakCondition::GasBelow(0));
+
+    let state = vm.transact(tx).map(ProgramState::from).unwrap();
+    assert_eq!(stopped_at(&state), Breakpoint::script(4));
+
+    let state = vm.resume().expect("Failed to resume");
+    assert_eq!(state, ProgramState::Return(1));
+}
+
+#[test]
+fn memory_watchpoint_breaks_after_write() {
+    let mut vm = Interpreter::with_memory_storage();
+
+    let tx = script(vec![
+        op::movi(0x10, 16),
+        op::aloc(0x10),
+        op::movi(0x11, 0x2a),
+        op::sw(RegId::HP, 0x11, 1),
+        op::sw(RegId::HP, 0x11, 0),
+        op::ret(RegId::ONE),
+    ]);
+
+    let watchpoint = Watchpoint::Memory {
+        address: VM_MAX_RAM - 8,
+        len: 8,
+    };
+    vm.set_watchpoint(watchpoint).expect("Watchpoint in bounds");
+    assert!(vm
+        .set_watchpoint(Watchpoint::Memory {
+            address: VM_MAX_RAM,
+            len: 1,
+        })
+        .is_err());
+
+    let state = vm.transact(tx).map(ProgramState::from).unwrap();
+    let debug = state.debug_ref().expect("Expected a debug event");
+    assert_eq!(debug.watchpoint(), Some(&watchpoint));
+    assert_eq!(stopped_at(&state), Breakpoint::script(4));
+
+    let state = vm.resume().expect("Failed to resume");
+    assert_eq!(state, ProgramState::Return(1));
+}
+
+#[test]
+fn storage_watchpoint_breaks_inside_contract() {
+    // Stores to the zeroed key of a freshly allocated heap slot
+    let (mut vm, contract_id, tx) = call_setup(vec![
+        op::movi(0x10, 32),
+        op::aloc(0x10),
+        op::movi(0x10, 0x2a),
+        op::sww(RegId::HP, 0x11, 0x10),
+        op::ret(RegId::ONE),
+    ]);
+
+    let watchpoint = Watchpoint::Storage {
+        contract: contract_id,
+        key: Bytes32::zeroed(),
+    };
+    vm.set_watchpoint(watchpoint).expect("Storage watchpoint");
+
+    let state = vm.transact(tx).map(ProgramState::from).unwrap();
+    assert_eq!(
+        state.debug_ref().and_then(DebugEval::watchpoint),
+        Some(&watchpoint)
+    );
+    assert_eq!(stopped_at(&state), Breakpoint::new(contract_id, 4));
+
+    let state = vm.resume().expect("Failed to resume");
+    assert_eq!(state, ProgramState::Return(1));
+}
+
+#[test]
+fn breakpoint_inside_contract() {
+    let (mut vm, contract_id, tx) =
+        call_setup(vec![op::noop(), op::noop(), op::ret(RegId::ONE)]);
+
+    vm.set_breakpoint(Breakpoint::new(contract_id, 1));
+
+    let state = vm.transact(tx).map(ProgramState::from).unwrap();
+    assert_eq!(stopped_at(&state), Breakpoint::new(contract_id, 1));
+
+    let state = vm.resume().expect("Failed to resume");
+    assert_eq!(state, ProgramState::Return(1));
+}
+
+#[test]
+fn step_over_into_and_out_of_call() {
+    let contract = vec![op::noop(), op::noop(), op::ret(RegId::ONE)];
+
+    // Step over the call
+    let (mut vm, _, tx) = call_setup(contract.clone());
+    vm.set_breakpoint(Breakpoint::script(1));
+    let state = vm.transact(tx).map(ProgramState::from).unwrap();
+    assert_eq!(stopped_at(&state), Breakpoint::script(1));
+
+    let state = vm.step_over().expect("Failed to step");
+    assert_eq!(stopped_at(&state), Breakpoint::script(2));
+    let state = vm.step_over().expect("Failed to step");
+    assert_eq!(stopped_at(&state), Breakpoint::script(3));
+    let state = vm.step_over().expect("Failed to step");
+    assert_eq!(state, ProgramState::Return(1));
+
+    // Step into the call, then out of it
+    let (mut vm, contract_id, tx) = call_setup(contract);
+    vm.set_breakpoint(Breakpoint::script(1));
+    vm.transact(tx).unwrap();
+
+    let state = vm.step_into().expect("Failed to step");
+    assert_eq!(stopped_at(&state), Breakpoint::new(contract_id, 0));
+    let state = vm.step_into().expect("Failed to step");
+    assert_eq!(stopped_at(&state), Breakpoint::new(contract_id, 1));
+
+    let state = vm.step_out().expect("Failed to step");
+    assert_eq!(stopped_at(&state), Breakpoint::script(2));
+    let state = vm.resume().expect("Failed to resume");
+    assert_eq!(state, ProgramState::Return(1));
+}
+
+#[test]
+fn registers_and_memory_are_writable_while_stopped() {
+    let mut vm = Interpreter::with_memory_storage();
+    assert!(vm.debug_registers_mut().is_none());
+
+    let tx = script(vec![
+        op::movi(0x10, 8),
+        op::aloc(0x10),
+        op::movi(0x10, 1),
+        op::lw(0x12, RegId::HP, 0),
+        op::log(0x10, 0x12, RegId::ZERO, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ]);
+
+    vm.set_breakpoint(Breakpoint::script(3));
+    let state = vm.transact(tx).map(ProgramState::from).unwrap();
+    assert_eq!(stopped_at(&state), Breakpoint::script(3));
+    assert!(vm.is_debug_stopped());
+
+    vm.debug_registers_mut().expect("VM is stopped")[0x10] = 0x2a;
+    let hp = vm.registers()[RegId::HP] as usize;
+    vm.debug_memory_mut()
+        .expect("VM is stopped")
+        .write(hp, &7u64.to_be_bytes());
+
+    let state = vm.resume().expect("Failed to resume");
+    assert_eq!(state, ProgramState::Return(1));
+    assert!(!vm.is_debug_stopped());
+    assert!(vm.debug_memory_mut().is_none());
+
+    let log = vm
+        .receipts()
+        .iter()
+        .find(|r| matches!(r, Receipt::Log { .. }))
+        .expect("Expected a log receipt");
+    assert_eq!((log.ra(), log.rb()), (Some(0x2a), Some(7)));
+}
+
+#[test]
+fn abandoned_session_releases_the_storage_checkpoint() {
+    let mut vm = Interpreter::with_memory_storage();
+    vm.set_breakpoint(Breakpoint::script(1));
+
+    let state = vm
+        .transact(script(vec![op::noop(), op::noop(), op::ret(RegId::ONE)]))
+        .map(ProgramState::from)
+        .unwrap();
+    assert_eq!(stopped_at(&state), Breakpoint::script(1));
+
+    // The suspended execution is abandoned for another transaction
+    vm.remove_breakpoint(&Breakpoint::script(1));
+    let state = vm
+        .transact(script(vec![op::noop(), op::ret(RegId::ONE)]))
+        .map(ProgramState::from)
+        .unwrap();
+    assert_eq!(state, ProgramState::Return(1));
+
+    let checkpoint = vm.as_mut().checkpoint().unwrap();
+    assert_eq!(checkpoint.depth(), 0);
+}
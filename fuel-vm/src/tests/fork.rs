@@ -0,0 +1,225 @@
+use alloc::{
+    borrow::Cow,
+    vec,
+    vec::Vec,
+};
+use core::{
+    cell::Cell,
+    convert::Infallible,
+};
+
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_storage::{
+    Mappable,
+    StorageInspect,
+};
+use fuel_tx::ConsensusParameters;
+use fuel_types::{
+    canonical::Serialize,
+    BlockHeight,
+};
+use fuel_vm::{
+    checked_transaction::Checked,
+    interpreter::InterpreterParams,
+    prelude::*,
+    storage::{
+        BlockHeader,
+        BlockHeaderProvider,
+        ContractsState,
+        ContractsStateKey,
+        ForkedStorage,
+        InterpreterStorage,
+    },
+};
+
+/// In-process provider of the state of a chain, counting the entries fetched from
+/// it.
+#[derive(Debug)]
+struct MockProvider {
+    storage: MemoryStorage,
+    fetches: Cell<usize>,
+}
+
+impl MockProvider {
+    fn new(storage: MemoryStorage) -> Self {
+        Self {
+            storage,
+            fetches: Cell::new(0),
+        }
+    }
+}
+
+impl<Type: Mappable> StorageInspect<Type> for MockProvider
+where
+    MemoryStorage: StorageInspect<Type, Error = Infallible>,
+{
+    type Error = Infallible;
+
+    fn get(
+        &self,
+        key: &Type::Key,
+    ) -> Result<Option<Cow<'_, Type::OwnedValue>>, Infallible> {
+        self.fetches.set(self.fetches.get() + 1);
+        StorageInspect::<Type>::get(&self.storage, key)
+    }
+
+    fn contains_key(&self, key: &Type::Key) -> Result<bool, Infallible> {
+        self.fetches.set(self.fetches.get() + 1);
+        StorageInspect::<Type>::contains_key(&self.storage, key)
+    }
+}
+
+impl BlockHeaderProvider for MockProvider {
+    type Error = Infallible;
+
+    fn block_header(&self, height: BlockHeight) -> Result<BlockHeader, Infallible> {
+        self.storage.block_header(height)
+    }
+}
+
+fn key(index: u8) -> Bytes32 {
+    let mut key = Bytes32::zeroed();
+    key[31] = index;
+    key
+}
+
+fn script_calling(
+    test_context: &mut TestBuilder,
+    contract_id: ContractId,
+) -> Checked<Script> {
+    let script = vec![
+        op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+        op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+        op::ret(RegId::ONE),
+    ];
+    test_context
+        .start_script(script, Call::new(contract_id, 0, 0).to_bytes())
+        .gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build()
+}
+
+fn fetches(transactor: &Transactor<ForkedStorage<MockProvider>, Script>) -> usize {
+    let storage: &ForkedStorage<MockProvider> = transactor.as_ref();
+    storage.provider().fetches.get()
+}
+
+fn fork(test_context: &TestBuilder) -> Transactor<ForkedStorage<MockProvider>, Script> {
+    let provider = MockProvider::new(test_context.get_storage().clone());
+    let block_height = provider.storage.block_height().expect("Infallible");
+
+    Transactor::new(
+        ForkedStorage::new(provider, block_height),
+        InterpreterParams::from(&ConsensusParameters::standard()),
+    )
+}
+
+#[test]
+fn entries_are_fetched_lazily_and_writes_stay_local() {
+    let mut test_context = TestBuilder::new(2322u64);
+    // Increments the slot 0.
+    let contract = vec![
+        op::movi(0x12, 32),
+        op::aloc(0x12),
+        op::srw(0x10, 0x11, RegId::HP),
+        op::addi(0x10, 0x10, 1),
+        op::sww(RegId::HP, 0x11, 0x10),
+        op::ret(RegId::ONE),
+    ];
+    let contract_id = test_context
+        .setup_contract(contract, None, None)
+        .contract_id;
+    let tx = script_calling(&mut test_context, contract_id);
+
+    let mut transactor = fork(&test_context);
+    assert_eq!(fetches(&transactor), 0);
+
+    transactor.transact(tx.clone());
+    assert!(transactor.is_success());
+    let fetched = fetches(&transactor);
+    assert!(fetched > 0);
+
+    transactor.transact(tx);
+    assert!(transactor.is_success());
+    assert_eq!(fetches(&transactor), fetched);
+
+    let slot = ContractsStateKey::new(&contract_id, &key(0));
+    let storage: &ForkedStorage<MockProvider> = transactor.as_ref();
+    let value = storage
+        .storage_as_ref::<ContractsState>()
+        .get(&slot)
+        .expect("Failed to read the slot")
+        .expect("The slot is set")
+        .into_owned();
+    assert_eq!(value[..8], 2u64.to_be_bytes());
+
+    assert_eq!(
+        storage
+            .provider()
+            .storage
+            .storage_as_ref::<ContractsState>()
+            .get(&slot)
+            .expect("Infallible"),
+        None
+    );
+}
+
+#[test]
+fn failing_transaction_is_replayed_with_patched_contract() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract_id = test_context
+        .setup_contract(vec![op::rvrt(RegId::ONE)], None, None)
+        .contract_id;
+    let tx = script_calling(&mut test_context, contract_id);
+
+    let mut transactor = fork(&test_context);
+    transactor.transact(tx.clone());
+    assert!(transactor.is_reverted());
+
+    // Logs the slot 0 and the balance of the zero asset.
+    let patched: Contract = vec![
+        op::movi(0x12, 32),
+        op::aloc(0x12),
+        op::srw(0x10, 0x11, RegId::HP),
+        op::bal(0x13, RegId::HP, RegId::FP),
+        op::log(0x10, 0x13, RegId::ZERO, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ]
+    .into_iter()
+    .collect::<Vec<u8>>()
+    .into();
+    let root = patched.root();
+    let mut value = Bytes32::zeroed();
+    value[..8].copy_from_slice(&7u64.to_be_bytes());
+
+    let storage = transactor.as_mut();
+    storage
+        .set_contract_code(&contract_id, patched)
+        .expect("Failed to patch the code");
+    storage
+        .set_storage_slot(&contract_id, &key(0), &value)
+        .expect("Failed to set the slot");
+    storage
+        .set_balance(&contract_id, &AssetId::zeroed(), 42)
+        .expect("Failed to set the balance");
+
+    transactor.transact(tx);
+    assert!(transactor.is_success());
+    let receipts = transactor.receipts().expect("The transaction was executed");
+    assert!(receipts
+        .iter()
+        .any(|receipt| matches!(receipt, Receipt::Log { ra: 7, rb: 42, .. })));
+    let storage: &ForkedStorage<MockProvider> = transactor.as_ref();
+    assert_eq!(
+        storage
+            .storage_contract_root(&contract_id)
+            .expect("Failed to read the root")
+            .map(|info| info.1),
+        Some(root)
+    );
+}
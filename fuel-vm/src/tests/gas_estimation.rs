@@ -0,0 +1,197 @@
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use fuel_asm::{
+    op,
+    GTFArgs,
+    Instruction,
+    RegId,
+};
+use fuel_tx::{
+    ConsensusParameters,
+    TransactionFee,
+};
+use fuel_types::canonical::Serialize;
+use fuel_vm::{
+    checked_transaction::Checked,
+    interpreter::InterpreterParams,
+    prelude::*,
+    transactor::EstimateScriptGasError,
+};
+
+/// Calls `contract` forwarding half of the context gas.
+fn call_half_of_cgas(
+    test_context: &mut TestBuilder,
+    contract_id: ContractId,
+    gas_limit: Word,
+) -> Checked<Script> {
+    let script = vec![
+        op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+        op::divi(0x11, RegId::CGAS, 2),
+        op::call(0x10, RegId::ZERO, RegId::ZERO, 0x11),
+        op::ret(RegId::ONE),
+    ];
+    test_context
+        .start_script(script, Call::new(contract_id, 0, 0).to_bytes())
+        .gas_limit(gas_limit)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build()
+}
+
+fn script(
+    test_context: &mut TestBuilder,
+    program: Vec<Instruction>,
+    gas_limit: Word,
+) -> Checked<Script> {
+    test_context
+        .start_script(program, vec![])
+        .gas_limit(gas_limit)
+        .fee_input()
+        .build()
+}
+
+fn max_gas_per_tx() -> Word {
+    ConsensusParameters::standard().tx_params().max_gas_per_tx
+}
+
+fn client(test_context: &TestBuilder) -> MemoryClient {
+    MemoryClient::new(
+        test_context.get_storage().clone(),
+        InterpreterParams::from(&ConsensusParameters::standard()),
+    )
+}
+
+fn result(test_context: &mut TestBuilder, tx: Checked<Script>) -> ScriptExecutionResult {
+    *test_context
+        .execute_tx(tx)
+        .expect("Failed to execute")
+        .receipts()
+        .iter()
+        .find_map(Receipt::result)
+        .expect("Expected a script result")
+}
+
+fn gas_used(receipts: &[Receipt]) -> Word {
+    receipts
+        .iter()
+        .find_map(Receipt::gas_used)
+        .expect("Expected a script result")
+}
+
+#[test]
+fn estimate_is_the_gas_used_by_simple_scripts() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let program = vec![
+        op::movi(0x10, 0x2a),
+        op::log(0x10, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ];
+    let tx = script(&mut test_context, program.clone(), 1_000_000);
+
+    let estimate = client(&test_context)
+        .estimate_script_gas(&tx, max_gas_per_tx())
+        .expect("Failed to estimate");
+    assert_eq!(estimate.gas_limit, gas_used(&estimate.receipts));
+
+    let tx = script(&mut test_context, program.clone(), estimate.gas_limit);
+    assert_eq!(
+        result(&mut test_context, tx),
+        ScriptExecutionResult::Success
+    );
+    let tx = script(&mut test_context, program, estimate.gas_limit - 1);
+    assert_eq!(result(&mut test_context, tx), ScriptExecutionResult::Panic);
+}
+
+#[test]
+fn estimate_covers_the_gas_forwarded_to_calls() {
+    let mut test_context = TestBuilder::new(2322u64);
+
+    // Burns gas in a loop
+    let contract = vec![
+        op::movi(0x10, 100),
+        op::subi(0x10, 0x10, 1),
+        op::jnei(0x10, RegId::ZERO, 1),
+        op::ret(RegId::ONE),
+    ];
+    let contract_id = test_context
+        .setup_contract(contract, None, None)
+        .contract_id;
+
+    let tx = call_half_of_cgas(&mut test_context, contract_id, 1_000_000);
+    let estimate = client(&test_context)
+        .estimate_script_gas(&tx, max_gas_per_tx())
+        .expect("Failed to estimate");
+
+    // The call is forwarded half of the remaining gas, which must cover the loop
+    assert!(estimate.gas_limit > gas_used(&estimate.receipts));
+
+    let tx = call_half_of_cgas(&mut test_context, contract_id, estimate.gas_limit);
+    assert_eq!(
+        result(&mut test_context, tx),
+        ScriptExecutionResult::Success
+    );
+    let tx = call_half_of_cgas(&mut test_context, contract_id, estimate.gas_limit - 1);
+    assert_eq!(result(&mut test_context, tx), ScriptExecutionResult::Panic);
+}
+
+#[test]
+fn estimate_charges_the_fee_of_the_estimated_limit() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let mut tx = |gas_limit| {
+        test_context
+            .start_script(vec![op::noop(), op::ret(RegId::ONE)], vec![])
+            .gas_price(10)
+            .gas_limit(gas_limit)
+            .coin_input(AssetId::BASE, 100_000_000)
+            .change_output(AssetId::BASE)
+            .build()
+    };
+
+    let estimate = MemoryClient::default()
+        .estimate_script_gas(&tx(1_000_000), max_gas_per_tx())
+        .expect("Failed to estimate");
+
+    let fee = TransactionFee::checked_from_tx(
+        ConsensusParameters::standard().fee_params(),
+        tx(estimate.gas_limit).transaction(),
+    );
+    assert_eq!(Some(estimate.fee), fee);
+    assert_ne!(estimate.fee.max_fee(), 0);
+}
+
+#[test]
+fn estimate_fails_for_reverting_scripts() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let tx = script(&mut test_context, vec![op::rvrt(RegId::ONE)], 1_000_000);
+
+    let error = client(&test_context)
+        .estimate_script_gas(&tx, max_gas_per_tx())
+        .expect_err("Reverting scripts can't be estimated");
+    assert!(matches!(
+        error,
+        EstimateScriptGasError::ScriptFailed {
+            result: ScriptExecutionResult::Revert,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn estimate_is_capped_by_the_max_gas_per_tx() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let program = vec![op::noop(), op::ji(0)];
+    let tx = script(&mut test_context, program, 1_000_000);
+
+    let error = client(&test_context)
+        .estimate_script_gas(&tx, 100_000)
+        .expect_err("Endless scripts can't be estimated");
+    let EstimateScriptGasError::ScriptFailed { result, receipts } = error else {
+        panic!("Expected the script to fail")
+    };
+    assert_eq!(result, ScriptExecutionResult::Panic);
+    assert_eq!(gas_used(&receipts), 100_000);
+}
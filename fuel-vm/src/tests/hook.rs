@@ -0,0 +1,253 @@
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use fuel_asm::{
+    op,
+    Instruction,
+    Opcode,
+    PanicReason,
+    RawInstruction,
+    RegId,
+};
+use fuel_tx::ConsensusParameters;
+use fuel_vm::{
+    consts::VM_REGISTER_COUNT,
+    hook::{
+        ExecutionHook,
+        ExecutionTrace,
+        InstructionOutcome,
+        InstructionStep,
+        TraceRecorder,
+    },
+    interpreter::InterpreterParams,
+    prelude::*,
+};
+
+fn transact_with_hook<H>(
+    script: Vec<Instruction>,
+    hook: H,
+) -> (Vec<Receipt>, Interpreter<MemoryStorage, Script>)
+where
+    H: ExecutionHook + Send + Sync,
+{
+    transact_bytes_with_hook(script.into_iter().collect(), hook)
+}
+
+fn transact_bytes_with_hook<H>(
+    script: Vec<u8>,
+    hook: H,
+) -> (Vec<Receipt>, Interpreter<MemoryStorage, Script>)
+where
+    H: ExecutionHook + Send + Sync,
+{
+    let consensus_params = ConsensusParameters::standard();
+    let tx = TransactionBuilder::script(script, vec![])
+        .gas_price(0)
+        .gas_limit(1_000_000)
+        .maturity(Default::default())
+        .add_random_fee_input()
+        .finalize()
+        .into_checked(Default::default(), &consensus_params)
+        .expect("failed to generate a checked tx");
+
+    let mut vm = Interpreter::with_storage(
+        MemoryStorage::default(),
+        InterpreterParams::from(&consensus_params),
+    );
+    vm.with_execution_hook(hook);
+
+    let mut transactor = Transactor::from(vm);
+    transactor.transact(tx);
+    let receipts = transactor.receipts().expect("Expected receipts").to_vec();
+
+    (receipts, transactor.into())
+}
+
+fn recorded_trace(vm: &Interpreter<MemoryStorage, Script>) -> &ExecutionTrace {
+    vm.execution_hook::<TraceRecorder>()
+        .expect("Recorder is installed")
+        .trace()
+}
+
+#[test]
+fn trace_recorder_records_every_instruction() {
+    let script = vec![
+        op::movi(0x10, 5),
+        op::addi(0x11, 0x10, 1),
+        op::log(0x10, 0x11, RegId::ZERO, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ];
+
+    let (receipts, vm) = transact_with_hook(script.clone(), TraceRecorder::new());
+    let trace = recorded_trace(&vm);
+
+    assert_eq!(trace.steps.len(), script.len());
+    assert!(!trace.failed());
+
+    for (i, (step, instruction)) in trace.steps.iter().zip(script).enumerate() {
+        assert_eq!(step.instruction, Some(instruction));
+        assert_eq!(step.raw, RawInstruction::from(instruction));
+        assert_eq!(step.offset, (i * Instruction::SIZE) as Word);
+        assert_eq!(step.depth, 0);
+        assert_eq!(step.contract, None);
+        assert_eq!(step.registers, None);
+        assert!(step.gas_cost > 0);
+    }
+
+    assert_eq!(trace.steps[2].receipts, vec![receipts[0].clone()]);
+    assert!(matches!(
+        trace.steps[3].receipts[..],
+        [Receipt::Return { .. }]
+    ));
+    assert!(trace.steps[..2].iter().all(|step| step.receipts.is_empty()));
+
+    let gas_used = match receipts.last() {
+        Some(Receipt::ScriptResult { gas_used, .. }) => *gas_used,
+        _ => panic!("Expected a script result"),
+    };
+    assert_eq!(trace.gas_used(), gas_used);
+
+    let gas = trace.steps.first().unwrap().gas;
+    trace.steps.iter().fold(gas, |gas, step| {
+        assert_eq!(step.gas, gas);
+        gas - step.gas_cost
+    });
+}
+
+#[test]
+fn trace_recorder_records_registers_and_panics() {
+    let script = vec![
+        op::movi(0x10, 7),
+        op::div(0x11, 0x10, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ];
+
+    let (_, vm) = transact_with_hook(script, TraceRecorder::new().with_registers());
+    let trace = recorded_trace(&vm);
+
+    assert_eq!(trace.steps.len(), 2);
+    assert!(trace.failed());
+
+    let registers = trace.steps[1]
+        .registers
+        .as_ref()
+        .expect("Registers recorded");
+    assert_eq!(registers.len(), VM_REGISTER_COUNT);
+    assert_eq!(registers[0x10], 7);
+    assert_eq!(trace.steps[0].panic_reason, None);
+    assert_eq!(
+        trace.steps[1].panic_reason,
+        Some(PanicReason::ArithmeticError)
+    );
+}
+
+#[test]
+fn trace_recorder_records_invalid_instructions() {
+    const INVALID: RawInstruction = 0xff00_0000;
+
+    let mut script: Vec<u8> = [op::movi(0x10, 7)].into_iter().collect();
+    script.extend(INVALID.to_be_bytes());
+
+    let (_, vm) = transact_bytes_with_hook(script, TraceRecorder::new());
+    let trace = recorded_trace(&vm);
+
+    assert_eq!(trace.steps.len(), 2);
+    assert!(trace.failed());
+    assert_eq!(trace.steps[1].raw, INVALID);
+    assert_eq!(trace.steps[1].instruction, None);
+    assert_eq!(trace.steps[1].offset, Instruction::SIZE as Word);
+    assert!(trace.steps[1].panic_reason.is_some());
+}
+
+#[test]
+fn execution_trace_serde_roundtrip() {
+    let script = vec![
+        op::movi(0x10, 1),
+        op::log(0x10, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ];
+
+    let (_, vm) = transact_with_hook(script, TraceRecorder::new().with_registers());
+    let trace = recorded_trace(&vm);
+
+    let json = serde_json::to_vec(trace).expect("Serialization failed");
+    let decoded: ExecutionTrace =
+        serde_json::from_slice(&json).expect("Deserialization failed");
+
+    assert_eq!(&decoded, trace);
+}
+
+#[derive(Debug, Default, Clone)]
+struct OpcodeCounter {
+    before: Vec<Opcode>,
+    after: Vec<(Opcode, Word)>,
+}
+
+impl ExecutionHook for OpcodeCounter {
+    fn before_instruction(&mut self, step: &InstructionStep<'_>) {
+        assert!(matches!(step.context, Context::Script { .. }));
+        self.before
+            .extend(step.instruction.map(|instruction| instruction.opcode()));
+    }
+
+    fn after_instruction(
+        &mut self,
+        step: &InstructionStep<'_>,
+        outcome: &InstructionOutcome<'_>,
+    ) {
+        if let Some(instruction) = step.instruction {
+            self.after.push((instruction.opcode(), outcome.gas_charged));
+        }
+    }
+}
+
+#[test]
+fn custom_hook_observes_instructions_without_changing_results() {
+    let script = vec![
+        op::movi(0x10, 3),
+        op::subi(0x10, 0x10, 1),
+        op::jnzb(0x10, RegId::ZERO, 0),
+        op::ret(RegId::ONE),
+    ];
+
+    let (hooked_receipts, mut vm) =
+        transact_with_hook(script.clone(), OpcodeCounter::default());
+    let expected_receipts = super::test_helpers::run_script(script);
+
+    // The transactions differ by their random fee input, so only compare the outcome
+    assert_eq!(hooked_receipts.len(), expected_receipts.len());
+    assert_eq!(hooked_receipts.last(), expected_receipts.last());
+
+    let counter = vm
+        .execution_hook::<OpcodeCounter>()
+        .expect("Counter is installed");
+    assert_eq!(
+        counter.before,
+        vec![
+            Opcode::MOVI,
+            Opcode::SUBI,
+            Opcode::JNZB,
+            Opcode::SUBI,
+            Opcode::JNZB,
+            Opcode::SUBI,
+            Opcode::JNZB,
+            Opcode::RET,
+        ]
+    );
+    assert_eq!(counter.before.len(), counter.after.len());
+    assert!(vm.execution_hook::<TraceRecorder>().is_none());
+
+    vm.execution_hook_mut::<OpcodeCounter>()
+        .expect("Counter is installed")
+        .before
+        .clear();
+    let removed = vm.remove_execution_hook().expect("Hook is installed");
+    let counter = removed
+        .into_any()
+        .downcast::<OpcodeCounter>()
+        .expect("Hook is a counter");
+    assert!(counter.before.is_empty());
+    assert!(vm.remove_execution_hook().is_none());
+}
@@ -4,25 +4,38 @@ use tokio_rayon as _;
 
 mod test_helpers;
 
+mod access_list;
 mod alu;
+mod async_storage;
 mod backtrace;
+mod block_executor;
 mod blockchain;
+mod call_tree;
 mod cgas;
 mod code_coverage;
 mod contract;
 mod crypto;
+mod debugger;
 mod encoding;
 mod flow;
+mod fork;
+mod gas_estimation;
 mod gas_factor;
+mod hook;
 mod jump_absolute;
 mod jump_relative;
 mod log;
 mod memory;
 mod metadata;
 mod outputs;
+mod pool;
 mod predicate;
 mod profile_gas;
 mod serde_profile;
+mod simulation;
+mod snapshot;
 mod spec;
+mod state_changes;
+mod time_travel;
 mod validation;
 mod wideint;
@@ -0,0 +1,268 @@
+#![cfg(feature = "std")]
+
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::ConsensusParameters;
+use fuel_vm::{
+    checked_transaction::{
+        CheckPredicateParams,
+        Checked,
+        EstimatePredicates,
+    },
+    hook::ExecutionHook,
+    interpreter::InterpreterParams,
+    prelude::*,
+};
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+fn script_tx(program: Vec<Instruction>) -> Checked<Script> {
+    TransactionBuilder::script(program.into_iter().collect(), vec![])
+        .gas_price(0)
+        .gas_limit(1_000_000)
+        .maturity(Default::default())
+        .add_random_fee_input()
+        .finalize()
+        .into_checked(Default::default(), &ConsensusParameters::standard())
+        .expect("failed to check tx")
+}
+
+fn dirty_heap_script() -> Checked<Script> {
+    script_tx(vec![
+        op::movi(0x10, 64),
+        op::aloc(0x10),
+        op::not(0x11, RegId::ZERO),
+        op::sw(RegId::HP, 0x11, 0),
+        op::sw(RegId::HP, 0x11, 7),
+        op::logd(RegId::ZERO, RegId::ZERO, RegId::HP, 0x10),
+        op::ret(RegId::ONE),
+    ])
+}
+
+fn read_heap_script() -> Checked<Script> {
+    script_tx(vec![
+        op::movi(0x10, 64),
+        op::aloc(0x10),
+        op::lw(0x11, RegId::HP, 0),
+        op::lw(0x12, RegId::HP, 7),
+        op::log(0x11, 0x12, RegId::ZERO, RegId::ZERO),
+        op::logd(RegId::ZERO, RegId::ZERO, RegId::HP, 0x10),
+        op::ret(RegId::ONE),
+    ])
+}
+
+fn fresh_transact(tx: Checked<Script>) -> (Vec<Receipt>, Script) {
+    let params = InterpreterParams::from(&ConsensusParameters::standard());
+    let mut transactor = Transactor::new(MemoryStorage::default(), params);
+    transactor.transact(tx);
+
+    let state = transactor
+        .to_owned_state_transition()
+        .expect("Should execute the transaction");
+
+    (state.receipts().to_vec(), state.tx().clone())
+}
+
+fn pooled_transact(
+    pool: &mut InterpreterPool<Script>,
+    tx: Checked<Script>,
+) -> (Vec<Receipt>, Script) {
+    let params = InterpreterParams::from(&ConsensusParameters::standard());
+    let mut transactor = Transactor::from(pool.acquire(MemoryStorage::default(), params));
+    transactor.transact(tx);
+
+    let state = transactor
+        .to_owned_state_transition()
+        .expect("Should execute the transaction");
+    pool.release(transactor.into());
+
+    (state.receipts().to_vec(), state.tx().clone())
+}
+
+#[test]
+fn pooled_script_matches_fresh_interpreter() {
+    let mut pool = InterpreterPool::with_max_idle(1);
+
+    let expected_dirty = fresh_transact(dirty_heap_script());
+    let expected_read = fresh_transact(read_heap_script());
+
+    assert_eq!(
+        pooled_transact(&mut pool, dirty_heap_script()),
+        expected_dirty
+    );
+    assert_eq!(pool.idle(), 1);
+    assert_eq!(
+        pooled_transact(&mut pool, read_heap_script()),
+        expected_read
+    );
+    assert_eq!(pool.idle(), 1);
+
+    let Receipt::Log { ra, rb, .. } = expected_read.0[0] else {
+        panic!("Expected a log receipt");
+    };
+    assert_eq!((ra, rb), (0, 0));
+}
+
+#[test]
+fn reset_for_tx_discards_previous_execution() {
+    let params = InterpreterParams::from(&ConsensusParameters::standard());
+    let mut transactor =
+        Transactor::<_, Script>::new(MemoryStorage::default(), params.clone());
+    transactor.transact(dirty_heap_script());
+
+    let mut interpreter: Interpreter<_, Script> = transactor.into();
+    assert!(interpreter.memory().allocated_pages() > 0);
+
+    interpreter.reset_for_tx();
+
+    let fresh = Interpreter::<MemoryStorage, Script>::with_storage(
+        Default::default(),
+        params.clone(),
+    );
+    assert_eq!(interpreter.memory(), fresh.memory());
+    assert_eq!(interpreter.memory().allocated_pages(), 0);
+    assert_eq!(interpreter.registers(), fresh.registers());
+    assert_eq!(interpreter.transaction(), fresh.transaction());
+    assert!(interpreter.receipts().is_empty());
+}
+
+#[test]
+fn pooled_create_matches_fresh_interpreter() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let consensus_params = ConsensusParameters::standard();
+    let params = InterpreterParams::from(&consensus_params);
+
+    let create_tx = |salt: Salt| {
+        let program: Witness = [op::ret(RegId::ONE)]
+            .into_iter()
+            .collect::<Vec<u8>>()
+            .into();
+        let contract = Contract::from(program.as_ref());
+        let state_root = Contract::default_state_root();
+        let contract_id = contract.id(&salt, &contract.root(), &state_root);
+
+        TransactionBuilder::create(program, salt, vec![])
+            .gas_price(0)
+            .gas_limit(1_000_000)
+            .maturity(Default::default())
+            .add_random_fee_input()
+            .add_output(Output::contract_created(contract_id, state_root))
+            .finalize()
+            .into_checked(Default::default(), &consensus_params)
+            .expect("failed to check tx")
+    };
+
+    let mut pool = InterpreterPool::<Create>::new();
+    let mut storage = MemoryStorage::default();
+
+    for _ in 0..3 {
+        let salt: Salt = rng.gen();
+
+        let mut fresh = Transactor::new(storage.clone(), params.clone());
+        fresh.transact(create_tx(salt));
+        let expected = fresh.to_owned_state_transition();
+
+        let mut pooled = Transactor::from(pool.acquire(storage, params.clone()));
+        pooled.transact(create_tx(salt));
+        assert_eq!(pooled.to_owned_state_transition(), expected);
+
+        storage = pool.release(pooled.into());
+    }
+}
+
+#[test]
+fn pooled_predicates_match_fresh_interpreter() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let params = CheckPredicateParams::from(&ConsensusParameters::standard());
+    let mut pool = InterpreterPool::<Script>::new();
+
+    // Writes to the heap and succeeds only if the heap was zeroed on entry
+    let predicate: Vec<u8> = [
+        op::movi(0x10, 8),
+        op::aloc(0x10),
+        op::lw(0x11, RegId::HP, 0),
+        op::not(0x12, RegId::ZERO),
+        op::sw(RegId::HP, 0x12, 0),
+        op::eq(0x10, 0x11, RegId::ZERO),
+        op::ret(0x10),
+    ]
+    .into_iter()
+    .collect();
+    let owner = Input::predicate_owner(&predicate);
+
+    let mut builder = TransactionBuilder::script(vec![], vec![]);
+    builder.gas_price(0).gas_limit(1_000_000);
+    for _ in 0..3 {
+        builder.add_input(Input::coin_predicate(
+            rng.gen(),
+            owner,
+            1_000,
+            AssetId::BASE,
+            rng.gen(),
+            Default::default(),
+            0,
+            predicate.clone(),
+            vec![],
+        ));
+    }
+    let mut tx = builder.finalize();
+    tx.estimate_predicates(&params)
+        .expect("Should estimate predicates");
+
+    let checked = tx
+        .into_checked_basic(Default::default(), &ConsensusParameters::standard())
+        .expect("Should successfully convert into Checked");
+
+    let expected = Interpreter::<PredicateStorage>::check_predicates(&checked, &params)
+        .expect("Predicates should be valid")
+        .gas_used();
+
+    for _ in 0..2 {
+        let gas_used = Interpreter::<PredicateStorage>::check_predicates_with_pool(
+            &checked, &params, &mut pool,
+        )
+        .expect("Predicates should be valid")
+        .gas_used();
+
+        assert_eq!(gas_used, expected);
+    }
+    assert_eq!(pool.idle(), 1);
+}
+
+#[test]
+fn release_drops_interpreters_beyond_max_idle() {
+    let params = InterpreterParams::default();
+    let mut pool = InterpreterPool::<Script>::with_max_idle(2);
+
+    let vms: Vec<_> = (0..3).map(|_| pool.acquire((), params.clone())).collect();
+    assert_eq!(pool.idle(), 0);
+
+    vms.into_iter().for_each(|vm| pool.release(vm));
+    assert_eq!(pool.idle(), 2);
+
+    let _vm = pool.acquire(MemoryStorage::default(), params);
+    assert_eq!(pool.idle(), 1);
+}
+
+#[derive(Debug, Clone, Default)]
+struct NoopHook;
+
+impl ExecutionHook for NoopHook {}
+
+#[test]
+fn release_detaches_execution_hook() {
+    let params = InterpreterParams::default();
+    let mut pool = InterpreterPool::<Script>::with_max_idle(1);
+
+    let mut vm = pool.acquire((), params.clone());
+    vm.with_execution_hook(NoopHook);
+    pool.release(vm);
+
+    let mut vm = pool.acquire((), params);
+    assert!(vm.remove_execution_hook().is_none());
+}
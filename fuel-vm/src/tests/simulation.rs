@@ -0,0 +1,251 @@
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::{
+    field::Outputs,
+    Chargeable,
+    ConsensusParameters,
+    Finalizable,
+};
+use fuel_vm::{
+    checked_transaction::{
+        CheckPredicateParams,
+        Checks,
+        EstimatePredicates,
+    },
+    prelude::*,
+    transactor::SimulationOptions,
+};
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+const AMOUNT: Word = 1_000;
+const SPENT: Word = 300;
+
+/// Spends the base asset of an unsigned coin input to a coin output, keeping the
+/// rest as change.
+fn transfer(rng: &mut StdRng, amount: Word) -> TransactionBuilder<Script> {
+    let mut builder = TransactionBuilder::script(
+        vec![
+            op::log(RegId::ONE, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+            op::ret(RegId::ONE),
+        ]
+        .into_iter()
+        .collect(),
+        vec![],
+    );
+    builder
+        .gas_price(1)
+        .gas_limit(10_000)
+        .add_unsigned_coin_input(
+            SecretKey::random(rng),
+            rng.gen(),
+            amount,
+            AssetId::BASE,
+            rng.gen(),
+            Default::default(),
+        )
+        .add_output(Output::coin(rng.gen(), SPENT, AssetId::BASE))
+        .add_output(Output::change(rng.gen(), 0, AssetId::BASE));
+    builder
+}
+
+fn change(outputs: &[Output]) -> Word {
+    outputs
+        .iter()
+        .find_map(|output| match output {
+            Output::Change { amount, .. } => Some(*amount),
+            _ => None,
+        })
+        .expect("Expected a change output")
+}
+
+#[test]
+fn simulation_matches_the_execution_of_the_signed_transaction() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let params = ConsensusParameters::standard();
+    let mut builder = transfer(rng, AMOUNT);
+
+    let unsigned = builder.clone().finalize_without_signature();
+    let simulation = MemoryClient::default()
+        .simulate(
+            unsigned,
+            Default::default(),
+            &params,
+            SimulationOptions::default(),
+        )
+        .expect("Failed to simulate");
+
+    assert!(!simulation.passes_checks());
+    assert_eq!(simulation.failed_checks.len(), 1);
+    assert_eq!(simulation.failed_checks[0].check, Checks::Signatures);
+    assert_eq!(simulation.state, ProgramState::Return(1));
+
+    let signed = builder.finalize_checked(Default::default());
+    let mut client = MemoryClient::default();
+    let receipts = client.transact(signed).to_vec();
+    let outputs = client
+        .state_transition()
+        .expect("Transaction is executed")
+        .tx()
+        .outputs()
+        .to_vec();
+
+    assert_eq!(simulation.receipts, receipts);
+    assert_eq!(simulation.outputs, outputs);
+    assert_eq!(
+        simulation.gas_used,
+        receipts.iter().find_map(Receipt::gas_used).unwrap()
+    );
+    assert!(change(&simulation.outputs) > 0);
+    assert!(change(&simulation.outputs) < AMOUNT - SPENT);
+}
+
+#[test]
+fn underfunded_transactions_are_simulated_with_topped_up_balances() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let params = ConsensusParameters::standard();
+    let tx = transfer(rng, 0).finalize_without_signature();
+
+    let client = MemoryClient::default();
+    let error = client
+        .simulate(
+            tx.clone(),
+            Default::default(),
+            &params,
+            SimulationOptions::default(),
+        )
+        .expect_err("Underfunded transaction is rejected");
+    assert!(matches!(
+        error,
+        InterpreterError::CheckError(CheckError::InsufficientFeeAmount { .. })
+    ));
+
+    let options = SimulationOptions {
+        top_up_balances: true,
+        ..Default::default()
+    };
+    let simulation = client
+        .simulate(tx, Default::default(), &params, options)
+        .expect("Failed to simulate");
+
+    let failed: Vec<_> = simulation.failed_checks.iter().map(|f| f.check).collect();
+    assert_eq!(
+        failed,
+        vec![Checks::Basic, Checks::Basic, Checks::Signatures]
+    );
+    assert!(matches!(
+        simulation.failed_checks[0].error,
+        CheckError::InsufficientFeeAmount { .. }
+    ));
+    assert!(matches!(
+        simulation.failed_checks[1].error,
+        CheckError::InsufficientInputAmount {
+            expected: SPENT,
+            ..
+        }
+    ));
+    assert_eq!(simulation.state, ProgramState::Return(1));
+}
+
+#[test]
+fn failing_predicates_are_reported_unless_skipped() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let params = ConsensusParameters::standard();
+
+    let predicate: Vec<u8> = vec![op::ret(RegId::ZERO)].into_iter().collect();
+    let owner = Input::predicate_owner(&predicate);
+    let tx = transfer(rng, AMOUNT)
+        .add_input(Input::coin_predicate(
+            rng.gen(),
+            owner,
+            0,
+            rng.gen(),
+            rng.gen(),
+            Default::default(),
+            0,
+            predicate,
+            vec![],
+        ))
+        .finalize();
+
+    let client = MemoryClient::default();
+    let simulation = client
+        .simulate(
+            tx.clone(),
+            Default::default(),
+            &params,
+            SimulationOptions::default(),
+        )
+        .expect("Failed to simulate");
+    let failed: Vec<_> = simulation.failed_checks.iter().map(|f| f.check).collect();
+    assert_eq!(failed, vec![Checks::Predicates]);
+
+    let options = SimulationOptions {
+        skip_predicates: true,
+        ..Default::default()
+    };
+    let simulation = client
+        .simulate(tx, Default::default(), &params, options)
+        .expect("Failed to simulate");
+    assert!(simulation.passes_checks());
+    assert_eq!(simulation.state, ProgramState::Return(1));
+}
+
+#[test]
+fn gas_used_includes_the_evaluated_predicates() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let params = ConsensusParameters::standard();
+
+    let predicate: Vec<u8> = vec![op::ret(RegId::ONE)].into_iter().collect();
+    let owner = Input::predicate_owner(&predicate);
+    let mut tx = transfer(rng, AMOUNT)
+        .add_input(Input::coin_predicate(
+            rng.gen(),
+            owner,
+            0,
+            rng.gen(),
+            rng.gen(),
+            Default::default(),
+            0,
+            predicate,
+            vec![],
+        ))
+        .finalize();
+    tx.estimate_predicates(&CheckPredicateParams::from(&params))
+        .expect("Should estimate predicates");
+    let gas_used_by_predicates = tx.gas_used_by_predicates();
+    assert!(gas_used_by_predicates > 0);
+
+    let client = MemoryClient::default();
+    let with_predicates = client
+        .simulate(
+            tx.clone(),
+            Default::default(),
+            &params,
+            SimulationOptions::default(),
+        )
+        .expect("Failed to simulate");
+
+    let options = SimulationOptions {
+        skip_predicates: true,
+        ..Default::default()
+    };
+    let without_predicates = client
+        .simulate(tx, Default::default(), &params, options)
+        .expect("Failed to simulate");
+
+    assert_eq!(
+        with_predicates.gas_used,
+        without_predicates.gas_used + gas_used_by_predicates
+    );
+}
@@ -0,0 +1,246 @@
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_storage::MerkleRootStorage;
+use fuel_tx::ConsensusParameters;
+use fuel_types::canonical::{
+    Deserialize,
+    Serialize,
+};
+use fuel_vm::{
+    checked_transaction::Checked,
+    interpreter::InterpreterParams,
+    prelude::*,
+    storage::{
+        import_state,
+        ContractBalance,
+        ContractsAssets,
+        ContractsAssetsStorage,
+        ContractsState,
+        SnapshotError,
+        StorageSnapshot,
+    },
+};
+
+fn key(index: u8) -> Bytes32 {
+    let mut key = Bytes32::zeroed();
+    key[31] = index;
+    key
+}
+
+/// Deploys a contract with two storage slots and a balance, which adds slot 0 to
+/// slot 1 when called, and builds a script calling it.
+fn setup(test_context: &mut TestBuilder) -> (ContractId, Checked<Script>) {
+    let contract = vec![
+        op::movi(0x10, 32),
+        op::aloc(0x10),
+        op::srw(0x11, 0x12, RegId::HP),
+        op::sw(RegId::HP, RegId::ONE, 3),
+        op::srw(0x13, 0x12, RegId::HP),
+        op::add(0x13, 0x13, 0x11),
+        op::sww(RegId::HP, 0x12, 0x13),
+        op::ret(RegId::ONE),
+    ];
+    let slots = vec![
+        StorageSlot::new(key(0), key(5)),
+        StorageSlot::new(key(1), key(7)),
+    ];
+    let contract_id = test_context
+        .setup_contract(contract, Some((AssetId::new([3; 32]), 100)), Some(slots))
+        .contract_id;
+
+    let script = vec![
+        op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+        op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+        op::ret(RegId::ONE),
+    ];
+    let tx = test_context
+        .start_script(script, Call::new(contract_id, 0, 0).to_bytes())
+        .gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+
+    (contract_id, tx)
+}
+
+fn execute(storage: MemoryStorage, tx: Checked<Script>) -> (MemoryStorage, Vec<Receipt>) {
+    let mut vm = Interpreter::with_storage(
+        storage,
+        InterpreterParams::from(&ConsensusParameters::standard()),
+    );
+    let receipts = vm
+        .transact(tx)
+        .expect("Failed to execute")
+        .receipts()
+        .to_vec();
+    (vm.as_ref().clone(), receipts)
+}
+
+fn roots(storage: &MemoryStorage, contract_id: &ContractId) -> (MerkleRoot, MerkleRoot) {
+    (
+        MerkleRootStorage::<ContractId, ContractsState>::root(storage, contract_id)
+            .expect("Infallible"),
+        MerkleRootStorage::<ContractId, ContractsAssets>::root(storage, contract_id)
+            .expect("Infallible"),
+    )
+}
+
+#[test]
+fn snapshot_contains_the_contracts_of_the_storage() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let (contract_id, _) = setup(&mut test_context);
+    let storage = test_context.get_storage();
+
+    let snapshot = storage.export_snapshot();
+
+    assert_eq!(snapshot.version(), StorageSnapshot::VERSION);
+    assert_eq!(snapshot.block_height, storage.block_height().unwrap());
+    assert_eq!(snapshot.coinbase, storage.coinbase().unwrap());
+    let contract = snapshot
+        .contracts
+        .iter()
+        .find(|contract| contract.contract_id == contract_id)
+        .expect("Missing contract");
+    assert_eq!(
+        &contract.bytecode,
+        storage
+            .storage_contract(&contract_id)
+            .unwrap()
+            .unwrap()
+            .as_ref()
+    );
+    assert_eq!(
+        contract.state,
+        vec![
+            StorageSlot::new(key(0), key(5)),
+            StorageSlot::new(key(1), key(7)),
+        ]
+    );
+    assert_eq!(
+        contract.balances,
+        vec![ContractBalance {
+            asset_id: AssetId::new([3; 32]),
+            amount: 100,
+        }]
+    );
+}
+
+#[test]
+fn snapshot_round_trips_through_the_encodings() {
+    let mut test_context = TestBuilder::new(2322u64);
+    setup(&mut test_context);
+    let snapshot = test_context.get_storage().export_snapshot();
+
+    let bytes = snapshot.to_bytes();
+    let decoded = StorageSnapshot::from_bytes(&bytes).expect("Deserialization failed");
+    assert_eq!(decoded, snapshot);
+
+    let json = serde_json::to_vec(&snapshot).expect("Serialization failed");
+    let decoded: StorageSnapshot =
+        serde_json::from_slice(&json).expect("Deserialization failed");
+    assert_eq!(decoded, snapshot);
+}
+
+#[test]
+fn imported_snapshot_executes_like_the_original_storage() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let (contract_id, tx) = setup(&mut test_context);
+    let storage = test_context.get_storage().clone();
+    let snapshot = storage.export_snapshot();
+
+    let imported = MemoryStorage::from_snapshot(&snapshot).expect("Failed to import");
+    assert_eq!(imported.export_snapshot(), snapshot);
+    assert_eq!(
+        roots(&imported, &contract_id),
+        roots(&storage, &contract_id)
+    );
+
+    let (storage, receipts) = execute(storage, tx.clone());
+    let (imported, imported_receipts) = execute(imported, tx);
+    assert_eq!(imported_receipts, receipts);
+    assert_eq!(imported.export_snapshot(), storage.export_snapshot());
+    assert_eq!(
+        roots(&imported, &contract_id),
+        roots(&storage, &contract_id)
+    );
+}
+
+#[test]
+fn snapshot_is_imported_into_any_interpreter_storage() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let (contract_id, _) = setup(&mut test_context);
+    let storage = test_context.get_storage();
+    let snapshot = storage.export_snapshot();
+
+    let mut imported = MemoryStorage::new(snapshot.block_height, snapshot.coinbase);
+    import_state(&mut &mut imported, &snapshot).expect("Failed to import");
+
+    assert_eq!(imported.export_snapshot(), snapshot);
+    assert_eq!(roots(&imported, &contract_id), roots(storage, &contract_id));
+}
+
+#[test]
+fn contracts_without_code_are_not_deployed_on_import() {
+    let contract_id = ContractId::new([1; 32]);
+    let mut storage = MemoryStorage::default();
+    storage
+        .merkle_contract_asset_id_balance_insert(
+            &contract_id,
+            &AssetId::new([3; 32]),
+            100,
+        )
+        .unwrap();
+    storage
+        .merkle_contract_state_insert(&contract_id, &key(0), &key(5))
+        .unwrap();
+
+    let snapshot = storage.export_snapshot();
+    assert!(!snapshot.contracts[0].is_deployed());
+
+    let imported = MemoryStorage::from_snapshot(&snapshot).expect("Failed to import");
+    assert!(!imported.storage_contract_exists(&contract_id).unwrap());
+    assert_eq!(imported.storage_contract_root(&contract_id).unwrap(), None);
+    assert_eq!(imported.export_snapshot(), snapshot);
+    assert_eq!(
+        roots(&imported, &contract_id),
+        roots(&storage, &contract_id)
+    );
+}
+
+#[test]
+fn snapshot_has_the_coinbase_of_the_current_block() {
+    let mut storage = MemoryStorage::default();
+    let height = storage.block_height().unwrap();
+    let mut header = storage.block_headers_mut().get(height);
+    header.coinbase = ContractId::new([4; 32]);
+    storage.block_headers_mut().insert(header);
+
+    let snapshot = storage.export_snapshot();
+    assert_eq!(snapshot.coinbase, header.coinbase);
+    assert_eq!(snapshot.coinbase, storage.coinbase().unwrap());
+}
+
+#[test]
+fn snapshot_of_another_version_is_rejected() {
+    let mut test_context = TestBuilder::new(2322u64);
+    setup(&mut test_context);
+    let mut bytes = test_context.get_storage().export_snapshot().to_bytes();
+
+    // The version is the first word of the encoding.
+    bytes[7] = 2;
+    let snapshot = StorageSnapshot::from_bytes(&bytes).expect("Deserialization failed");
+
+    assert_eq!(snapshot.version(), 2);
+    assert_eq!(
+        MemoryStorage::from_snapshot(&snapshot).unwrap_err(),
+        SnapshotError::UnsupportedVersion(2)
+    );
+}
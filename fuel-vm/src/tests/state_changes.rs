@@ -0,0 +1,324 @@
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use fuel_asm::{
+    op,
+    Instruction,
+    RegId,
+};
+use fuel_tx::{
+    ConsensusParameters,
+    Create,
+    StorageSlot,
+    TransactionBuilder,
+};
+use fuel_types::canonical::Serialize;
+use fuel_vm::{
+    interpreter::InterpreterParams,
+    prelude::*,
+    state::{
+        StateChanges,
+        ValueChange,
+    },
+    storage::{
+        ContractsAssets,
+        ContractsState,
+    },
+};
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+use test_case::test_case;
+
+/// Calls `contract_id`, forwarding all the gas.
+fn call(
+    test_context: &mut TestBuilder,
+    contract_id: ContractId,
+) -> StateTransition<Script> {
+    let script = vec![
+        op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+        op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+        op::ret(RegId::ONE),
+    ];
+    let tx = test_context
+        .start_script(script, Call::new(contract_id, 0, 0).to_bytes())
+        .gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+    test_context.execute_tx(tx).expect("Failed to execute")
+}
+
+fn word_value(word: Word) -> Bytes32 {
+    let mut value = Bytes32::zeroed();
+    value[..8].copy_from_slice(&word.to_be_bytes());
+    value
+}
+
+fn key(index: u8) -> Bytes32 {
+    let mut key = Bytes32::zeroed();
+    key[31] = index;
+    key
+}
+
+/// Writes `5` to slot 0, `7` and `0` to the slots 1 and 2, then clears slot 1.
+fn storage_writes() -> Vec<Instruction> {
+    vec![
+        op::movi(0x10, 96),
+        op::aloc(0x10),
+        op::movi(0x11, 5),
+        op::sww(RegId::HP, 0x12, 0x11),
+        op::sw(RegId::HP, RegId::ONE, 3),
+        op::addi(0x13, RegId::HP, 32),
+        op::movi(0x14, 7),
+        op::sw(0x13, 0x14, 0),
+        op::movi(0x15, 2),
+        op::swwq(RegId::HP, 0x12, 0x13, 0x15),
+        op::scwq(RegId::HP, 0x12, RegId::ONE),
+        op::ret(RegId::ONE),
+    ]
+}
+
+/// Mints 100 and burns 30 of the asset with a zero sub id.
+fn mint_and_burn() -> Vec<Instruction> {
+    vec![
+        op::movi(0x10, 32),
+        op::aloc(0x10),
+        op::movi(0x11, 100),
+        op::mint(0x11, RegId::HP),
+        op::movi(0x11, 30),
+        op::burn(0x11, RegId::HP),
+        op::ret(RegId::ONE),
+    ]
+}
+
+#[test]
+fn storage_writes_are_recorded_with_their_old_values() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let initial = StorageSlot::new(key(0), word_value(1));
+    let contract_id = test_context
+        .setup_contract(storage_writes(), None, Some(vec![initial]))
+        .contract_id;
+
+    let state = call(&mut test_context, contract_id);
+    let changes = state.state_changes();
+
+    let expected = [
+        (
+            key(0),
+            ValueChange {
+                old: Some(word_value(1)),
+                new: Some(word_value(5)),
+            },
+        ),
+        (
+            key(1),
+            ValueChange {
+                old: None,
+                new: None,
+            },
+        ),
+        (
+            key(2),
+            ValueChange {
+                old: None,
+                new: Some(Bytes32::zeroed()),
+            },
+        ),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(changes.contract_state[&contract_id], expected);
+    assert_eq!(changes.contract_state.len(), 1);
+    // The call forwards no coins, which still writes the balance of the contract
+    assert!(changes
+        .balances
+        .values()
+        .flat_map(|balances| balances.values())
+        .all(|change| change.old.unwrap_or_default() == change.new.unwrap_or_default()));
+    assert!(changes.deployed_contracts.is_empty());
+}
+
+#[test]
+fn minted_and_burned_amounts_are_recorded_with_the_balances() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract_id = test_context
+        .setup_contract(mint_and_burn(), None, None)
+        .contract_id;
+    let asset_id = contract_id.asset_id(&Bytes32::zeroed());
+
+    let state = call(&mut test_context, contract_id);
+    let changes = state.state_changes();
+
+    assert_eq!(changes.minted, [(asset_id, 100)].into_iter().collect());
+    assert_eq!(changes.burned, [(asset_id, 30)].into_iter().collect());
+    assert_eq!(
+        changes.balances[&contract_id][&asset_id],
+        ValueChange {
+            old: None,
+            new: Some(70)
+        }
+    );
+    assert!(changes.contract_state.is_empty());
+}
+
+#[test]
+fn deployed_contracts_are_recorded_with_their_initial_state() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let params = ConsensusParameters::standard();
+
+    let bytecode: Vec<u8> = vec![op::ret(RegId::ONE)].into_iter().collect();
+    let salt: Salt = rng.gen();
+    let slot = StorageSlot::new(key(0), word_value(1));
+    let contract = Contract::from(bytecode.clone());
+    let root = contract.root();
+    let state_root = Contract::initial_state_root([&slot].into_iter());
+    let contract_id = contract.id(&salt, &root, &state_root);
+
+    let tx = TransactionBuilder::create(bytecode.into(), salt, vec![slot])
+        .add_random_fee_input()
+        .add_output(Output::contract_created(contract_id, state_root))
+        .finalize_checked(Default::default());
+
+    let state = Transactor::<_, Create>::new(MemoryStorage::default(), (&params).into())
+        .transact(tx)
+        .to_owned_state_transition()
+        .expect("Failed to deploy");
+    let changes = state.state_changes();
+
+    let deployed = &changes.deployed_contracts[&contract_id];
+    assert_eq!(deployed.bytecode, contract);
+    assert_eq!(deployed.salt, salt);
+    assert_eq!(deployed.root, root);
+    assert_eq!(
+        changes.contract_state[&contract_id][&key(0)],
+        ValueChange {
+            old: None,
+            new: Some(word_value(1)),
+        }
+    );
+}
+
+#[test]
+fn applying_the_changes_reproduces_the_state() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract_id = test_context
+        .setup_contract(storage_writes(), None, None)
+        .contract_id;
+    let mut replica = test_context.get_storage().clone();
+
+    let state = call(&mut test_context, contract_id);
+    state
+        .state_changes()
+        .apply(&mut replica)
+        .expect("Failed to apply the changes");
+
+    let storage = test_context.get_storage();
+    for index in 0..3 {
+        assert_eq!(
+            replica.merkle_contract_state(&contract_id, &key(index)),
+            storage.merkle_contract_state(&contract_id, &key(index)),
+        );
+    }
+    assert_eq!(
+        MerkleRootStorage::<ContractId, ContractsState>::root(&replica, &contract_id),
+        MerkleRootStorage::<ContractId, ContractsState>::root(storage, &contract_id),
+    );
+}
+
+#[test]
+fn applying_and_reverting_the_changes_restores_the_balance_root() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract_id = test_context
+        .setup_contract(mint_and_burn(), None, None)
+        .contract_id;
+    let mut replica = test_context.get_storage().clone();
+    let balance_root = |storage: &MemoryStorage| {
+        MerkleRootStorage::<ContractId, ContractsAssets>::root(storage, &contract_id)
+            .expect("Infallible")
+    };
+    let initial_root = balance_root(&replica);
+
+    let state = call(&mut test_context, contract_id);
+    let changes = state.state_changes();
+
+    changes
+        .apply(&mut replica)
+        .expect("Failed to apply the changes");
+    assert_eq!(
+        balance_root(&replica),
+        balance_root(test_context.get_storage())
+    );
+    assert_ne!(balance_root(&replica), initial_root);
+
+    changes
+        .revert(&mut replica)
+        .expect("Failed to revert the changes");
+    assert_eq!(balance_root(&replica), initial_root);
+}
+
+#[test]
+fn state_changes_serde_round_trip() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract_id = test_context
+        .setup_contract(mint_and_burn(), None, None)
+        .contract_id;
+
+    let state = call(&mut test_context, contract_id);
+    let changes = state.state_changes();
+    assert!(!changes.is_empty());
+
+    let json = serde_json::to_vec(changes).expect("Serialization failed");
+    let decoded: StateChanges =
+        serde_json::from_slice(&json).expect("Deserialization failed");
+    assert_eq!(&decoded, changes);
+}
+
+#[test_case(op::rvrt(RegId::ONE) ; "revert")]
+#[test_case(op::div(0x10, 0x10, RegId::ZERO) ; "panic")]
+fn storage_changes_of_failed_scripts_are_rolled_back(failure: Instruction) {
+    let mut test_context = TestBuilder::new(2322u64);
+    let initial = StorageSlot::new(key(0), word_value(1));
+    let mut program = storage_writes();
+    *program.last_mut().unwrap() = failure;
+    let contract_id = test_context
+        .setup_contract(program, None, Some(vec![initial]))
+        .contract_id;
+    let storage = test_context.get_storage().clone();
+
+    let script = vec![
+        op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+        op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+        op::ret(RegId::ONE),
+    ];
+    let tx = test_context
+        .start_script(script, Call::new(contract_id, 0, 0).to_bytes())
+        .gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+    let params = InterpreterParams::from(&ConsensusParameters::standard());
+    let mut transactor = Transactor::<_, Script>::new(storage.clone(), params);
+    transactor.transact(tx);
+
+    assert!(transactor.is_reverted());
+    let state = transactor.state_transition().expect("Failed to execute");
+    assert!(state.state_changes().is_empty());
+    let after: &MemoryStorage = transactor.as_ref();
+    for index in 0..3 {
+        assert_eq!(
+            after.merkle_contract_state(&contract_id, &key(index)),
+            storage.merkle_contract_state(&contract_id, &key(index)),
+        );
+    }
+    assert_eq!(
+        after.contract_state(&contract_id, &key(0)).into_owned(),
+        word_value(1)
+    );
+}
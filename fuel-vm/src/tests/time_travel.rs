@@ -0,0 +1,257 @@
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use fuel_asm::{
+    op,
+    GTFArgs,
+    Instruction,
+    RegId,
+};
+use fuel_tx::ConsensusParameters;
+use fuel_types::canonical::Serialize;
+use fuel_vm::{
+    checked_transaction::Checked,
+    consts::VM_MAX_RAM,
+    interpreter::{
+        diff::Record,
+        InterpreterParams,
+    },
+    prelude::*,
+};
+
+type RecordingInterpreter = Interpreter<Record<MemoryStorage>, Script>;
+
+fn recording_vm(storage: MemoryStorage) -> RecordingInterpreter {
+    Interpreter::with_storage(
+        storage,
+        InterpreterParams::from(&ConsensusParameters::standard()),
+    )
+    .add_recording()
+}
+
+fn script(program: Vec<Instruction>) -> Checked<Script> {
+    TransactionBuilder::script(program.into_iter().collect(), vec![])
+        .gas_limit(1_000_000)
+        .add_random_fee_input()
+        .finalize()
+        .into_checked(Default::default(), &ConsensusParameters::standard())
+        .expect("failed to generate checked tx")
+}
+
+fn stopped_at(state: &ProgramState) -> Breakpoint {
+    *state
+        .debug_ref()
+        .and_then(DebugEval::breakpoint)
+        .expect("Expected a debug event")
+}
+
+fn heap_word(vm: &RecordingInterpreter) -> Vec<u8> {
+    let start = (VM_MAX_RAM - 8) as usize;
+    vm.memory().read(start..start + 8).into_owned()
+}
+
+fn store_and_log() -> Vec<Instruction> {
+    vec![
+        op::movi(0x10, 8),
+        op::aloc(0x10),
+        op::movi(0x11, 0x2a),
+        op::sw(RegId::HP, 0x11, 0),
+        op::log(0x11, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ]
+}
+
+#[test]
+fn step_back_and_goto_rewind_and_replay_the_vm() {
+    let mut vm = recording_vm(MemoryStorage::default());
+    vm.record_history(16);
+    vm.set_breakpoint(Breakpoint::script(5));
+
+    let state = vm
+        .transact(script(store_and_log()))
+        .map(ProgramState::from)
+        .unwrap();
+    assert_eq!(stopped_at(&state), Breakpoint::script(5));
+    assert_eq!(vm.history_position(), Some(5));
+    assert_eq!(vm.history_range(), Some(0..=5));
+
+    let registers = vm.registers().to_vec();
+    let memory = heap_word(&vm);
+    // The result receipt appended by `transact` isn't produced by an instruction
+    let receipts = vm.receipts()[..1].to_vec();
+    assert_eq!(memory, 0x2au64.to_be_bytes());
+    assert!(matches!(receipts[..], [Receipt::Log { ra: 0x2a, .. }]));
+
+    // Undo the log
+    let state = vm.step_back().expect("History is recorded");
+    assert_eq!(stopped_at(&state), Breakpoint::script(4));
+    assert_eq!(vm.history_position(), Some(4));
+    assert!(vm.receipts().is_empty());
+    assert_eq!(heap_word(&vm), memory);
+
+    // Undo the store
+    let state = vm.goto(3).expect("Position is recorded");
+    assert_eq!(stopped_at(&state), Breakpoint::script(3));
+    assert_eq!(heap_word(&vm), [0; 8]);
+    assert_eq!(vm.registers()[0x11], 0x2a);
+    assert_eq!(vm.registers()[RegId::HP], VM_MAX_RAM - 8);
+
+    let state = vm.goto(0).expect("Position is recorded");
+    assert_eq!(stopped_at(&state), Breakpoint::script(0));
+    assert_eq!(vm.registers()[RegId::HP], VM_MAX_RAM);
+    assert_eq!(vm.registers()[0x10], 0);
+
+    // Replay everything
+    let state = vm.goto(5).expect("Position is recorded");
+    assert_eq!(stopped_at(&state), Breakpoint::script(5));
+    assert_eq!(vm.registers(), &registers[..]);
+    assert_eq!(heap_word(&vm), memory);
+    assert_eq!(vm.receipts(), &receipts[..]);
+
+    let state = vm.resume().expect("Failed to resume");
+    assert_eq!(state, ProgramState::Return(1));
+}
+
+#[test]
+fn resuming_after_step_back_reexecutes_and_discards_undone_steps() {
+    let mut vm = recording_vm(MemoryStorage::default());
+    vm.record_history(16);
+    vm.set_breakpoint(Breakpoint::script(5));
+
+    vm.transact(script(store_and_log())).unwrap();
+    vm.goto(2).expect("Position is recorded");
+
+    let state = vm.step_into().expect("Failed to step");
+    assert_eq!(stopped_at(&state), Breakpoint::script(3));
+    assert_eq!(vm.history_range(), Some(0..=3));
+
+    let state = vm.resume().expect("Failed to resume");
+    assert_eq!(stopped_at(&state), Breakpoint::script(5));
+    assert_eq!(heap_word(&vm), 0x2au64.to_be_bytes());
+    assert_eq!(vm.receipts().len(), 1);
+
+    let state = vm.resume().expect("Failed to resume");
+    assert_eq!(state, ProgramState::Return(1));
+}
+
+#[test]
+fn history_is_bounded() {
+    let mut vm = recording_vm(MemoryStorage::default());
+    vm.record_history(2);
+    vm.set_breakpoint(Breakpoint::script(5));
+
+    vm.transact(script(store_and_log())).unwrap();
+    assert_eq!(vm.history_position(), Some(5));
+    assert_eq!(vm.history_range(), Some(3..=5));
+
+    assert_eq!(vm.goto(2), Err(InterpreterError::HistoryUnavailable));
+    assert_eq!(vm.goto(6), Err(InterpreterError::HistoryUnavailable));
+    vm.step_back().expect("Position is recorded");
+    vm.step_back().expect("Position is recorded");
+    assert_eq!(vm.step_back(), Err(InterpreterError::HistoryUnavailable));
+    assert_eq!(vm.history_position(), Some(3));
+}
+
+#[test]
+fn time_travel_requires_a_suspended_vm_with_history() {
+    let mut vm = recording_vm(MemoryStorage::default());
+    vm.set_breakpoint(Breakpoint::script(5));
+    vm.transact(script(store_and_log())).unwrap();
+    assert_eq!(vm.history_position(), None);
+    assert_eq!(vm.step_back(), Err(InterpreterError::HistoryUnavailable));
+
+    let mut vm = recording_vm(MemoryStorage::default());
+    vm.record_history(16);
+    let state = vm.transact(script(store_and_log())).map(ProgramState::from);
+    assert_eq!(state, Ok(ProgramState::Return(1)));
+    assert_eq!(
+        vm.step_back(),
+        Err(InterpreterError::DebugStateNotInitialized)
+    );
+
+    vm.remove_history();
+    assert_eq!(vm.history_range(), None);
+}
+
+#[test]
+fn step_back_rewinds_contract_storage_and_state_changes() {
+    let mut test_context = TestBuilder::new(2322u64);
+
+    // Stores to the zeroed key of a freshly allocated heap slot
+    let contract = vec![
+        op::movi(0x10, 32),
+        op::aloc(0x10),
+        op::movi(0x10, 0x2a),
+        op::sww(RegId::HP, 0x11, 0x10),
+        op::ret(RegId::ONE),
+    ];
+    let contract_id = test_context
+        .setup_contract(contract, None, None)
+        .contract_id;
+
+    let script = vec![
+        op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+        op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+        op::ret(RegId::ONE),
+    ];
+    let tx = test_context
+        .start_script(script, Call::new(contract_id, 0, 0).to_bytes())
+        .gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+
+    let mut vm = recording_vm(test_context.get_storage().clone());
+    vm.record_history(16);
+    vm.set_breakpoint(Breakpoint::script(2));
+
+    let stored = |vm: &RecordingInterpreter| {
+        vm.as_ref()
+            .merkle_contract_state(&contract_id, &Bytes32::zeroed())
+            .expect("Infallible")
+            .map(|value| value.into_owned())
+    };
+
+    let recorded = |vm: &RecordingInterpreter| {
+        vm.state_changes()
+            .contract_state
+            .get(&contract_id)
+            .and_then(|slots| slots.get(&Bytes32::zeroed()))
+            .map(|change| change.new)
+    };
+
+    let state = vm.transact(tx).map(ProgramState::from).unwrap();
+    assert_eq!(stopped_at(&state), Breakpoint::script(2));
+    let value = stored(&vm).expect("Value is stored");
+    let changes = vm.state_changes().clone();
+    assert_eq!(recorded(&vm), Some(Some(value)));
+
+    // Two instructions of the script, then four of the contract
+    let state = vm.goto(5).expect("Position is recorded");
+    assert_eq!(stopped_at(&state), Breakpoint::new(contract_id, 3));
+    assert_eq!(stored(&vm), None);
+    assert_eq!(recorded(&vm), None);
+
+    let state = vm.goto(6).expect("Position is recorded");
+    assert_eq!(stopped_at(&state), Breakpoint::new(contract_id, 4));
+    assert_eq!(stored(&vm), Some(value));
+    assert_eq!(recorded(&vm), Some(Some(value)));
+
+    // Stepping back over the `SWW`
+    vm.step_back().expect("Position is recorded");
+    assert_eq!(stored(&vm), None);
+    assert_eq!(recorded(&vm), None);
+    vm.goto(7).expect("Position is recorded");
+    assert_eq!(vm.state_changes(), &changes);
+
+    vm.step_back().expect("Position is recorded");
+    let state = vm.resume().expect("Failed to resume");
+    assert_eq!(stopped_at(&state), Breakpoint::script(2));
+    assert_eq!(stored(&vm), Some(value));
+
+    let state = vm.resume().expect("Failed to resume");
+    assert_eq!(state, ProgramState::Return(1));
+}
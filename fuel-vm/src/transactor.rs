@@ -7,6 +7,10 @@ use crate::{
         IntoChecked,
     },
     error::InterpreterError,
+    hook::{
+        CallTracer,
+        CallTree,
+    },
     interpreter::{
         CheckedMetadata,
         ExecutableTransaction,
@@ -28,6 +32,19 @@ use fuel_tx::{
     Script,
 };
 
+mod estimation;
+mod simulation;
+
+pub use estimation::{
+    EstimateScriptGasError,
+    ScriptGasEstimate,
+};
+pub use simulation::{
+    FailedCheck,
+    Simulation,
+    SimulationOptions,
+};
+
 #[derive(Debug)]
 /// State machine to execute transactions and provide runtime entities on
 /// demand.
@@ -65,6 +82,7 @@ where
                 state,
                 self.interpreter.transaction(),
                 self.interpreter.receipts(),
+                self.interpreter.state_changes(),
             )
         })
     }
@@ -79,6 +97,7 @@ where
                 state,
                 self.interpreter.transaction().clone(),
                 self.interpreter.receipts().to_vec(),
+                self.interpreter.state_changes().clone(),
             )
         })
     }
@@ -123,6 +142,24 @@ where
         }
     }
 
+    /// Record the [`CallTree`] of the executed transactions.
+    ///
+    /// It installs a [`CallTracer`] as the execution hook of the interpreter,
+    /// replacing any previously installed hook.
+    pub fn with_call_tracer(&mut self) -> &mut Self {
+        self.interpreter.with_execution_hook(CallTracer::new());
+        self
+    }
+
+    /// Call tree of the last executed transaction.
+    ///
+    /// Will be `None` if call tracing wasn't enabled with [`Self::with_call_tracer`].
+    pub fn call_tree(&self) -> Option<&CallTree> {
+        self.interpreter
+            .execution_hook::<CallTracer>()
+            .map(CallTracer::tree)
+    }
+
     /// Gets the interpreter.
     pub fn interpreter(&self) -> &Interpreter<S, Tx> {
         &self.interpreter
@@ -184,6 +221,10 @@ where
 {
     /// Execute a transaction, and return the new state of the transactor
     pub fn transact(&mut self, tx: Checked<Tx>) -> &mut Self {
+        if let Some(tracer) = self.interpreter.execution_hook_mut::<CallTracer>() {
+            tracer.clear();
+        }
+
         match self.interpreter.transact(tx) {
             Ok(s) => {
                 self.program_state.replace(s.into());
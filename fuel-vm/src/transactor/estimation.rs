@@ -0,0 +1,190 @@
+//! Estimation of the gas limit of script transactions.
+
+use alloc::vec::Vec;
+
+use fuel_tx::{
+    field::{
+        GasLimit,
+        GasPrice,
+    },
+    CheckError,
+    Receipt,
+    Script,
+    ScriptExecutionResult,
+    TransactionFee,
+};
+use fuel_types::Word;
+
+use crate::{
+    checked_transaction::Checked,
+    error::InterpreterError,
+    interpreter::InterpreterParams,
+    pool::InterpreterPool,
+    storage::InterpreterStorage,
+};
+
+use super::Transactor;
+
+/// Smallest gas limit a script transaction succeeds with, as estimated by
+/// [`Transactor::estimate_script_gas`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptGasEstimate {
+    /// Smallest `gas_limit` the script succeeds with.
+    pub gas_limit: Word,
+    /// Fee of the transaction with the estimated gas limit.
+    pub fee: TransactionFee,
+    /// Receipts of the execution with the estimated gas limit.
+    pub receipts: Vec<Receipt>,
+}
+
+/// Reasons the gas limit of a script transaction can't be estimated.
+#[derive(Debug, derive_more::Display)]
+#[display(bound = "StorageError: core::fmt::Display")]
+pub enum EstimateScriptGasError<StorageError> {
+    /// The script doesn't succeed, even with the maximum gas limit of a transaction.
+    #[display(fmt = "The script failed with {result:?}")]
+    ScriptFailed {
+        /// Result of the execution with the maximum gas limit.
+        result: ScriptExecutionResult,
+        /// Receipts of the execution with the maximum gas limit.
+        receipts: Vec<Receipt>,
+    },
+    /// The transaction couldn't be executed.
+    #[display(fmt = "{_0}")]
+    Interpreter(InterpreterError<StorageError>),
+}
+
+impl<StorageError> From<InterpreterError<StorageError>>
+    for EstimateScriptGasError<StorageError>
+{
+    fn from(error: InterpreterError<StorageError>) -> Self {
+        Self::Interpreter(error)
+    }
+}
+
+impl<S> Transactor<S, Script>
+where
+    S: InterpreterStorage + Clone,
+{
+    /// Estimate the smallest `gas_limit` the script transaction succeeds with, and
+    /// the fee it is charged with that limit.
+    ///
+    /// The transaction is executed against copies of the storage, which is left
+    /// untouched. It is first executed with `max_gas_per_tx`, the largest limit the
+    /// chain accepts, then with increasing limits starting at the gas it used, since
+    /// the gas forwarded to contract calls is capped by the context gas
+    /// ([`CallFrame::context_gas`](crate::call::CallFrame::context_gas)). A call
+    /// forwarding a fraction of `$cgas` may therefore run out of gas even if the
+    /// limit covers the gas used by the first execution. The estimate never exceeds
+    /// `max_gas_per_tx`.
+    ///
+    /// Scripts are assumed to succeed with any limit above the smallest one, which
+    /// is searched by bisection. The trial executions use a zero gas price, so they
+    /// don't depend on the balance of the fee inputs.
+    pub fn estimate_script_gas(
+        &self,
+        checked: &Checked<Script>,
+        max_gas_per_tx: Word,
+    ) -> Result<ScriptGasEstimate, EstimateScriptGasError<S::DataError>> {
+        let mut estimator = Estimator {
+            storage: self.interpreter.as_ref(),
+            params: self.interpreter.interpreter_params(),
+            checked,
+            pool: InterpreterPool::with_max_idle(1),
+        };
+
+        let maximal = estimator.run(max_gas_per_tx)?;
+        if maximal.result != ScriptExecutionResult::Success {
+            return Err(EstimateScriptGasError::ScriptFailed {
+                result: maximal.result,
+                receipts: maximal.receipts,
+            })
+        }
+
+        // Limits up to `failing` are known to fail, and the `succeeding` one to succeed
+        let mut failing = maximal.gas_used.saturating_sub(1);
+        let mut succeeding = (max_gas_per_tx, maximal);
+
+        // Double the candidate limit until the script succeeds
+        let mut candidate = succeeding.1.gas_used.max(1);
+        while candidate < succeeding.0 {
+            let run = estimator.run(candidate)?;
+            if run.result == ScriptExecutionResult::Success {
+                succeeding = (candidate, run);
+                break
+            }
+            failing = candidate;
+            candidate = candidate.saturating_mul(2);
+        }
+
+        while succeeding.0 - failing > 1 {
+            let candidate = failing + (succeeding.0 - failing) / 2;
+            let run = estimator.run(candidate)?;
+            if run.result == ScriptExecutionResult::Success {
+                succeeding = (candidate, run);
+            } else {
+                failing = candidate;
+            }
+        }
+
+        let (gas_limit, run) = succeeding;
+        let mut tx = checked.transaction().clone();
+        *tx.gas_limit_mut() = gas_limit;
+        let fee = TransactionFee::checked_from_tx(self.interpreter.fee_params(), &tx)
+            .ok_or(InterpreterError::CheckError(CheckError::ArithmeticOverflow))?;
+
+        Ok(ScriptGasEstimate {
+            gas_limit,
+            fee,
+            receipts: run.receipts,
+        })
+    }
+}
+
+/// Outcome of a trial execution.
+struct Run {
+    result: ScriptExecutionResult,
+    gas_used: Word,
+    receipts: Vec<Receipt>,
+}
+
+/// Executes the transaction with different gas limits.
+struct Estimator<'a, S> {
+    storage: &'a S,
+    params: &'a InterpreterParams,
+    checked: &'a Checked<Script>,
+    pool: InterpreterPool<Script>,
+}
+
+impl<S> Estimator<'_, S>
+where
+    S: InterpreterStorage + Clone,
+{
+    fn run(&mut self, gas_limit: Word) -> Result<Run, InterpreterError<S::DataError>> {
+        let checks = self.checked.checks();
+        let (mut tx, metadata) = self.checked.clone().into();
+        *tx.gas_limit_mut() = gas_limit;
+        *tx.gas_price_mut() = 0;
+        let checked = Checked::new(tx, metadata, *checks);
+
+        let mut vm = self.pool.acquire(self.storage.clone(), self.params.clone());
+        let result = vm.transact(checked).map(|_| ());
+        let receipts = vm.receipts().to_vec();
+        self.pool.release(vm);
+        result?;
+
+        let (result, gas_used) = receipts
+            .iter()
+            .find_map(|receipt| match receipt {
+                Receipt::ScriptResult { result, gas_used } => Some((*result, *gas_used)),
+                _ => None,
+            })
+            .unwrap_or((ScriptExecutionResult::GenericFailure(0), gas_limit));
+
+        Ok(Run {
+            result,
+            gas_used,
+            receipts,
+        })
+    }
+}
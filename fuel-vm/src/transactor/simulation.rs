@@ -0,0 +1,160 @@
+//! Simulation of transactions that wouldn't pass the checks.
+
+use alloc::vec::Vec;
+
+use fuel_tx::{
+    field::Outputs,
+    CheckError,
+    ConsensusParameters,
+    Output,
+    Receipt,
+    Script,
+};
+use fuel_types::{
+    BlockHeight,
+    Word,
+};
+
+use crate::{
+    checked_transaction::{
+        script::into_checked_basic_with,
+        CheckPredicates,
+        Checked,
+        Checks,
+    },
+    error::InterpreterError,
+    interpreter::{
+        Interpreter,
+        InterpreterParams,
+    },
+    state::ProgramState,
+    storage::InterpreterStorage,
+};
+
+use super::Transactor;
+
+/// Relaxations of the checks applied by [`Transactor::simulate`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SimulationOptions {
+    /// Don't evaluate the predicates of the inputs.
+    pub skip_predicates: bool,
+    /// Virtually provide the amounts missing to cover the fee and the coin outputs,
+    /// instead of rejecting the transaction.
+    pub top_up_balances: bool,
+}
+
+/// A check a simulated transaction wouldn't have passed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailedCheck {
+    /// The failed check.
+    pub check: Checks,
+    /// The reason of the failure.
+    pub error: CheckError,
+}
+
+/// Outcome of a transaction executed by [`Transactor::simulate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Simulation {
+    /// The final state of the VM.
+    pub state: ProgramState,
+    /// Receipts produced by the execution.
+    pub receipts: Vec<Receipt>,
+    /// Gas used by the execution, as reported by the `ScriptResult` receipt. It
+    /// includes the gas used by the predicates if they were evaluated, since the
+    /// script starts with the gas limit minus the gas of the predicates.
+    pub gas_used: Word,
+    /// Outputs of the transaction after the execution, with the computed change and
+    /// variable outputs.
+    pub outputs: Vec<Output>,
+    /// Checks the transaction wouldn't have passed, in the order they were made.
+    pub failed_checks: Vec<FailedCheck>,
+}
+
+impl Simulation {
+    /// Returns true if the transaction would have passed all the checks that were
+    /// made.
+    pub fn passes_checks(&self) -> bool {
+        self.failed_checks.is_empty()
+    }
+}
+
+impl<S> Transactor<S, Script>
+where
+    S: InterpreterStorage + Clone,
+{
+    /// Execute a script transaction without requiring it to pass the signature
+    /// checks, against a copy of the storage.
+    ///
+    /// The signatures are verified, but a failure is only reported in
+    /// [`Simulation::failed_checks`], like the failures of the predicates and,
+    /// with [`SimulationOptions::top_up_balances`], of the balances. Errors of the
+    /// other basic checks are returned, since a malformed transaction can't be
+    /// executed. The storage of the transactor is never modified.
+    pub fn simulate(
+        &self,
+        tx: Script,
+        block_height: BlockHeight,
+        consensus_params: &ConsensusParameters,
+        options: SimulationOptions,
+    ) -> Result<Simulation, InterpreterError<S::DataError>> {
+        let mut failed_checks = Vec::new();
+        let mut fail = |check, error| failed_checks.push(FailedCheck { check, error });
+
+        let checked =
+            into_checked_basic_with(tx, block_height, consensus_params, |error| {
+                if !options.top_up_balances {
+                    return Err(error)
+                }
+                fail(Checks::Basic, error);
+                Ok(())
+            })?;
+
+        let checked = match checked.clone().check_signatures(&consensus_params.chain_id) {
+            Ok(checked) => checked,
+            Err(error) => {
+                fail(Checks::Signatures, error);
+                checked
+            }
+        };
+
+        let checked = if options.skip_predicates {
+            checked
+        } else {
+            match checked.clone().check_predicates(&consensus_params.into()) {
+                Ok(checked) => checked,
+                Err(error) => {
+                    fail(Checks::Predicates, error);
+                    checked
+                }
+            }
+        };
+
+        // The checks the transaction didn't pass are cleared from the bitmask
+        let mut checks = *checked.checks();
+        for failed in &failed_checks {
+            checks.remove(failed.check);
+        }
+        let (tx, metadata) = checked.into();
+        let checked = Checked::new(tx, metadata, checks);
+
+        let mut vm = Interpreter::<_, Script>::with_storage(
+            self.interpreter.as_ref().clone(),
+            InterpreterParams::from(consensus_params),
+        );
+        let state = *vm.transact(checked)?.state();
+
+        let receipts = vm.receipts().to_vec();
+        let gas_used = receipts
+            .iter()
+            .find_map(Receipt::gas_used)
+            .unwrap_or_default();
+
+        Ok(Simulation {
+            state,
+            receipts,
+            gas_used,
+            outputs: vm.transaction().outputs().to_vec(),
+            failed_checks,
+        })
+    }
+}